@@ -0,0 +1,83 @@
+/*
+ * C bindings of dynchess-lib, see ffi/src/lib.rs.
+ *
+ * A board is an opaque handle created by dynchess_board_new or
+ * dynchess_board_from_fen and freed with dynchess_board_free. Functions
+ * that can fail return one of the DYNCHESS_* codes. Blocks are numbered
+ * from a1 = 0 to h8 = 63, rank by rank.
+ */
+#ifndef DYNCHESS_H
+#define DYNCHESS_H
+
+#include <stddef.h>
+#include <stdint.h>
+
+#ifdef __cplusplus
+extern "C" {
+#endif
+
+#define DYNCHESS_OK                  0
+#define DYNCHESS_ERR_NULL           -1
+#define DYNCHESS_ERR_INVALID_STRING -2
+#define DYNCHESS_ERR_ILLEGAL_MOVE   -3
+#define DYNCHESS_ERR_GAME_OVER      -4
+#define DYNCHESS_ERR_NO_MOVE        -5
+
+#define DYNCHESS_STATE_ON            0
+#define DYNCHESS_STATE_STALEMATE     1
+#define DYNCHESS_STATE_CHECKMATE     2
+#define DYNCHESS_STATE_ABORTED       3
+#define DYNCHESS_STATE_VARIANT_WIN   4
+#define DYNCHESS_STATE_VARIANT_DRAW  5
+
+typedef struct DynChessBoard DynChessBoard;
+
+/*
+ * promotion is the lowercase FEN letter of the promotion or 0. For a drop
+ * drop is the uppercase FEN letter of the piece and from is the same as
+ * to, 0 otherwise.
+ */
+typedef struct DynChessMove {
+    uint8_t from;
+    uint8_t to;
+    char promotion;
+    char drop;
+} DynChessMove;
+
+DynChessBoard *dynchess_board_new(void);
+/* NULL if the FEN can't be read */
+DynChessBoard *dynchess_board_from_fen(const char *fen);
+void dynchess_board_free(DynChessBoard *board);
+
+/* Plays a move in UCI notation, like "e2e4" or "e7e8q" */
+int dynchess_board_make_move(DynChessBoard *board, const char *uci);
+/* Plays a move given by dynchess_board_legal_moves */
+int dynchess_board_play(DynChessBoard *board, DynChessMove move);
+/* Takes the last move back */
+int dynchess_board_undo(DynChessBoard *board);
+
+/* Writes up to len moves and returns how many there are */
+int dynchess_board_legal_moves(const DynChessBoard *board, DynChessMove *moves, size_t len);
+
+/*
+ * Write a NUL terminated string into buf and return its length like
+ * snprintf. Nothing is written if it doesn't fit, a return value of len or
+ * more means buf was too small.
+ */
+int dynchess_move_to_uci(const DynChessBoard *board, DynChessMove move, char *buf, size_t len);
+int dynchess_board_fen(const DynChessBoard *board, char *buf, size_t len);
+
+/* One of the DYNCHESS_STATE_* values */
+int dynchess_board_state(const DynChessBoard *board);
+/* 1 if white is to move, 0 for black */
+int dynchess_board_white_turn(const DynChessBoard *board);
+/* 1 if the color to move is in check */
+int dynchess_board_is_check(const DynChessBoard *board);
+/* 1 if white won, 2 if black won, 0 otherwise */
+int dynchess_board_winner(const DynChessBoard *board);
+
+#ifdef __cplusplus
+}
+#endif
+
+#endif
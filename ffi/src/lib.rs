@@ -0,0 +1,327 @@
+// C bindings of dynchess-lib, declared in `include/dynchess.h`. They are a
+// crate of their own so the library itself stays an `rlib` that builds
+// without `std`.
+//
+// A board is handed out as an opaque pointer that has to be freed with
+// `dynchess_board_free`. Functions that can fail return one of the
+// `DYNCHESS_*` codes, strings are NUL terminated UTF-8 and every pointer
+// has to be valid or NULL.
+//
+// The safety rules of the `unsafe` functions are the ones above
+#![allow(clippy::missing_safety_doc)]
+// Explicit `return` statements are the style of this crate
+#![allow(clippy::needless_return)]
+
+use dynchess_lib::*;
+use std::ffi::{c_char, c_int, CStr};
+
+#[cfg(test)]
+mod tests;
+
+pub const DYNCHESS_OK: c_int = 0;
+pub const DYNCHESS_ERR_NULL: c_int = -1;
+pub const DYNCHESS_ERR_INVALID_STRING: c_int = -2;
+pub const DYNCHESS_ERR_ILLEGAL_MOVE: c_int = -3;
+pub const DYNCHESS_ERR_GAME_OVER: c_int = -4;
+pub const DYNCHESS_ERR_NO_MOVE: c_int = -5;
+
+// The board behind the handle, with the boards before every move so they
+// can be taken back
+pub struct DynChessBoard
+{
+    board: ChessBoard,
+    history: Vec<ChessBoard>,
+}
+
+// A move as C sees it. `promotion` is the lowercase FEN letter of the
+// promotion or 0. For a drop `drop` is the uppercase FEN letter of the
+// piece and `from` is the same as `to`, 0 otherwise
+#[repr(C)]
+#[derive(Debug, PartialEq, Eq, Clone, Copy)]
+pub struct DynChessMove
+{
+    pub from: u8,
+    pub to: u8,
+    pub promotion: c_char,
+    pub drop: c_char,
+}
+
+impl DynChessMove
+{
+    fn __from_move(mv: ChessMove) -> DynChessMove
+    {
+        let letter = |_type: ChessPieceType, white: bool| -> c_char
+        {
+            return ChessPiece::from_type(_type, white).to_fen_char() as c_char;
+        };
+
+        return match mv
+        {
+            ChessMove::Drag { from, to, promotion } => DynChessMove { from, to, promotion: promotion.map_or(0, |t| letter(t, false)), drop: 0 },
+            ChessMove::Drop { piece, to } => DynChessMove { from: to, to, promotion: 0, drop: letter(piece, true) },
+        };
+    }
+
+    fn __to_move(self) -> Option<ChessMove>
+    {
+        let piece_type = |c: c_char| -> Option<ChessPieceType>
+        {
+            return (c as u8 as char).to_string().parse::<ChessPiece>().ok()?.get_type();
+        };
+
+        if self.drop != 0
+        {
+            return Some(ChessMove::Drop { piece: piece_type(self.drop)?, to: self.to });
+        }
+
+        let promotion: Option<ChessPieceType> = match self.promotion
+        {
+            0 => None,
+            c => Some(piece_type(c)?),
+        };
+        return Some(ChessMove::Drag { from: self.from, to: self.to, promotion });
+    }
+}
+
+impl DynChessBoard
+{
+    fn __play(&mut self, mv: ChessMove) -> c_int
+    {
+        if self.board.get_state() != ChessState::On
+        {
+            return DYNCHESS_ERR_GAME_OVER;
+        }
+
+        // A move from C can have any squares, `make_move` expects them on
+        // the board
+        if !self.board.legal_moves().contains(&mv)
+        {
+            return DYNCHESS_ERR_ILLEGAL_MOVE;
+        }
+
+        let before: ChessBoard = self.board.clone();
+        if !self.board.make_move(mv)
+        {
+            return DYNCHESS_ERR_ILLEGAL_MOVE;
+        }
+
+        self.history.push(before);
+        return DYNCHESS_OK;
+    }
+}
+
+unsafe fn __read_str<'a>(s: *const c_char) -> Option<&'a str>
+{
+    if s.is_null()
+    {
+        return None;
+    }
+
+    return CStr::from_ptr(s).to_str().ok();
+}
+
+// Copies the string into the buffer the way `snprintf` does: returns the
+// length of the string, and only writes it whole if it fits with its NUL
+unsafe fn __write_str(s: &str, buf: *mut c_char, len: usize) -> c_int
+{
+    if !buf.is_null() && len > s.len()
+    {
+        std::ptr::copy_nonoverlapping(s.as_ptr() as *const c_char, buf, s.len());
+        *buf.add(s.len()) = 0;
+    }
+    else if !buf.is_null() && len > 0
+    {
+        *buf = 0;
+    }
+
+    return s.len() as c_int;
+}
+
+// A board with the start position of default chess
+#[no_mangle]
+pub extern "C" fn dynchess_board_new() -> *mut DynChessBoard
+{
+    let handle: DynChessBoard = DynChessBoard { board: ChessBoard::init_position(), history: Vec::new() };
+    return Box::into_raw(Box::new(handle));
+}
+
+// A board from a FEN of default chess, NULL if it can't be read
+#[no_mangle]
+pub unsafe extern "C" fn dynchess_board_from_fen(fen: *const c_char) -> *mut DynChessBoard
+{
+    return match __read_str(fen).map(ChessBoard::from_fen)
+    {
+        Some(Ok(board)) => Box::into_raw(Box::new(DynChessBoard { board, history: Vec::new() })),
+        _ => std::ptr::null_mut(),
+    };
+}
+
+#[no_mangle]
+pub unsafe extern "C" fn dynchess_board_free(board: *mut DynChessBoard)
+{
+    if !board.is_null()
+    {
+        drop(Box::from_raw(board));
+    }
+}
+
+// Plays a move in UCI notation, like `e2e4` or `e7e8q`
+#[no_mangle]
+pub unsafe extern "C" fn dynchess_board_make_move(board: *mut DynChessBoard, uci: *const c_char) -> c_int
+{
+    let handle: &mut DynChessBoard = match board.as_mut()
+    {
+        Some(handle) => handle,
+        None => return DYNCHESS_ERR_NULL,
+    };
+
+    let uci: &str = match __read_str(uci)
+    {
+        Some(uci) => uci,
+        None => return if uci.is_null() { DYNCHESS_ERR_NULL } else { DYNCHESS_ERR_INVALID_STRING },
+    };
+
+    return match handle.board.from_uci(uci)
+    {
+        Some(mv) => handle.__play(mv),
+        None if handle.board.get_state() != ChessState::On => DYNCHESS_ERR_GAME_OVER,
+        None => DYNCHESS_ERR_ILLEGAL_MOVE,
+    };
+}
+
+// Plays a move given by `dynchess_board_legal_moves`
+#[no_mangle]
+pub unsafe extern "C" fn dynchess_board_play(board: *mut DynChessBoard, mv: DynChessMove) -> c_int
+{
+    let handle: &mut DynChessBoard = match board.as_mut()
+    {
+        Some(handle) => handle,
+        None => return DYNCHESS_ERR_NULL,
+    };
+
+    return match mv.__to_move()
+    {
+        Some(mv) => handle.__play(mv),
+        None => DYNCHESS_ERR_ILLEGAL_MOVE,
+    };
+}
+
+// Takes the last move back
+#[no_mangle]
+pub unsafe extern "C" fn dynchess_board_undo(board: *mut DynChessBoard) -> c_int
+{
+    let handle: &mut DynChessBoard = match board.as_mut()
+    {
+        Some(handle) => handle,
+        None => return DYNCHESS_ERR_NULL,
+    };
+
+    return match handle.history.pop()
+    {
+        Some(before) => { handle.board = before; DYNCHESS_OK },
+        None => DYNCHESS_ERR_NO_MOVE,
+    };
+}
+
+// Writes up to `len` legal moves into `moves` and returns how many there
+// are, which can be more than `len`
+#[no_mangle]
+pub unsafe extern "C" fn dynchess_board_legal_moves(board: *const DynChessBoard, moves: *mut DynChessMove, len: usize) -> c_int
+{
+    let handle: &DynChessBoard = match board.as_ref()
+    {
+        Some(handle) => handle,
+        None => return DYNCHESS_ERR_NULL,
+    };
+
+    let legal: Vec<ChessMove> = handle.board.clone().legal_moves();
+    if !moves.is_null()
+    {
+        for (i, mv) in legal.iter().take(len).enumerate()
+        {
+            *moves.add(i) = DynChessMove::__from_move(*mv);
+        }
+    }
+
+    return legal.len() as c_int;
+}
+
+// Writes the move in UCI notation into `buf`, see `dynchess_board_fen`
+#[no_mangle]
+pub unsafe extern "C" fn dynchess_move_to_uci(board: *const DynChessBoard, mv: DynChessMove, buf: *mut c_char, len: usize) -> c_int
+{
+    let handle: &DynChessBoard = match board.as_ref()
+    {
+        Some(handle) => handle,
+        None => return DYNCHESS_ERR_NULL,
+    };
+
+    return match mv.__to_move()
+    {
+        Some(mv) => __write_str(&handle.board.to_uci(mv), buf, len),
+        None => DYNCHESS_ERR_ILLEGAL_MOVE,
+    };
+}
+
+// Writes the FEN of the board into `buf` and returns its length without
+// the NUL. Nothing is written if it doesn't fit, so a return value of `len`
+// or more means the buffer was too small
+#[no_mangle]
+pub unsafe extern "C" fn dynchess_board_fen(board: *const DynChessBoard, buf: *mut c_char, len: usize) -> c_int
+{
+    return match board.as_ref()
+    {
+        Some(handle) => __write_str(&handle.board.to_fen(), buf, len),
+        None => DYNCHESS_ERR_NULL,
+    };
+}
+
+// The `ChessState` of the board: 0 on, 1 stalemate, 2 checkmate, 3
+// aborted, 4 won and 5 drawn by a rule of the variant
+#[no_mangle]
+pub unsafe extern "C" fn dynchess_board_state(board: *const DynChessBoard) -> c_int
+{
+    return match board.as_ref()
+    {
+        Some(handle) => handle.board.get_state() as c_int,
+        None => DYNCHESS_ERR_NULL,
+    };
+}
+
+// 1 if white is to move, 0 for black
+#[no_mangle]
+pub unsafe extern "C" fn dynchess_board_white_turn(board: *const DynChessBoard) -> c_int
+{
+    return match board.as_ref()
+    {
+        Some(handle) => handle.board.white_turn() as c_int,
+        None => DYNCHESS_ERR_NULL,
+    };
+}
+
+// 1 if the color to move is in check, 0 otherwise
+#[no_mangle]
+pub unsafe extern "C" fn dynchess_board_is_check(board: *const DynChessBoard) -> c_int
+{
+    return match board.as_ref()
+    {
+        Some(handle) => handle.board.is_check(handle.board.white_turn()) as c_int,
+        None => DYNCHESS_ERR_NULL,
+    };
+}
+
+// 1 if white won, 2 if black won, 0 while the game is on or for a draw
+#[no_mangle]
+pub unsafe extern "C" fn dynchess_board_winner(board: *const DynChessBoard) -> c_int
+{
+    return match board.as_ref()
+    {
+        Some(handle) => match handle.board.get_winner()
+        {
+            Some(true) => 1,
+            Some(false) => 2,
+            None => 0,
+        },
+        None => DYNCHESS_ERR_NULL,
+    };
+}
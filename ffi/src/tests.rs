@@ -0,0 +1,22 @@
+use super::*;
+
+#[test]
+fn test_ffi_from_rust()
+{
+    unsafe
+    {
+        let board: *mut DynChessBoard = dynchess_board_new();
+        let mut moves: [DynChessMove; 64] = [DynChessMove { from: 0, to: 0, promotion: 0, drop: 0 }; 64];
+        assert_eq!(dynchess_board_legal_moves(board, moves.as_mut_ptr(), moves.len()), 20);
+        assert_eq!(dynchess_board_play(board, moves[0]), DYNCHESS_OK);
+        assert_eq!(dynchess_board_white_turn(board), 0);
+        assert_eq!(dynchess_board_undo(board), DYNCHESS_OK);
+        assert_eq!(dynchess_board_undo(board), DYNCHESS_ERR_NO_MOVE);
+        dynchess_board_free(board);
+    }
+
+    let drop: ChessMove = ChessMove::Drop { piece: ChessPieceType::Knight, to: 0o25 };
+    assert_eq!(DynChessMove::__from_move(drop).__to_move(), Some(drop));
+    let promotion: ChessMove = ChessMove::Drag { from: 0o64, to: 0o74, promotion: Some(ChessPieceType::Rook) };
+    assert_eq!(DynChessMove::__from_move(promotion).__to_move(), Some(promotion));
+}
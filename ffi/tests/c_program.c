@@ -0,0 +1,61 @@
+/* Plays fool's mate through the C bindings, exits with the failed line */
+#include <stdio.h>
+#include <string.h>
+#include "dynchess.h"
+
+#define CHECK(cond) do { if (!(cond)) { fprintf(stderr, "line %d: %s\n", __LINE__, #cond); return __LINE__; } } while (0)
+
+int main(void)
+{
+    DynChessBoard *board = dynchess_board_new();
+    DynChessMove moves[256];
+    char buf[128];
+
+    CHECK(board != NULL);
+    CHECK(dynchess_board_legal_moves(board, moves, 256) == 20);
+    CHECK(dynchess_board_legal_moves(board, moves, 4) == 20);
+    CHECK(dynchess_move_to_uci(board, moves[0], buf, sizeof buf) == 4);
+
+    CHECK(dynchess_board_make_move(board, "f2f3") == DYNCHESS_OK);
+    CHECK(dynchess_board_make_move(board, "e2e4") == DYNCHESS_ERR_ILLEGAL_MOVE);
+    CHECK(dynchess_board_make_move(board, "e7e5") == DYNCHESS_OK);
+    CHECK(dynchess_board_make_move(board, "g2g4") == DYNCHESS_OK);
+
+    DynChessMove outside = { 200, 31, 0, 0 };
+    CHECK(dynchess_board_play(board, outside) == DYNCHESS_ERR_ILLEGAL_MOVE);
+    outside.from = 59;
+    outside.to = 200;
+    CHECK(dynchess_board_play(board, outside) == DYNCHESS_ERR_ILLEGAL_MOVE);
+
+    DynChessMove mate = { 59, 31, 0, 0 };
+    CHECK(dynchess_board_play(board, mate) == DYNCHESS_OK);
+    CHECK(dynchess_board_state(board) == DYNCHESS_STATE_CHECKMATE);
+    CHECK(dynchess_board_is_check(board) == 1);
+    CHECK(dynchess_board_winner(board) == 2);
+    CHECK(dynchess_board_make_move(board, "a2a3") == DYNCHESS_ERR_GAME_OVER);
+
+    CHECK(dynchess_board_fen(board, buf, 8) >= 8);
+    CHECK(buf[0] == '\0');
+    CHECK(dynchess_board_fen(board, buf, sizeof buf) < (int)sizeof buf);
+    CHECK(strcmp(buf, "rnb1kbnr/pppp1ppp/8/4p3/6Pq/5P2/PPPPP2P/RNBQKBNR w KQkq - 1 3") == 0);
+
+    CHECK(dynchess_board_undo(board) == DYNCHESS_OK);
+    CHECK(dynchess_board_state(board) == DYNCHESS_STATE_ON);
+    CHECK(dynchess_board_white_turn(board) == 0);
+    dynchess_board_free(board);
+
+    board = dynchess_board_from_fen("4k3/1P6/8/8/8/8/8/4K3 w - - 0 1");
+    CHECK(board != NULL);
+    CHECK(dynchess_board_make_move(board, "b7b8n") == DYNCHESS_OK);
+    CHECK(dynchess_board_fen(board, buf, sizeof buf) > 0);
+    CHECK(strncmp(buf, "1N2k3/", 6) == 0);
+    CHECK(dynchess_board_undo(board) == DYNCHESS_OK);
+    CHECK(dynchess_board_undo(board) == DYNCHESS_ERR_NO_MOVE);
+    dynchess_board_free(board);
+
+    CHECK(dynchess_board_from_fen("not a fen") == NULL);
+    CHECK(dynchess_board_make_move(NULL, "e2e4") == DYNCHESS_ERR_NULL);
+    dynchess_board_free(NULL);
+
+    return 0;
+}
@@ -0,0 +1,37 @@
+use std::path::{Path, PathBuf};
+use std::process::Command;
+
+// Builds `c_program.c` against the static library with the system compiler
+// and runs it
+#[test]
+fn test_ffi_from_c()
+{
+    let root: &Path = Path::new(env!("CARGO_MANIFEST_DIR"));
+    // The test binary and the library are both built in `target/<profile>/deps`
+    let exe: PathBuf = std::env::current_exe().unwrap();
+    let lib_dir: &Path = exe.parent().unwrap();
+    let lib: PathBuf = lib_dir.join("libdynchess_ffi.a");
+    if !lib.exists()
+    {
+        eprintln!("{} wasn't built, skipping", lib.display());
+        return;
+    }
+
+    let out: PathBuf = lib_dir.join("dynchess_ffi_test");
+    let compiled = Command::new(std::env::var("CC").unwrap_or(String::from("cc")))
+        .arg(root.join("tests/c_program.c"))
+        .arg("-I").arg(root.join("include"))
+        .arg(&lib)
+        .args(["-lpthread", "-ldl", "-lm", "-o"])
+        .arg(&out)
+        .status();
+
+    match compiled
+    {
+        Ok(status) => assert!(status.success(), "c_program.c doesn't compile"),
+        Err(e) => { eprintln!("No C compiler ({}), skipping", e); return; },
+    };
+
+    let run = Command::new(&out).output().unwrap();
+    assert!(run.status.success(), "{}", String::from_utf8_lossy(&run.stderr));
+}
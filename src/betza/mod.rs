@@ -0,0 +1,281 @@
+// Piece movement described in Betza notation, used for the fairy pieces.
+//
+// A description is a list of atoms, each being a single step that can be
+// made in every direction:
+//      W (1,0)  F (1,1)  D (2,0)  N (2,1)  A (2,2)
+//      H (3,0)  C (3,1)  Z (3,2)  G (3,3)
+// plus the shorthands K = WF, R = WW, B = FF and Q = RB.
+// Writing the atom twice (`NN`) makes it a rider that repeats the step
+// until it's blocked, a number after it (`W3`) limits how many steps.
+// Modifiers written before an atom change it:
+//      m   only moves, does not capture
+//      c   only captures
+//      g   hops over the first piece on its way and lands right behind it
+//      p   hops over the first piece on its way and may land anywhere behind it
+//      f b l r v s     only forward, backward, left, right, vertical or sideways
+//                      steps, `fl`, `fr`, `bl` and `br` pick a quarter
+// For example the archbishop is `BN` and the grasshopper is `gQ`.
+// Forward is seen from the side of the piece.
+//
+// The fairy piece types are a closed set: the archbishop, chancellor,
+// amazon, camel, zebra and grasshopper of `ChessPieceType`. A board can
+// give any of them another movement with `ChessBoard::register_piece`, but
+// a new kind of piece needs its own `ChessPieceType` and `ChessPiece`
+// values, its letter in `fen`, its value in `engine` and a place in the
+// piece list of `database`
+use super::*;
+
+#[cfg(test)]
+mod tests;
+
+// Reasons why a Betza description could not be used
+#[derive(Debug, PartialEq, Eq, Clone)]
+pub enum BetzaError
+{
+    UnknownAtom(char),
+    // The number of steps of a limited rider, from 1 to 255, like the `300`
+    // of `W300`
+    InvalidRange(String),
+    // Modifiers without an atom after them, or an empty description
+    MissingAtom,
+    // The default pieces have rules that can't be written in Betza
+    // (castling, en passant, pawn double steps)
+    DefaultPiece(ChessPieceType),
+}
+
+// What a step is allowed to do on the block it ends on
+#[derive(Debug, PartialEq, Eq, Clone, Copy)]
+pub enum BetzaMode
+{
+    Both,
+    Move,
+    Capture,
+}
+
+#[derive(Debug, PartialEq, Eq, Clone, Copy)]
+pub enum BetzaHop
+{
+    No,
+    // Lands right behind the hurdle
+    Grasshopper,
+    // Lands anywhere behind the hurdle
+    Cannon,
+}
+
+#[derive(Debug, PartialEq, Eq, Clone)]
+pub struct BetzaAtom
+{
+    // Directions of the step as seen from white, forward is +y
+    steps: Vec<(i8, i8)>,
+    // Maximum number of steps in a direction, 0 is unlimited
+    range: u8,
+    mode: BetzaMode,
+    hop: BetzaHop,
+}
+
+impl BetzaAtom
+{
+    pub fn get_steps(&self) -> &[(i8, i8)]
+    {
+        return &self.steps;
+    }
+
+    pub fn get_range(&self) -> u8
+    {
+        return self.range;
+    }
+
+    pub fn get_mode(&self) -> BetzaMode
+    {
+        return self.mode;
+    }
+
+    pub fn get_hop(&self) -> BetzaHop
+    {
+        return self.hop;
+    }
+}
+
+#[derive(Debug, PartialEq, Eq, Clone)]
+pub struct PieceMovement
+{
+    atoms: Vec<BetzaAtom>,
+}
+
+impl PieceMovement
+{
+    pub fn parse(betza: &str) -> Result<PieceMovement, BetzaError>
+    {
+        let chars: Vec<char> = betza.chars().collect();
+        let mut atoms: Vec<BetzaAtom> = Vec::new();
+
+        let mut mode: BetzaMode = BetzaMode::Both;
+        let mut hop: BetzaHop = BetzaHop::No;
+        let mut directions: Vec<String> = Vec::new();
+        let mut pending: bool = false;
+
+        let mut i: usize = 0;
+        while i < chars.len()
+        {
+            let c: char = chars[i];
+            i += 1;
+
+            match c
+            {
+                'm' => mode = BetzaMode::Move,
+                'c' => mode = BetzaMode::Capture,
+                'g' => hop = BetzaHop::Grasshopper,
+                'p' => hop = BetzaHop::Cannon,
+                'f' | 'b' | 'v' | 's' => directions.push(c.to_string()),
+                // Right after forward or backward it picks a quarter
+                'l' | 'r' => match directions.last_mut()
+                {
+                    Some(last) if (last == "f" || last == "b") && matches!(chars[i-2], 'f' | 'b') => last.push(c),
+                    _ => directions.push(c.to_string()),
+                },
+                _ =>
+                {
+                    let bases: Vec<((i8, i8), u8)> = match __atom(c)
+                    {
+                        Some(bases) => bases,
+                        None => return Err(BetzaError::UnknownAtom(c)),
+                    };
+
+                    // Doubled atom = unlimited rider, a number = limited rider
+                    let mut range: Option<u8> = None;
+                    if i < chars.len() && chars[i] == c
+                    {
+                        range = Some(0);
+                        i += 1;
+                    }
+                    else
+                    {
+                        let digits: String = chars[i..].iter().take_while(|d| d.is_ascii_digit()).collect();
+                        if !digits.is_empty()
+                        {
+                            i += digits.len();
+                            // 0 is the range of an unlimited rider, it can't be
+                            // written as a number
+                            match digits.parse::<u8>()
+                            {
+                                Ok(steps) if steps > 0 => range = Some(steps),
+                                _ => return Err(BetzaError::InvalidRange(digits)),
+                            };
+                        }
+                    }
+
+                    for (base, default_range) in bases
+                    {
+                        let steps: Vec<(i8, i8)> = __symmetric_steps(base).into_iter()
+                            .filter(|step| __in_directions(*step, &directions))
+                            .collect();
+
+                        atoms.push(BetzaAtom { steps, range: range.unwrap_or(default_range),
+                            mode, hop });
+                    }
+
+                    mode = BetzaMode::Both;
+                    hop = BetzaHop::No;
+                    directions.clear();
+                    pending = false;
+                    continue;
+                }
+            }
+
+            pending = true;
+        }
+
+        if pending || atoms.is_empty()
+        {
+            return Err(BetzaError::MissingAtom);
+        }
+
+        return Ok(PieceMovement { atoms });
+    }
+
+    pub fn get_atoms(&self) -> &[BetzaAtom]
+    {
+        return &self.atoms;
+    }
+
+    // Betza description of the fairy pieces. The default pieces have none
+    pub fn default_betza(_type: ChessPieceType) -> Option<&'static str>
+    {
+        return match _type
+        {
+            ChessPieceType::Archbishop =>   Some("BN"),
+            ChessPieceType::Chancellor =>   Some("RN"),
+            ChessPieceType::Amazon =>       Some("QN"),
+            ChessPieceType::Camel =>        Some("C"),
+            ChessPieceType::Zebra =>        Some("Z"),
+            ChessPieceType::Grasshopper =>  Some("gQ"),
+            _ => None,
+        };
+    }
+}
+
+// Base steps of an atom with their default range
+fn __atom(c: char) -> Option<Vec<((i8, i8), u8)>>
+{
+    let atom: Vec<((i8, i8), u8)> = match c
+    {
+        'W' => vec![((1, 0), 1)],
+        'F' => vec![((1, 1), 1)],
+        'D' => vec![((2, 0), 1)],
+        'N' => vec![((2, 1), 1)],
+        'A' => vec![((2, 2), 1)],
+        'H' => vec![((3, 0), 1)],
+        'C' => vec![((3, 1), 1)],
+        'Z' => vec![((3, 2), 1)],
+        'G' => vec![((3, 3), 1)],
+        'K' => vec![((1, 0), 1), ((1, 1), 1)],
+        'R' => vec![((1, 0), 0)],
+        'B' => vec![((1, 1), 0)],
+        'Q' => vec![((1, 0), 0), ((1, 1), 0)],
+        _ => return None,
+    };
+
+    return Some(atom);
+}
+
+// All 8 (or 4) directions of a step
+fn __symmetric_steps(base: (i8, i8)) -> Vec<(i8, i8)>
+{
+    let (a, b) = base;
+    let mut steps: Vec<(i8, i8)> = Vec::new();
+
+    for (x, y) in [(a, b), (b, a)]
+    {
+        for (sx, sy) in [(1, 1), (1, -1), (-1, 1), (-1, -1)]
+        {
+            let step: (i8, i8) = (x*sx, y*sy);
+            if !steps.contains(&step) { steps.push(step); }
+        }
+    }
+
+    return steps;
+}
+
+fn __in_directions(step: (i8, i8), directions: &[String]) -> bool
+{
+    let (dx, dy) = step;
+
+    if directions.is_empty()
+    {
+        return true;
+    }
+
+    return directions.iter().any(|d| match d.as_str()
+    {
+        "f" => dy > 0,
+        "b" => dy < 0,
+        "l" => dx < 0,
+        "r" => dx > 0,
+        "v" => dx == 0 || i8::abs(dy) > i8::abs(dx),
+        "s" => dy == 0 || i8::abs(dx) > i8::abs(dy),
+        "fl" => dy > 0 && dx < 0,
+        "fr" => dy > 0 && dx > 0,
+        "bl" => dy < 0 && dx < 0,
+        _ => dy < 0 && dx > 0,
+    });
+}
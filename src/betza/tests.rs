@@ -0,0 +1,78 @@
+use super::*;
+
+#[test]
+fn test_betza_atoms()
+{
+    // Leapers
+    let knight: PieceMovement = PieceMovement::parse("N").unwrap();
+    assert!(knight.get_atoms().len() == 1);
+    assert!(knight.get_atoms()[0].get_steps().len() == 8);
+    assert!(knight.get_atoms()[0].get_range() == 1);
+
+    let wazir: PieceMovement = PieceMovement::parse("W").unwrap();
+    assert!(wazir.get_atoms()[0].get_steps().len() == 4);
+
+    // Riders
+    let nightrider: PieceMovement = PieceMovement::parse("NN").unwrap();
+    assert!(nightrider.get_atoms()[0].get_range() == 0);
+    let short_rook: PieceMovement = PieceMovement::parse("W3").unwrap();
+    assert!(short_rook.get_atoms()[0].get_range() == 3);
+
+    // Shorthands
+    assert!(PieceMovement::parse("R").unwrap() == PieceMovement::parse("WW").unwrap());
+    assert!(PieceMovement::parse("Q").unwrap() == PieceMovement::parse("WWFF").unwrap());
+    assert!(PieceMovement::parse("K").unwrap() == PieceMovement::parse("WF").unwrap());
+
+    // Compound pieces
+    let archbishop: PieceMovement = PieceMovement::parse("BN").unwrap();
+    assert!(archbishop.get_atoms().len() == 2);
+}
+
+#[test]
+fn test_betza_modifiers()
+{
+    // A pawn without the double step
+    let pawn: PieceMovement = PieceMovement::parse("fmWfcF").unwrap();
+    let atoms: &[BetzaAtom] = pawn.get_atoms();
+    assert!(atoms[0].get_steps() == [(0, 1)]);
+    assert!(atoms[0].get_mode() == BetzaMode::Move);
+    let mut captures: Vec<(i8, i8)> = atoms[1].get_steps().to_vec();
+    captures.sort();
+    assert!(captures == [(-1, 1), (1, 1)]);
+    assert!(atoms[1].get_mode() == BetzaMode::Capture);
+
+    // Quarters
+    let atom: PieceMovement = PieceMovement::parse("frF").unwrap();
+    assert!(atom.get_atoms()[0].get_steps() == [(1, 1)]);
+
+    // Sideways and vertical
+    let mut steps: Vec<(i8, i8)> = PieceMovement::parse("sW").unwrap().get_atoms()[0].get_steps().to_vec();
+    steps.sort();
+    assert!(steps == [(-1, 0), (1, 0)]);
+    assert!(PieceMovement::parse("vN").unwrap().get_atoms()[0].get_steps().len() == 4);
+
+    // Hoppers
+    let grasshopper: PieceMovement = PieceMovement::parse("gQ").unwrap();
+    assert!(grasshopper.get_atoms().iter().all(|a| a.get_hop() == BetzaHop::Grasshopper));
+    let cannon: PieceMovement = PieceMovement::parse("mRcpR").unwrap();
+    assert!(cannon.get_atoms()[0].get_hop() == BetzaHop::No);
+    assert!(cannon.get_atoms()[1].get_hop() == BetzaHop::Cannon);
+}
+
+#[test]
+fn test_betza_errors()
+{
+    assert!(PieceMovement::parse("").err() == Some(BetzaError::MissingAtom));
+    assert!(PieceMovement::parse("Nm").err() == Some(BetzaError::MissingAtom));
+    assert!(PieceMovement::parse("X").err() == Some(BetzaError::UnknownAtom('X')));
+    assert!(PieceMovement::parse("W300").err() == Some(BetzaError::InvalidRange(String::from("300"))));
+    assert!(PieceMovement::parse("fR0").err() == Some(BetzaError::InvalidRange(String::from("0"))));
+    assert!(PieceMovement::parse("W255").unwrap().get_atoms()[0].get_range() == 255);
+
+    for _type in [ChessPieceType::Archbishop, ChessPieceType::Chancellor, ChessPieceType::Amazon,
+        ChessPieceType::Camel, ChessPieceType::Zebra, ChessPieceType::Grasshopper]
+    {
+        assert!(PieceMovement::parse(PieceMovement::default_betza(_type).unwrap()).is_ok());
+    }
+    assert!(PieceMovement::default_betza(ChessPieceType::Queen).is_none());
+}
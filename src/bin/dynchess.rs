@@ -0,0 +1,416 @@
+// Play chess in the terminal.
+//
+//      dynchess [--white human|engine] [--black human|engine]
+//               [--time 5+3] [--movetime 1000] [--variant atomic]
+//               [--fen <fen>] [--color] [--ascii]
+//
+// Moves are entered in SAN (`Nf3`) or UCI notation (`g1f3`), see `help`
+// for the other commands
+#![allow(clippy::needless_return)]
+
+use std::io::{self, BufRead, Write};
+use std::sync::Arc;
+use std::time::{Duration, Instant};
+
+use dynchess_lib::engine::{Engine, SearchLimit, SearchResult};
+use dynchess_lib::game::{Game, GameEnd};
+use dynchess_lib::variant::{self, Variant};
+use dynchess_lib::{ChessBoard, ChessMove, ChessPiece, ChessState};
+
+const HELP: &str = "\
+Moves are written in SAN (e4, Nf3, O-O, e8=Q, N@f3) or UCI (e2e4, e7e8q).
+Commands:
+    undo        take back your last move
+    flip        turn the board around
+    fen         print the position as FEN
+    pgn         print the game as PGN
+    hint        let the engine suggest a move
+    resign      give up the game
+    new         start a new game
+    help        show this text
+    quit        leave";
+
+#[derive(PartialEq, Clone, Copy)]
+enum Player
+{
+    Human,
+    Engine,
+}
+
+struct Options
+{
+    // White and black
+    players: [Player; 2],
+    // Time for the whole game and the increment per move
+    clock: Option<(Duration, Duration)>,
+    // Thinking time of the engine without a clock
+    movetime: Duration,
+    variant: Arc<dyn Variant>,
+    fen: Option<String>,
+    color: bool,
+    unicode: bool,
+}
+
+fn main()
+{
+    let options: Options = match parse_args(std::env::args().skip(1).collect())
+    {
+        Ok(options) => options,
+        Err(error) => {
+            eprintln!("dynchess: {}", error);
+            std::process::exit(2);
+        }
+    };
+
+    let mut game: Game = match new_game(&options)
+    {
+        Ok(game) => game,
+        Err(error) => {
+            eprintln!("dynchess: {}", error);
+            std::process::exit(2);
+        }
+    };
+
+    let mut clocks: [Duration; 2] = [options.clock.map_or(Duration::ZERO, |c| c.0); 2];
+    // Black at the bottom when only black is played by a human
+    let mut flipped: bool = options.players == [Player::Engine, Player::Human];
+    let mut engine: Engine = Engine::new();
+    let mut redraw: bool = true;
+    // When the color to move got its turn, commands that aren't moves
+    // don't stop its clock
+    let mut started: Instant = Instant::now();
+    let stdin = io::stdin();
+    let mut lines = stdin.lock().lines();
+
+    loop
+    {
+        let board: &ChessBoard = game.get_board();
+        let turn: usize = if board.white_turn() { 0 } else { 1 };
+
+        if !game.is_on() && redraw
+        {
+            println!("{}", render(board, flipped, &options));
+            println!("{} {}", game.result(), describe_end(&game));
+
+            // Nobody to ask what's next
+            if !options.players.contains(&Player::Human)
+            {
+                println!("\n{}", game.to_pgn());
+                return;
+            }
+        }
+        else if game.is_on() && options.players[turn] == Player::Engine
+        {
+            let limit: SearchLimit = engine_limit(&options, clocks[turn]);
+            let result: SearchResult = engine.search(board, limit);
+
+            let mv: ChessMove = match result.get_move()
+            {
+                Some(mv) => mv,
+                None => continue,
+            };
+
+            let mut copy: ChessBoard = board.clone();
+            let san: String = copy.to_san(mv);
+            println!("{} plays {} (depth {}, score {})", side_name(turn == 0), san, result.get_depth(), result.get_score());
+
+            if tick(&mut game, &mut clocks, &options, turn, started)
+            {
+                game.play(mv);
+            }
+            started = Instant::now();
+            redraw = true;
+            continue;
+        }
+        else if redraw
+        {
+            println!("{}", render(board, flipped, &options));
+            if options.clock.is_some()
+            {
+                println!("white {}  black {}", format_clock(clocks[0]), format_clock(clocks[1]));
+            }
+        }
+
+        let prompt: String = if game.is_on() { side_name(turn == 0).to_string() } else { String::from("game over") };
+        print!("{}> ", prompt);
+        io::stdout().flush().unwrap();
+
+        let line: String = match lines.next()
+        {
+            Some(Ok(line)) => line.trim().to_string(),
+            _ => return,
+        };
+
+        // Only commands that change what's on the board draw it again
+        redraw = matches!(line.as_str(), "flip" | "resign" | "new" | "undo");
+
+        match line.as_str()
+        {
+            "" => (),
+            "quit" | "exit" => return,
+            "help" => println!("{}", HELP),
+            "flip" => flipped = !flipped,
+            "fen" => println!("{}", game.get_board().to_fen()),
+            "pgn" => println!("{}", game.to_pgn()),
+            "resign" => game.resign(turn == 0),
+            "new" => {
+                game = new_game(&options).unwrap();
+                clocks = [options.clock.map_or(Duration::ZERO, |c| c.0); 2];
+                started = Instant::now();
+            },
+            "undo" => {
+                // Take back the engine's answer as well
+                game.undo();
+                while game.get_board().get_state() == ChessState::On
+                    && options.players[if game.get_board().white_turn() { 0 } else { 1 }] == Player::Engine
+                    && game.undo()
+                {
+                }
+                started = Instant::now();
+            },
+            "hint" => {
+                let result: SearchResult = engine.search(game.get_board(), SearchLimit::Time(Duration::from_millis(500)));
+                match result.get_move()
+                {
+                    Some(mv) => {
+                        let mut copy: ChessBoard = game.get_board().clone();
+                        println!("hint: {}", copy.to_san(mv));
+                    },
+                    None => println!("no moves left"),
+                }
+            },
+            _ => {
+                let mut copy: ChessBoard = game.get_board().clone();
+                let mv: Option<ChessMove> = copy.from_san(&line).or_else(|| copy.from_uci(&line));
+
+                match mv
+                {
+                    Some(mv) if game.is_on() => {
+                        if tick(&mut game, &mut clocks, &options, turn, started)
+                        {
+                            game.play(mv);
+                        }
+                        started = Instant::now();
+                        redraw = true;
+                    },
+                    Some(_) => println!("the game is over, try `new` or `undo`"),
+                    None => println!("unknown move or command `{}`, try `help`", line),
+                }
+            },
+        };
+    }
+}
+
+fn parse_args(args: Vec<String>) -> Result<Options, String>
+{
+    let mut options: Options = Options {
+        players: [Player::Human, Player::Engine],
+        clock: None,
+        movetime: Duration::from_millis(1000),
+        variant: Arc::new(variant::Standard),
+        fen: None,
+        color: false,
+        unicode: true,
+    };
+
+    let mut args = args.into_iter();
+    while let Some(arg) = args.next()
+    {
+        match arg.as_str()
+        {
+            "--color" => options.color = true,
+            "--ascii" => options.unicode = false,
+            "--help" | "-h" => {
+                println!("usage: dynchess [--white human|engine] [--black human|engine] [--time 5+3]");
+                println!("                [--movetime 1000] [--variant name] [--fen fen] [--color] [--ascii]\n");
+                println!("{}", HELP);
+                std::process::exit(0);
+            },
+            "--white" | "--black" | "--time" | "--movetime" | "--variant" | "--fen" => {
+                let value: String = args.next().ok_or(format!("missing value for {}", arg))?;
+                match arg.as_str()
+                {
+                    "--white" => options.players[0] = parse_player(&value)?,
+                    "--black" => options.players[1] = parse_player(&value)?,
+                    "--time" => options.clock = Some(parse_time_control(&value)?),
+                    "--movetime" => options.movetime = Duration::from_millis(value.parse().map_err(|_| format!("invalid movetime `{}`", value))?),
+                    "--variant" => options.variant = variant::from_name(&value).ok_or(format!("unknown variant `{}`", value))?,
+                    _ => options.fen = Some(value),
+                }
+            },
+            _ => return Err(format!("unknown argument `{}`, try --help", arg)),
+        }
+    }
+
+    return Ok(options);
+}
+
+fn parse_player(value: &str) -> Result<Player, String>
+{
+    return match value
+    {
+        "human" => Ok(Player::Human),
+        "engine" => Ok(Player::Engine),
+        _ => Err(format!("unknown player `{}`, use human or engine", value)),
+    };
+}
+
+// Minutes for the game and seconds of increment, like `5+3` or `10`
+fn parse_time_control(value: &str) -> Result<(Duration, Duration), String>
+{
+    let error: String = format!("invalid time control `{}`, use minutes+increment like 5+3", value);
+    let (base, increment) = value.split_once('+').unwrap_or((value, "0"));
+
+    let base: f64 = base.parse().map_err(|_| error.clone())?;
+    let increment: f64 = increment.parse().map_err(|_| error.clone())?;
+    if base <= 0.0 || increment < 0.0
+    {
+        return Err(error);
+    }
+
+    return Ok((Duration::from_secs_f64(base*60.0), Duration::from_secs_f64(increment)));
+}
+
+fn new_game(options: &Options) -> Result<Game, String>
+{
+    let board: ChessBoard = match &options.fen
+    {
+        Some(fen) => ChessBoard::from_fen_variant(fen, options.variant.clone()).map_err(|e| format!("invalid FEN: {:?}", e))?,
+        None => ChessBoard::init_variant(options.variant.clone()),
+    };
+
+    let mut game: Game = Game::from_board(board);
+    let names: [&str; 2] = options.players.map(|p| if p == Player::Human { "Human" } else { "dynchess" });
+    game.set_tag("Event", "Casual game");
+    game.set_tag("White", names[0]);
+    game.set_tag("Black", names[1]);
+
+    return Ok(game);
+}
+
+// How long the engine thinks, a part of the time left on its clock
+fn engine_limit(options: &Options, left: Duration) -> SearchLimit
+{
+    return match options.clock
+    {
+        Some((_, increment)) => SearchLimit::from_clock(left, increment),
+        None => SearchLimit::Time(options.movetime),
+    };
+}
+
+// Takes the time used for the move off the clock. Returns false if the
+// player ran out of time, which ends the game
+fn tick(game: &mut Game, clocks: &mut [Duration; 2], options: &Options, turn: usize, started: Instant) -> bool
+{
+    let (_, increment) = match options.clock
+    {
+        Some(clock) => clock,
+        None => return true,
+    };
+
+    let used: Duration = started.elapsed();
+    if used >= clocks[turn]
+    {
+        clocks[turn] = Duration::ZERO;
+        game.timeout(turn == 0);
+        return false;
+    }
+
+    clocks[turn] = clocks[turn] - used + increment;
+    return true;
+}
+
+fn format_clock(time: Duration) -> String
+{
+    let secs: u64 = time.as_secs();
+    return format!("{}:{:02}", secs/60, secs%60);
+}
+
+fn side_name(white: bool) -> &'static str
+{
+    return if white { "white" } else { "black" };
+}
+
+fn describe_end(game: &Game) -> String
+{
+    let board: &ChessBoard = game.get_board();
+    let winner: &str = match game.get_winner()
+    {
+        Some(white) => side_name(white),
+        None => "nobody",
+    };
+
+    return match (game.get_end(), board.get_state())
+    {
+        (Some(GameEnd::Resigned(white)), _) => format!("({} resigned)", side_name(white)),
+        (Some(GameEnd::Timeout(white)), _) => format!("({} ran out of time)", side_name(white)),
+        (Some(GameEnd::Repetition), _) => String::from("(threefold repetition)"),
+        (Some(GameEnd::FiftyMoves), _) => String::from("(fifty move rule)"),
+        (Some(GameEnd::Draw), _) => String::from("(draw agreed)"),
+        (None, ChessState::Checkmate) => format!("(checkmate, {} wins)", winner),
+        (None, ChessState::Stalemate) => String::from("(stalemate)"),
+        (None, ChessState::VariantWin) => format!("({} wins by the rules of {})", winner, board.get_variant().name()),
+        (None, _) => String::from("(draw)"),
+    };
+}
+
+fn render(board: &ChessBoard, flipped: bool, options: &Options) -> String
+{
+    let w: u8 = board.width();
+    let h: u8 = board.height();
+    let mut out: String = String::new();
+
+    let files: Vec<u8> = if flipped { (0..w).rev().collect() } else { (0..w).collect() };
+    let ranks: Vec<u8> = if flipped { (0..h).collect() } else { (0..h).rev().collect() };
+    let header: String = files.iter().map(|x| format!(" {} ", (b'a' + x) as char)).collect();
+
+    // The pockets are shown next to their players, black on top unless
+    // the board is flipped
+    out.push_str(&render_pocket(board, flipped));
+    out.push_str(&format!("    {}\n", header));
+    for y in ranks.iter()
+    {
+        out.push_str(&format!("{:>3} ", y+1));
+        for x in files.iter()
+        {
+            let coords: u8 = y*w + x;
+            let piece: String = piece_symbol(board.get_piece(coords), options.unicode);
+
+            if options.color
+            {
+                let light: bool = (x + y) % 2 == 1;
+                let bg: u8 = if light { 180 } else { 137 };
+                let fg: u8 = if board.get_piece(coords).is_white() { 231 } else { 16 };
+                let piece: String = if board.get_piece(coords).is_empty() { String::from(" ") } else { piece };
+                out.push_str(&format!("\x1b[48;5;{}m\x1b[38;5;{}m {} \x1b[0m", bg, fg, piece));
+            }
+            else
+            {
+                out.push_str(&format!(" {} ", piece));
+            }
+        }
+        out.push_str(&format!(" {}\n", y+1));
+    }
+    out.push_str(&format!("    {}\n", header));
+    out.push_str(&render_pocket(board, !flipped));
+
+    return out;
+}
+
+fn render_pocket(board: &ChessBoard, white: bool) -> String
+{
+    let pocket: String = board.get_pocket(white).iter()
+        .map(|t| piece_symbol(ChessPiece::from_type(*t, white), false)).collect::<Vec<String>>().join(" ");
+
+    if pocket.is_empty()
+    {
+        return String::new();
+    }
+
+    return format!("    [{}]\n", pocket);
+}
+
+fn piece_symbol(piece: ChessPiece, unicode: bool) -> String
+{
+    return if unicode { piece.to_unicode_char() } else { piece.to_fen_char() }.to_string();
+}
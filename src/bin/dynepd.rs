@@ -0,0 +1,72 @@
+// Run a test suite in EPD, like WAC or STS, with the built in engine.
+//
+//      dynepd <suite.epd> [--depth 6 | --time 1]
+//
+// Prints a line for every position as it's searched and the score of the
+// suite at the end
+#![allow(clippy::needless_return)]
+
+use std::time::Duration;
+
+use dynchess_lib::engine::{Engine, SearchLimit};
+use dynchess_lib::epd::{self, Epd, PositionReport, SuiteReport};
+
+const USAGE: &str = "\
+usage: dynepd <suite.epd> [--depth 6 | --time 1]
+
+    --depth     search every position this many half moves deep
+    --time      search every position for this many seconds, 1 by default";
+
+fn main()
+{
+    if let Err(error) = run(std::env::args().skip(1).collect())
+    {
+        eprintln!("{}", error);
+        std::process::exit(1);
+    }
+}
+
+fn run(args: Vec<String>) -> Result<(), String>
+{
+    let mut path: Option<String> = None;
+    let mut limit: SearchLimit = SearchLimit::Time(Duration::from_secs(1));
+
+    let mut args = args.into_iter();
+    while let Some(arg) = args.next()
+    {
+        match arg.as_str()
+        {
+            "--help" | "-h" => {
+                println!("{}", USAGE);
+                return Ok(());
+            },
+            "--depth" | "--time" => {
+                let value: String = args.next().ok_or(format!("missing value for {}", arg))?;
+                let invalid = || format!("invalid value `{}` for {}", value, arg);
+                limit = match arg.as_str()
+                {
+                    "--depth" => SearchLimit::Depth(value.parse().ok().filter(|depth| *depth > 0).ok_or_else(invalid)?),
+                    _ => SearchLimit::Time(value.parse().ok().and_then(|s| Duration::try_from_secs_f64(s).ok()).ok_or_else(invalid)?),
+                };
+            },
+            _ if path.is_none() && !arg.starts_with("--") => path = Some(arg),
+            _ => return Err(format!("unknown argument `{}`, try --help", arg)),
+        }
+    }
+
+    let path: String = path.ok_or(format!("no suite given\n\n{}", USAGE))?;
+    let text: String = std::fs::read_to_string(&path).map_err(|error| format!("can't read {}: {}", path, error))?;
+    let positions: Vec<Epd> = epd::read_epd(&text).map_err(|(line, error)| format!("{}:{}: {:?}", path, line, error))?;
+
+    let mut engine: Engine = Engine::new();
+    let mut report: SuiteReport = SuiteReport::new();
+    for (index, position) in positions.iter().enumerate()
+    {
+        let position: PositionReport = epd::run_position(&mut engine, position, index, limit);
+        println!("{}", position.to_text());
+        report.add(position);
+    }
+
+    println!("{}", report.summary());
+    return Ok(());
+}
@@ -0,0 +1,276 @@
+// Play a match between two engines.
+//
+//      dynmatch --engine <cmd> --engine <cmd> [--games 100] [--tc 10+0.1]
+//               [--book openings.epd] [--sprt 0,5] [--alpha 0.05] [--beta 0.05]
+//               [--resign 600,3] [--draw 10,8,40] [--tablebase] [--pgn out.pgn]
+//
+// An engine is the command of a UCI engine, with its arguments, or
+// `builtin` for the built in engine, optionally with a fixed depth like
+// `builtin:4`. The score and the Elo difference are those of the first
+// engine
+#![allow(clippy::needless_return)]
+
+use std::fs;
+use std::io::Write;
+
+use dynchess_lib::engine::SearchLimit;
+use dynchess_lib::game::Game;
+use dynchess_lib::match_runner::{self, Adjudication, EnginePlayer, Match, MatchScore, MaterialTablebase, Player, Sprt, SprtStatus, TimeControl, UciPlayer};
+use dynchess_lib::ChessBoard;
+
+const USAGE: &str = "\
+usage: dynmatch --engine <cmd> --engine <cmd> [--games 100] [--tc 10+0.1]
+                [--book file.epd|file.pgn] [--sprt elo0,elo1] [--alpha 0.05] [--beta 0.05]
+                [--resign score,moves] [--draw score,moves,after] [--tablebase] [--pgn file]
+
+    --engine    a UCI engine command, or `builtin` / `builtin:<depth>`
+    --tc        seconds for the game + seconds of increment
+    --book      openings in EPD or PGN, each played with both colors
+    --sprt      stop once the first engine is shown to gain at most elo0
+                or at least elo1
+    --resign    a player loses after `moves` moves at `score` centipawns down
+    --draw      a draw after `moves` moves of each player within `score`
+                centipawns, from move `after` on
+    --tablebase end games with a king against a king and one piece";
+
+struct Options
+{
+    engines: Vec<String>,
+    games: u32,
+    time: TimeControl,
+    book: Option<String>,
+    sprt: Option<(f64, f64)>,
+    alpha: f64,
+    beta: f64,
+    resign: Option<(i32, u16)>,
+    draw: Option<(i32, u16, u16)>,
+    tablebase: bool,
+    pgn: Option<String>,
+}
+
+fn main()
+{
+    let options: Options = match parse_args(std::env::args().skip(1).collect())
+    {
+        Ok(options) => options,
+        Err(error) => {
+            eprintln!("{}", error);
+            std::process::exit(1);
+        },
+    };
+
+    if let Err(error) = run(options)
+    {
+        eprintln!("{}", error);
+        std::process::exit(1);
+    }
+}
+
+fn run(options: Options) -> Result<(), String>
+{
+    let first: Box<dyn Player> = start_engine(&options.engines[0])?;
+    let second: Box<dyn Player> = start_engine(&options.engines[1])?;
+    let mut runner: Match = Match::new(first, second, options.time);
+
+    let mut adjudication: Adjudication = Adjudication::new();
+    if let Some((score, moves)) = options.resign
+    {
+        adjudication.set_resign(score, moves);
+    }
+    if let Some((score, moves, after)) = options.draw
+    {
+        adjudication.set_draw(score, moves, after);
+    }
+    if options.tablebase
+    {
+        adjudication.set_tablebase(Box::new(MaterialTablebase));
+    }
+    runner.set_adjudication(adjudication);
+
+    if let Some(path) = &options.book
+    {
+        runner.set_openings(read_book(path)?);
+    }
+
+    let mut pgn: Option<fs::File> = match &options.pgn
+    {
+        Some(path) => Some(fs::File::create(path).map_err(|e| format!("can't write {}: {}", path, e))?),
+        None => None,
+    };
+
+    let sprt: Option<Sprt> = options.sprt.map(|(elo0, elo1)| Sprt::new(elo0, elo1, options.alpha, options.beta));
+    let names: [String; 2] = runner.get_names().map(|name| name.to_string());
+
+    for round in 1..=options.games
+    {
+        let game: &Game = runner.play_game();
+        println!(
+            "Game {} ({} vs {}): {} {{{}}}",
+            round,
+            game.get_tag("White").unwrap_or("?"),
+            game.get_tag("Black").unwrap_or("?"),
+            game.result(),
+            describe_end(game),
+        );
+
+        if let Some(file) = pgn.as_mut()
+        {
+            writeln!(file, "{}\n", game.to_pgn()).map_err(|e| format!("can't write the games: {}", e))?;
+        }
+
+        let score: MatchScore = runner.get_score();
+        print_score(&names, &score);
+
+        if let Some(sprt) = &sprt
+        {
+            let (lower, upper) = sprt.bounds();
+            println!("SPRT: llr {:.2} ({:.2}, {:.2})", sprt.llr(&score), lower, upper);
+
+            match sprt.status(&score)
+            {
+                SprtStatus::AcceptH0 => { println!("H0 accepted"); break; },
+                SprtStatus::AcceptH1 => { println!("H1 accepted"); break; },
+                SprtStatus::Continue => {},
+            };
+        }
+    }
+
+    return Ok(());
+}
+
+fn print_score(names: &[String; 2], score: &MatchScore)
+{
+    println!(
+        "Score of {} vs {}: {} - {} - {} [{:.3}] {}",
+        names[0], names[1], score.get_wins(), score.get_losses(), score.get_draws(), score.ratio(), score.games(),
+    );
+
+    match match_runner::elo_difference(score)
+    {
+        Some((elo, margin)) => println!("Elo difference: {:.1} +/- {:.1}", elo, margin),
+        None => println!("Elo difference: unknown"),
+    };
+}
+
+fn describe_end(game: &Game) -> String
+{
+    let termination: &str = game.get_tag("Termination").unwrap_or("normal");
+    return format!("{}, {} moves", termination, game.get_moves().len().div_ceil(2));
+}
+
+// `builtin` and `builtin:<depth>` are the built in engine, anything else
+// is the command of a UCI engine
+fn start_engine(spec: &str) -> Result<Box<dyn Player>, String>
+{
+    if spec == "builtin"
+    {
+        return Ok(Box::new(EnginePlayer::new("dynchess", None)));
+    }
+    if let Some(depth) = spec.strip_prefix("builtin:")
+    {
+        let depth: u8 = depth.parse().map_err(|_| format!("invalid depth `{}`", depth))?;
+        return Ok(Box::new(EnginePlayer::new(&format!("dynchess depth {}", depth), Some(SearchLimit::Depth(depth)))));
+    }
+
+    let mut words = spec.split_whitespace();
+    let command: &str = words.next().ok_or("empty engine command")?;
+    let args: Vec<&str> = words.collect();
+    let player: UciPlayer = UciPlayer::start(command, &args).map_err(|e| format!("can't start `{}`: {}", spec, e))?;
+    return Ok(Box::new(player));
+}
+
+fn read_book(path: &str) -> Result<Vec<ChessBoard>, String>
+{
+    let text: String = fs::read_to_string(path).map_err(|e| format!("can't read {}: {}", path, e))?;
+    let openings: Vec<ChessBoard> = match path.to_lowercase().ends_with(".pgn")
+    {
+        true => match_runner::read_pgn_book(&text).map_err(|e| format!("invalid book {}: {:?}", path, e))?,
+        false => match_runner::read_epd_book(&text).map_err(|e| format!("invalid book {}: {:?}", path, e))?,
+    };
+
+    if openings.is_empty()
+    {
+        return Err(format!("the book {} has no openings", path));
+    }
+    return Ok(openings);
+}
+
+fn parse_args(args: Vec<String>) -> Result<Options, String>
+{
+    let mut options: Options = Options {
+        engines: Vec::new(),
+        games: 100,
+        time: TimeControl::parse("10+0.1").unwrap(),
+        book: None,
+        sprt: None,
+        alpha: 0.05,
+        beta: 0.05,
+        resign: None,
+        draw: None,
+        tablebase: false,
+        pgn: None,
+    };
+
+    let mut args = args.into_iter();
+    while let Some(arg) = args.next()
+    {
+        match arg.as_str()
+        {
+            "--tablebase" => options.tablebase = true,
+            "--help" | "-h" => {
+                println!("{}", USAGE);
+                std::process::exit(0);
+            },
+            "--engine" | "--games" | "--tc" | "--book" | "--sprt" | "--alpha" | "--beta" | "--resign" | "--draw" | "--pgn" => {
+                let value: String = args.next().ok_or(format!("missing value for {}", arg))?;
+                let invalid = || format!("invalid value `{}` for {}", value, arg);
+                match arg.as_str()
+                {
+                    "--engine" => options.engines.push(value.clone()),
+                    "--games" => options.games = value.parse().map_err(|_| invalid())?,
+                    "--tc" => options.time = TimeControl::parse(&value).ok_or_else(invalid)?,
+                    "--book" => options.book = Some(value.clone()),
+                    "--sprt" => options.sprt = Some(parse_list::<f64, 2>(&value).map(|v| (v[0], v[1])).ok_or_else(invalid)?),
+                    "--alpha" => options.alpha = value.parse().map_err(|_| invalid())?,
+                    "--beta" => options.beta = value.parse().map_err(|_| invalid())?,
+                    "--resign" => {
+                        let [score, moves] = parse_list::<i32, 2>(&value).ok_or_else(invalid)?;
+                        options.resign = Some((score, u16::try_from(moves).map_err(|_| invalid())?));
+                    },
+                    "--draw" => {
+                        let [score, moves, after] = parse_list::<i32, 3>(&value).ok_or_else(invalid)?;
+                        let moves: u16 = u16::try_from(moves).map_err(|_| invalid())?;
+                        let after: u16 = u16::try_from(after).map_err(|_| invalid())?;
+                        options.draw = Some((score, moves, after));
+                    },
+                    _ => options.pgn = Some(value.clone()),
+                }
+            },
+            _ => return Err(format!("unknown argument `{}`, try --help", arg)),
+        }
+    }
+
+    if options.engines.len() != 2
+    {
+        return Err(format!("two engines are needed\n\n{}", USAGE));
+    }
+    if !(0.0 < options.alpha && options.alpha < 1.0 && 0.0 < options.beta && options.beta < 1.0)
+    {
+        return Err("alpha and beta must be between 0 and 1".to_string());
+    }
+
+    return Ok(options);
+}
+
+// Exactly `N` values separated by commas, like `10,8,40`
+fn parse_list<T: std::str::FromStr + Copy + Default, const N: usize>(value: &str) -> Option<[T; N]>
+{
+    let mut list: [T; N] = [T::default(); N];
+    let mut parts = value.split(',');
+    for item in list.iter_mut()
+    {
+        *item = parts.next()?.trim().parse().ok()?;
+    }
+
+    return parts.next().is_none().then_some(list);
+}
@@ -0,0 +1,50 @@
+// Host games on the LAN.
+//
+//      dynserver [--address 0.0.0.0:7878]
+//
+// Clients connect over WebSocket or plain TCP, see the `server` module for
+// the messages
+#![allow(clippy::needless_return)]
+
+use dynchess_lib::server::Server;
+
+fn main()
+{
+    let mut address: String = String::from("0.0.0.0:7878");
+
+    let mut args = std::env::args().skip(1);
+    while let Some(arg) = args.next()
+    {
+        match arg.as_str()
+        {
+            "--address" => match args.next()
+            {
+                Some(value) => address = value,
+                None => fail("missing value for --address"),
+            },
+            "--help" | "-h" => {
+                println!("usage: dynserver [--address 0.0.0.0:7878]");
+                return;
+            },
+            _ => fail(&format!("unknown argument `{}`, try --help", arg)),
+        }
+    }
+
+    let server: Server = match Server::bind(&address)
+    {
+        Ok(server) => server,
+        Err(error) => fail(&format!("can't listen on {}: {}", address, error)),
+    };
+
+    println!("listening on {}", address);
+    if let Err(error) = server.run()
+    {
+        fail(&format!("the server stopped: {}", error));
+    }
+}
+
+fn fail(message: &str) -> !
+{
+    eprintln!("{}", message);
+    std::process::exit(1);
+}
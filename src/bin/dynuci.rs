@@ -0,0 +1,17 @@
+// The built in engine as a UCI engine, for GUIs and match runners.
+//
+//      dynuci
+//
+// Commands are read from the standard input, see `engine::uci`. The
+// number of search threads is set with the `Threads` option
+#![allow(clippy::needless_return)]
+
+use std::io;
+
+use dynchess_lib::engine::uci::UciEngine;
+
+fn main()
+{
+    let mut engine: UciEngine<io::Stdout> = UciEngine::new(io::stdout());
+    engine.run(io::stdin().lock());
+}
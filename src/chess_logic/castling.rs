@@ -0,0 +1,252 @@
+use super::*;
+
+// Knight placements of the Chess960 numbering scheme, as indices among the
+// 5 blocks that are left after placing the bishops and the queen
+const KNIGHT_TABLE: [(usize, usize); 10] = [(0, 1), (0, 2), (0, 3), (0, 4), (1, 2),
+                                            (1, 3), (1, 4), (2, 3), (2, 4), (3, 4)];
+
+impl ChessBoard
+{
+    // Initialize a Chess960 (Fischer Random) start position from its index,
+    // 0 to 959. Index 518 is the default chess position.
+    // The board is set to chess960 mode, so castling is done by dragging the
+    // king onto its own rook
+    pub fn init_chess960(index: u16) -> ChessBoard
+    {
+        let mut chess_board: ChessBoard = Self::init_from_rank(&Self::chess960_rank(index), 8);
+        chess_board._chess960 = true;
+
+        return chess_board;
+    }
+
+    // Get the order of the pieces on the first rank for the Chess960 start
+    // position with the given index
+    pub fn chess960_rank(index: u16) -> [ChessPieceType; 8]
+    {
+        assert!(index < 960, "Chess960 position index outside of bounds");
+
+        let mut rank: [Option<ChessPieceType>; 8] = [None; 8];
+        let mut n: usize = index as usize;
+
+        // Bishops on the light and the dark blocks
+        rank[2*(n%4)+1] = Some(ChessPieceType::Bishop);
+        n /= 4;
+        rank[2*(n%4)] = Some(ChessPieceType::Bishop);
+        n /= 4;
+
+        // The queen on one of the 6 blocks that are left
+        let queen: usize = Self::__nth_empty(&rank, n%6);
+        rank[queen] = Some(ChessPieceType::Queen);
+        n /= 6;
+
+        // Both knights are picked before placing them, since placing the first
+        // one changes which blocks are empty
+        let (k1, k2) = KNIGHT_TABLE[n];
+        let knight1: usize = Self::__nth_empty(&rank, k1);
+        let knight2: usize = Self::__nth_empty(&rank, k2);
+        rank[knight1] = Some(ChessPieceType::Knight);
+        rank[knight2] = Some(ChessPieceType::Knight);
+
+        // The king always ends up between the rooks
+        for _type in [ChessPieceType::Rook, ChessPieceType::King, ChessPieceType::Rook]
+        {
+            let i: usize = Self::__nth_empty(&rank, 0);
+            rank[i] = Some(_type);
+        }
+
+        return rank.map(|_type| _type.unwrap());
+    }
+
+    fn __nth_empty(rank: &[Option<ChessPieceType>; 8], n: usize) -> usize
+    {
+        return (0..8).filter(|i| rank[*i].is_none()).nth(n).unwrap();
+    }
+
+    // If chess960 mode is on, castling is done by dragging the king onto
+    // the rook it castles with. Otherwise the king can also be dragged to
+    // its destination (g or c file), like in the default chess
+    pub fn set_chess960(&mut self, chess960: bool)
+    {
+        self._chess960 = chess960;
+    }
+
+    pub fn is_chess960(&self) -> bool
+    {
+        return self._chess960;
+    }
+
+    // Get the coordinates of the rooks that can still castle, in the order
+    // white king side, white queen side, black king side, black queen side
+    pub fn castling_rights(&self) -> [Option<u8>; 4]
+    {
+        return self._castling;
+    }
+
+    // Checks if dragging the piece from `from` to `to` is meant as castling
+    // and which castling right it would use
+    pub(crate) fn __castling_side(&self, from: u8, to: u8) -> Option<usize>
+    {
+        let from_el: ChessPiece = self.board[from as usize];
+        let sides: [usize; 2] = match from_el
+        {
+            ChessPiece::WKing => [0, 1],
+            ChessPiece::BKing => [2, 3],
+            _ => return None,
+        };
+
+        // King takes own rook
+        for side in sides
+        {
+            if self._castling[side] == Some(to) { return Some(side); }
+        }
+
+        if self._chess960
+        {
+            return None;
+        }
+
+        // The king moves two steps or more towards the g or c file. A single
+        // step would be a normal king move
+        let f: ChessPos = self.__pos(from);
+        let t: ChessPos = self.__pos(to);
+        if f.y != t.y || i8::abs(f.x as i8 - t.x as i8) < 2
+        {
+            return None;
+        }
+
+        if t.x == self._width-2
+        {
+            return Some(sides[0]).filter(|side| self._castling[*side].is_some());
+        }
+
+        if t.x == 2
+        {
+            return Some(sides[1]).filter(|side| self._castling[*side].is_some());
+        }
+
+        return None;
+    }
+
+    // Checks if castling with the given right is allowed: it's the color's
+    // turn, the blocks the king and the rook pass are empty and the king
+    // isn't in check before, during or after castling
+    pub(super) fn __can_castle(&mut self, side: usize) -> bool
+    {
+        let rook: u8 = match self._castling[side]
+        {
+            Some(rook) => rook,
+            None => return false,
+        };
+
+        let white: bool = side < 2;
+        if white == self.w_lock
+        {
+            return false;
+        }
+
+        let king: u8 = if white { self._w_king } else { self._b_king };
+        let (k_dest, r_dest) = self.__castling_destinations(side);
+
+        let k_x: u8 = self.__pos(king).x;
+        let r_x: u8 = self.__pos(rook).x;
+        let k_dest_x: u8 = self.__pos(k_dest).x;
+        let r_dest_x: u8 = self.__pos(r_dest).x;
+        let y: u8 = self.__pos(king).y;
+
+        // Take both pieces off the board while checking, they don't block
+        // each other and the rook can't shield the king from an attack
+        let (king_el, rook_el) = (self.board[king as usize], self.board[rook as usize]);
+        self.board[king as usize] = ChessPiece::Empty;
+        self.board[rook as usize] = ChessPiece::Empty;
+
+        let mut allowed: bool = true;
+        for x in u8::min(k_x, k_dest_x)..=u8::max(k_x, k_dest_x)
+        {
+            let coords: u8 = self.__conv(x, y);
+            allowed &= self.board[coords as usize].is_empty();
+            allowed &= self.__is_threatened(coords, white) == self.__size();
+        }
+
+        for x in u8::min(r_x, r_dest_x)..=u8::max(r_x, r_dest_x)
+        {
+            allowed &= self.board[self.__conv(x, y) as usize].is_empty();
+        }
+
+        self.board[king as usize] = king_el;
+        self.board[rook as usize] = rook_el;
+
+        return allowed;
+    }
+
+    // Moves the king and the rook to their places after castling
+    pub(super) fn __castle(&mut self, side: usize)
+    {
+        let white: bool = side < 2;
+        let rook: u8 = self._castling[side].unwrap();
+        let king: u8 = if white { self._w_king } else { self._b_king };
+        let (k_dest, r_dest) = self.__castling_destinations(side);
+
+        let _king: ChessPiece = self.board[king as usize];
+        let _rook: ChessPiece = self.board[rook as usize];
+
+        self.board[king as usize] = ChessPiece::Empty;
+        self.board[rook as usize] = ChessPiece::Empty;
+        self.board[k_dest as usize] = _king;
+        self.board[r_dest as usize] = _rook;
+
+        if white
+        {
+            self._w_king = k_dest;
+            self._castling[0] = None;
+            self._castling[1] = None;
+        }
+        else
+        {
+            self._b_king = k_dest;
+            self._castling[2] = None;
+            self._castling[3] = None;
+        }
+    }
+
+    // Takes away the castling rights lost by a (non castling) move
+    pub(super) fn __update_castling(&mut self, from: u8, to: u8)
+    {
+        for side in 0..4
+        {
+            // A castling rook moved or was taken
+            if self._castling[side] == Some(from) || self._castling[side] == Some(to)
+            {
+                self._castling[side] = None;
+            }
+        }
+
+        // The king moved
+        if self.board[to as usize] == ChessPiece::WKing
+        {
+            self._castling[0] = None;
+            self._castling[1] = None;
+        }
+
+        if self.board[to as usize] == ChessPiece::BKing
+        {
+            self._castling[2] = None;
+            self._castling[3] = None;
+        }
+    }
+
+    // Where the king and the rook end up after castling, no matter where
+    // they started: g and f file on the king side, c and d file on the
+    // queen side. On wider boards the king side files are counted from the
+    // last file instead
+    pub(super) fn __castling_destinations(&self, side: usize) -> (u8, u8)
+    {
+        let y: u8 = if side < 2 { 0 } else { self._height-1 };
+
+        if side == 0 || side == 2
+        {
+            return (self.__conv(self._width-2, y), self.__conv(self._width-3, y));
+        }
+
+        return (self.__conv(2, y), self.__conv(3, y));
+    }
+}
@@ -0,0 +1,148 @@
+use super::*;
+
+impl ChessBoard
+{
+    // Get the pieces white or black can drop, sorted by type
+    // Public
+    pub fn get_pocket(&self, white: bool) -> &[ChessPieceType]
+    {
+        return &self._pockets[if white { 0 } else { 1 }];
+    }
+
+    // Puts a piece into the pocket of the color
+    pub fn add_to_pocket(&mut self, white: bool, _type: ChessPieceType)
+    {
+        let pocket: &mut Vec<ChessPieceType> = &mut self._pockets[if white { 0 } else { 1 }];
+        pocket.push(_type);
+        pocket.sort();
+    }
+
+    // Takes a piece out of the pocket of the color, false if there's none
+    pub fn take_from_pocket(&mut self, white: bool, _type: ChessPieceType) -> bool
+    {
+        let pocket: &mut Vec<ChessPieceType> = &mut self._pockets[if white { 0 } else { 1 }];
+        match pocket.iter().position(|t| *t == _type)
+        {
+            Some(i) => { pocket.remove(i); return true; },
+            None => return false,
+        };
+    }
+
+    // If the block is taken by a piece that was promoted from a pawn
+    // Public
+    pub fn is_promoted(&self, coords: u8) -> bool
+    {
+        return self._promoted[coords as usize];
+    }
+
+    // Drops a piece of the given type from the pocket of the color that is
+    // about to move onto the empty block `to`, like `N@f3`.
+    // Just like `ChessBoard::drag`, illegal drops are ignored
+    pub fn drop_piece(&mut self, _type: ChessPieceType, to: u8)
+    {
+        if self._state != ChessState::On || !self.__is_legal_drop(_type, to)
+        {
+            return;
+        }
+
+        let white: bool = self.white_turn();
+        let _el: ChessPiece = ChessPiece::from_type(_type, white);
+
+        self.take_from_pocket(white, _type);
+        self.board[to as usize] = _el;
+        self._piece_count[_el as usize] += 1;
+
+        self._halfmove_clock += 1;
+        self._en_passant = None;
+        self.__end_turn();
+    }
+
+    // Checks if the piece can be dropped onto `to` by the color that is
+    // about to move. The block has to be empty, pawns can't be dropped on
+    // the first or the last rank and the own king can't be left in check
+    pub(crate) fn __is_legal_drop(&mut self, _type: ChessPieceType, to: u8) -> bool
+    {
+        let white: bool = self.white_turn();
+
+        if !self._variant.has_pockets() || !self.get_pocket(white).contains(&_type)
+        {
+            return false;
+        }
+
+        if !self.board[to as usize].is_empty()
+        {
+            return false;
+        }
+
+        let y: u8 = self.__pos(to).y;
+        if _type == ChessPieceType::Pawn && (y == 0 || y == self._height-1)
+        {
+            return false;
+        }
+
+        if !self._variant.has_king(white)
+        {
+            return true;
+        }
+
+        self.board[to as usize] = ChessPiece::from_type(_type, white);
+        let safe: bool = !self.is_check(white);
+        self.board[to as usize] = ChessPiece::Empty;
+
+        return safe;
+    }
+
+    // Checks if the color that is about to move can drop any piece
+    pub(super) fn __has_legal_drop(&mut self) -> bool
+    {
+        let mut found: bool = false;
+        self.__for_each_drop_type(|board, _type| {
+            found = (0..board.__size()).any(|to| board.__is_legal_drop(_type, to));
+            return !found;
+        });
+
+        return found;
+    }
+
+    // Calls `f` once for every type in the pocket of the color to move,
+    // until it returns false. The pocket is sorted, so every type is only
+    // looked at once without copying it
+    pub(super) fn __for_each_drop_type(&mut self, mut f: impl FnMut(&mut ChessBoard, ChessPieceType) -> bool)
+    {
+        let color: usize = if self.white_turn() { 0 } else { 1 };
+        for i in 0..self._pockets[color].len()
+        {
+            let _type: ChessPieceType = self._pockets[color][i];
+            if i > 0 && self._pockets[color][i-1] == _type { continue; }
+
+            if !f(self, _type) { return; }
+        }
+    }
+
+    // Reads a drop written like `N@f3`, or `@f3` for a pawn
+    pub fn read_drop(&self, drop: &str) -> Option<(ChessPieceType, u8)>
+    {
+        let (piece, coords) = drop.split_once('@')?;
+
+        // Pieces are written as white ones, no matter who drops them
+        if !piece.chars().all(|c| c.is_ascii_uppercase()) || piece.chars().count() > 1
+        {
+            return None;
+        }
+
+        let _type: ChessPieceType = match piece.chars().next()
+        {
+            None => ChessPieceType::Pawn,
+            Some(c) => fen::__char_to_piece(c)?.get_type()?,
+        };
+
+        return Some((_type, self.read_coords(coords)?));
+    }
+
+    // Writes a drop like `N@f3`
+    pub fn write_drop(&self, _type: ChessPieceType, to: u8) -> String
+    {
+        let piece: char = fen::__piece_to_char(ChessPiece::from_type(_type, true));
+        return format!("{}@{}", piece, self.write_coords(to));
+    }
+}
@@ -0,0 +1,141 @@
+use super::*;
+
+impl ChessBoard
+{
+    // Get the block a pawn passed over with the two step move just made,
+    // where an enemy pawn can take it en passant. It's only kept while
+    // such a capture is legal, so positions that only differ by a capture
+    // nobody can make are the same position, in FEN and in `hash` as well
+    // Public
+    pub fn get_en_passant(&self) -> Option<u8>
+    {
+        return self._en_passant;
+    }
+
+    // Checks if the move from `from` to `to` takes a piece, a pawn taken
+    // en passant included. The move itself is expected to be verified
+    // Public
+    pub fn is_capture(&self, from: u8, to: u8) -> bool
+    {
+        return !self.board[to as usize].is_empty() || self.__en_passant_victim(from, to).is_some();
+    }
+
+    // The block of the pawn taken if the move from `from` to `to` is a
+    // capture en passant: a pawn taking a diagonal step onto the block the
+    // enemy pawn passed over
+    pub(crate) fn __en_passant_victim(&self, from: u8, to: u8) -> Option<u8>
+    {
+        if self._en_passant != Some(to)
+        {
+            return None;
+        }
+
+        let from_el: ChessPiece = self.board[from as usize];
+        let f: ChessPos = self.__pos(from);
+        let t: ChessPos = self.__pos(to);
+
+        let _forward: bool = match from_el
+        {
+            ChessPiece::WPawn => t.y == f.y+1,
+            ChessPiece::BPawn => t.y+1 == f.y,
+            _ => false,
+        };
+        if !_forward || f.x.abs_diff(t.x) != 1
+        {
+            return None;
+        }
+
+        // The pawn that took two steps stands next to the one taking it
+        let victim: u8 = self.__conv(t.x, f.y);
+        let _pawn: ChessPiece = if from_el.is_white() { ChessPiece::BPawn } else { ChessPiece::WPawn };
+
+        return (self.board[victim as usize] == _pawn).then_some(victim);
+    }
+
+    // The block passed over if the move from `from` to `to` is a pawn
+    // taking two steps from its start rank. The two steps of the white
+    // pawns on the first rank in horde can't be taken en passant
+    pub(crate) fn __double_step(&self, from: u8, to: u8) -> Option<u8>
+    {
+        let from_el: ChessPiece = self.board[from as usize];
+        let f: ChessPos = self.__pos(from);
+        let t: ChessPos = self.__pos(to);
+
+        let _double: bool = match from_el
+        {
+            ChessPiece::WPawn => f.y == 1 && t.y == 3,
+            ChessPiece::BPawn => f.y == self._height-2 && t.y+2 == f.y,
+            _ => false,
+        };
+        if !_double || f.x != t.x
+        {
+            return None;
+        }
+
+        return Some(self.__conv(f.x, (f.y + t.y)/2));
+    }
+
+    // Forgets the en passant block unless the color that is about to move
+    // can take the pawn on it legally. Called once the turn is handed over
+    pub(crate) fn __update_en_passant(&mut self)
+    {
+        let target: u8 = match self._en_passant
+        {
+            Some(target) => target,
+            None => return,
+        };
+
+        // The pawns that could take it stand next to the one that moved
+        let t: ChessPos = self.__pos(target);
+        let y: u8 = if self.w_lock { t.y+1 } else { t.y-1 };
+        let _pawn: ChessPiece = if self.w_lock { ChessPiece::BPawn } else { ChessPiece::WPawn };
+
+        let mut legal: bool = false;
+        for x in [t.x.wrapping_sub(1), t.x+1]
+        {
+            if x >= self._width { continue; }
+
+            let from: u8 = self.__conv(x, y);
+            legal |= self.board[from as usize] == _pawn && self.__is_legal(from, target);
+        }
+
+        if !legal
+        {
+            self._en_passant = None;
+        }
+    }
+
+    // Reads the en passant field of FEN. The block has to be the one a
+    // pawn of the color that just moved passed over, with that pawn in
+    // front of it and nothing behind it
+    pub(crate) fn __read_en_passant(&mut self, field: &str) -> bool
+    {
+        if field == "-"
+        {
+            return true;
+        }
+
+        let target: u8 = match self.read_coords(field)
+        {
+            Some(target) => target,
+            None => return false,
+        };
+
+        let t: ChessPos = self.__pos(target);
+        // Black to move after a white pawn took two steps
+        let (rank, pawn_y, start_y, _pawn) = match self.w_lock
+        {
+            true => (2, 3, 1, ChessPiece::WPawn),
+            false => (self._height-3, self._height-4, self._height-2, ChessPiece::BPawn),
+        };
+
+        let _valid: bool = t.y == rank && self.board[self.__conv(t.x, pawn_y) as usize] == _pawn
+            && self.board[target as usize].is_empty() && self.board[self.__conv(t.x, start_y) as usize].is_empty();
+        if _valid
+        {
+            self._en_passant = Some(target);
+        }
+
+        return _valid;
+    }
+}
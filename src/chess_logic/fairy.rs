@@ -0,0 +1,110 @@
+use super::*;
+use betza::{BetzaError, BetzaHop, BetzaMode, PieceMovement};
+
+impl ChessBoard
+{
+    // Defines how the pieces of a fairy type move on this board, in Betza
+    // notation (see `betza`). Replaces the default movement of the type.
+    // Only the six fairy types of `ChessPieceType` can be registered, there
+    // is no way to add another type
+    pub fn register_piece(&mut self, _type: ChessPieceType, betza: &str) -> Result<(), BetzaError>
+    {
+        if !_type.is_fairy()
+        {
+            return Err(BetzaError::DefaultPiece(_type));
+        }
+
+        let movement: PieceMovement = PieceMovement::parse(betza)?;
+        self._movements.insert(_type, movement);
+        // The hash doesn't tell the movements apart
+        self._can_capture = None;
+
+        return Ok(());
+    }
+
+    // Get the movement of a fairy piece type
+    pub fn get_movement(&self, _type: ChessPieceType) -> Option<&PieceMovement>
+    {
+        return self._movements.get(&_type);
+    }
+
+    // Registers the default movement of every fairy piece type
+    pub(super) fn __default_movements() -> BTreeMap<ChessPieceType, PieceMovement>
+    {
+        let mut movements: BTreeMap<ChessPieceType, PieceMovement> = BTreeMap::new();
+        for _type in [ChessPieceType::Archbishop, ChessPieceType::Chancellor,
+            ChessPieceType::Amazon, ChessPieceType::Camel, ChessPieceType::Zebra,
+            ChessPieceType::Grasshopper]
+        {
+            let betza: &str = PieceMovement::default_betza(_type).unwrap();
+            movements.insert(_type, PieceMovement::parse(betza).unwrap());
+        }
+
+        return movements;
+    }
+
+    // Checks if the fairy piece on `from` can get to `to` following its
+    // movement. `capture` tells if `to` is taken by an enemy piece, no
+    // matter what's actually on the board (used to look for threats)
+    pub(super) fn __betza_reaches(&self, from: u8, to: u8, capture: bool) -> bool
+    {
+        let from_el: ChessPiece = self.board[from as usize];
+        let movement: &PieceMovement = match from_el.get_type().and_then(|t| self._movements.get(&t))
+        {
+            Some(movement) => movement,
+            None => return false,
+        };
+
+        let f: ChessPos = self.__pos(from);
+        // Black pieces look down the board
+        let forward: i8 = if from_el.is_white() { 1 } else { -1 };
+
+        for atom in movement.get_atoms()
+        {
+            let _allowed: bool = match atom.get_mode()
+            {
+                BetzaMode::Both => true,
+                BetzaMode::Move => !capture,
+                BetzaMode::Capture => capture,
+            };
+
+            if !_allowed { continue; }
+
+            for (dx, dy) in atom.get_steps()
+            {
+                let mut x: i8 = f.x as i8;
+                let mut y: i8 = f.y as i8;
+                let mut steps: u8 = 0;
+                let mut hopped: bool = false;
+
+                loop
+                {
+                    x += dx;
+                    y += dy*forward;
+                    steps += 1;
+
+                    let _on_board: bool = x >= 0 && y >= 0 && x < self._width as i8 && y < self._height as i8;
+                    // The range is counted until the hurdle for hoppers
+                    let _in_range: bool = atom.get_range() == 0 || steps <= atom.get_range() || hopped;
+                    if !_on_board || !_in_range { break; }
+
+                    let coords: u8 = self.__conv(x as u8, y as u8);
+                    let _taken: bool = if coords == to { capture } else { !self.board[coords as usize].is_empty() };
+
+                    if atom.get_hop() == BetzaHop::No || hopped
+                    {
+                        if coords == to { return true; }
+                        // The grasshopper only lands right behind the hurdle
+                        if _taken || atom.get_hop() == BetzaHop::Grasshopper { break; }
+                        continue;
+                    }
+
+                    // Looking for the hurdle
+                    hopped = _taken;
+                }
+            }
+        }
+
+        return false;
+    }
+}
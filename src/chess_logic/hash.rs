@@ -0,0 +1,94 @@
+use super::*;
+
+impl ChessBoard
+{
+    // Zobrist hash of the position: a random looking number for every piece
+    // on every block, xor'ed together with the ones for the color to move,
+    // the castling rights, en passant, the pockets and the checks given.
+    // The numbers are derived from their index, so the hash stays the same
+    // between runs
+    pub fn hash(&self) -> u64
+    {
+        let mut hash: u64 = 0;
+
+        for (i, _el) in self.board.iter().enumerate()
+        {
+            if _el.is_empty() { continue; }
+
+            hash ^= Self::__zobrist_key((*_el as u64) << 8 | i as u64);
+            // Promoted pieces only play differently once they're captured
+            // into a pocket
+            if self._promoted[i] && self._variant.has_pockets()
+            {
+                hash ^= Self::__zobrist_key(25 << 8 | i as u64);
+            }
+        }
+
+        if self.w_lock
+        {
+            hash ^= Self::__zobrist_key(26 << 8);
+        }
+
+        for (side, rook) in self._castling.iter().enumerate()
+        {
+            if let Some(rook) = rook
+            {
+                hash ^= Self::__zobrist_key((27 + side as u64) << 8 | *rook as u64);
+            }
+        }
+
+        // Every copy of a type in a pocket has its own number, the copies
+        // get the low 8 bits and the type the ones above
+        for (color, pocket) in self._pockets.iter().enumerate()
+        {
+            for (i, _type) in pocket.iter().enumerate()
+            {
+                let copies: u64 = pocket[..i].iter().filter(|t| *t == _type).count() as u64;
+                hash ^= Self::__zobrist_key((31 + color as u64) << 16 | (*_type as u64) << 8 | copies);
+            }
+        }
+
+        // Only kept while the pawn can be taken
+        if let Some(target) = self._en_passant
+        {
+            hash ^= Self::__zobrist_key(35 << 8 | target as u64);
+        }
+
+        for (color, checks) in self._checks.iter().enumerate()
+        {
+            hash ^= Self::__zobrist_key((33 + color as u64) << 8 | *checks as u64);
+        }
+
+        return hash;
+    }
+
+    // SplitMix64 of the index
+    fn __zobrist_key(index: u64) -> u64
+    {
+        let mut z: u64 = index.wrapping_add(1).wrapping_mul(0x9E3779B97F4A7C15);
+        z = (z ^ (z >> 30)).wrapping_mul(0xBF58476D1CE4E5B9);
+        z = (z ^ (z >> 27)).wrapping_mul(0x94D049BB133111EB);
+
+        return z ^ (z >> 31);
+    }
+}
+
+// Two boards are equal if the same game goes on with the same rules on
+// them, the move counters included
+impl PartialEq for ChessBoard
+{
+    fn eq(&self, other: &Self) -> bool
+    {
+        let _position: bool = self.board == other.board && self.w_lock == other.w_lock
+            && self._width == other._width && self._promoted == other._promoted
+            && self._pockets == other._pockets;
+        let _rights: bool = self._castling == other._castling && self._en_passant == other._en_passant
+            && self._chess960 == other._chess960
+            && self._checks == other._checks;
+        let _counters: bool = self._halfmove_clock == other._halfmove_clock && self._fullmove == other._fullmove;
+        let _rules: bool = self._variant.name() == other._variant.name() && self._movements == other._movements
+            && self._default_promotion == other._default_promotion && self._state == other._state;
+
+        return _position && _rights && _counters && _rules;
+    }
+}
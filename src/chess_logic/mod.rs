@@ -1,9 +1,15 @@
 use super::*;
-use std::ops::Range;
-
+use core::ops::Range;
+use variant::{Standard, Variant};
 
 #[cfg(test)]
 mod tests;
+mod castling;
+mod en_passant;
+mod fairy;
+mod drops;
+mod moves;
+mod hash;
 
 impl ChessBoard
 {
@@ -12,11 +18,37 @@ impl ChessBoard
         return self._state;
     }
 
-    // Get the board
+    // Get the board, only for boards of the default 8 x 8 size
     // Public
     pub fn get_board(&self) -> [ChessPiece; 64]
     {
-        return self.board;
+        assert!(self._width == 8 && self._height == 8, "Board is not 8 x 8, use `get_squares`");
+
+        let mut board: [ChessPiece; 64] = [ChessPiece::Empty; 64];
+        board.copy_from_slice(&self.board);
+        return board;
+    }
+
+    // Get the board as rank after rank, starting from the first one,
+    // for boards of any size
+    // Public
+    pub fn get_squares(&self) -> &[ChessPiece]
+    {
+        return &self.board;
+    }
+
+    // Number of files on the board
+    // Public
+    pub fn width(&self) -> u8
+    {
+        return self._width;
+    }
+
+    // Number of ranks on the board
+    // Public
+    pub fn height(&self) -> u8
+    {
+        return self._height;
     }
 
     // If white's turn
@@ -33,70 +65,123 @@ impl ChessBoard
         return self.board[coords as usize];
     }
 
+    // How many of the piece are on the board, without counting them
+    // Public
+    pub fn piece_count(&self, piece: ChessPiece) -> u8
+    {
+        return self._piece_count[piece as usize];
+    }
+
     // Initialize a chess board with pieces on default positions
     pub fn init_position() -> ChessBoard
     {
+        return Self::init_from_rank(&[ChessPieceType::Rook, ChessPieceType::Knight,
+            ChessPieceType::Bishop, ChessPieceType::Queen, ChessPieceType::King,
+            ChessPieceType::Bishop, ChessPieceType::Knight, ChessPieceType::Rook], 8);
+    }
+
+    // Initialize a chess board with `height` ranks and as many files as there
+    // are pieces in `rank`. The pieces are placed in the given order on the
+    // first and last rank, with pawns in front of them. The rooks on both
+    // sides of the king get castling rights
+    pub fn init_from_rank(rank: &[ChessPieceType], height: u8) -> ChessBoard
+    {
+        let width: u8 = rank.len() as u8;
+        let mut chess_board: ChessBoard = Self::__empty(width, height);
+
+        for (x, _type) in rank.iter().enumerate()
+        {
+            let x: u8 = x as u8;
+            chess_board.board[ChessPos::conv_dim(x, 0, width, height) as usize] = ChessPiece::from_type(*_type, true);
+            chess_board.board[ChessPos::conv_dim(x, 1, width, height) as usize] = ChessPiece::WPawn;
+            chess_board.board[ChessPos::conv_dim(x, height-2, width, height) as usize] = ChessPiece::BPawn;
+            chess_board.board[ChessPos::conv_dim(x, height-1, width, height) as usize] = ChessPiece::from_type(*_type, false);
+        }
+
+        chess_board.__locate_kings();
+        chess_board.__count_pieces();
+
+        // The rooks on both sides of the king are the castling rooks
+        let k_x: u8 = chess_board.__pos(chess_board._w_king).x;
+        for x in 0..width
+        {
+            if chess_board.board[x as usize] != ChessPiece::WRook { continue; }
+
+            let side: usize = if x > k_x { 0 } else { 1 };
+            chess_board._castling[side] = Some(x);
+            chess_board._castling[side+2] = Some(ChessPos::conv_dim(x, height-1, width, height));
+        }
+
+        return chess_board;
+    }
+
+    // Initialize a chess board of `width` x `height` blocks without any
+    // pieces on it, white to move. Used as a starting point when building
+    // up other positions
+    pub(crate) fn __empty(width: u8, height: u8) -> ChessBoard
+    {
+        // Coordinates are stored as u8, including the one after the last block
+        assert!(width >= 3 && height >= 4 && (width as u16)*(height as u16) < 256,
+            "Board dimensions outside of bounds");
+
         // White start by default
         let w_lock = false;
+        let board: Vec<ChessPiece> = vec![ChessPiece::Empty; width as usize * height as usize];
+
+        let _promoted: Vec<bool> = vec![false; board.len()];
+
+        ChessBoard { w_lock, board, _width: width, _height: height, _w_king: 0, _b_king: 0,
+            _default_promotion: ChessPieceType::Queen, _movements: Self::__default_movements(),
+            _castling: [None; 4], _en_passant: None,
+            _chess960: false, _halfmove_clock: 0, _fullmove: 1,
+            _variant: Arc::new(Standard), _checks: [0, 0],
+            _pockets: [Vec::new(), Vec::new()], _promoted,
+            _state: ChessState::On, _winner: None, _piece_count: [0; PIECE_KINDS],
+            _can_capture: None }
+    }
 
-        let mut _piece_count: HashMap<ChessPiece, u8> = HashMap::new();
+    // Number of blocks on the board, also used as the coordinates of a block
+    // outside of the board
+    pub(crate) fn __size(&self) -> u8
+    {
+        return self._width*self._height;
+    }
 
-        let mut board: [ChessPiece; 64] = [ChessPiece::Empty; 64];
+    // `ChessPos::from` for the dimensions of this board
+    pub(crate) fn __pos(&self, coords: u8) -> ChessPos
+    {
+        return ChessPos::from_dim(coords, self._width, self._height, false);
+    }
+
+    // `ChessPos::conv` for the dimensions of this board
+    pub(crate) fn __conv(&self, x: u8, y: u8) -> u8
+    {
+        return ChessPos::conv_dim(x, y, self._width, self._height);
+    }
+
+    // Find the kings on the board and store their coordinates
+    pub(crate) fn __locate_kings(&mut self)
+    {
+        for i in 0..self.__size()
         {
-            // Register the initial number of pieces on the board
-            _piece_count.insert(ChessPiece::BRook, 2);
-            _piece_count.insert(ChessPiece::WRook, 2);
-            _piece_count.insert(ChessPiece::BKnight, 2);
-            _piece_count.insert(ChessPiece::WKnight, 2);
-            _piece_count.insert(ChessPiece::BBishop, 2);
-            _piece_count.insert(ChessPiece::WBishop, 2);
-            _piece_count.insert(ChessPiece::BPawn, 8);
-            _piece_count.insert(ChessPiece::WPawn, 8);
-            _piece_count.insert(ChessPiece::BQueen, 1);
-            _piece_count.insert(ChessPiece::BQueen, 1);
-            _piece_count.insert(ChessPiece::BKing, 1);
-            _piece_count.insert(ChessPiece::BKing, 1);
-
-            // Place the chess pieces in the default order
-            board[0o00] = ChessPiece::WRook;
-            board[0o01] = ChessPiece::WKnight;
-            board[0o02] = ChessPiece::WBishop;
-            board[0o03] = ChessPiece::WQueen;
-            board[0o04] = ChessPiece::WKing;
-            board[0o05] = ChessPiece::WBishop;
-            board[0o06] = ChessPiece::WKnight;
-            board[0o07] = ChessPiece::WRook;
-            board[0o10] = ChessPiece::WPawn;
-            board[0o11] = ChessPiece::WPawn;
-            board[0o12] = ChessPiece::WPawn;
-            board[0o13] = ChessPiece::WPawn;
-            board[0o14] = ChessPiece::WPawn;
-            board[0o15] = ChessPiece::WPawn;
-            board[0o16] = ChessPiece::WPawn;
-            board[0o17] = ChessPiece::WPawn;
-
-            board[0o60] = ChessPiece::BPawn;
-            board[0o61] = ChessPiece::BPawn;
-            board[0o62] = ChessPiece::BPawn;
-            board[0o63] = ChessPiece::BPawn;
-            board[0o64] = ChessPiece::BPawn;
-            board[0o65] = ChessPiece::BPawn;
-            board[0o66] = ChessPiece::BPawn;
-            board[0o67] = ChessPiece::BPawn;
-            board[0o70] = ChessPiece::BRook;
-            board[0o71] = ChessPiece::BKnight;
-            board[0o72] = ChessPiece::BBishop;
-            board[0o73] = ChessPiece::BQueen;
-            board[0o74] = ChessPiece::BKing;
-            board[0o75] = ChessPiece::BBishop;
-            board[0o76] = ChessPiece::BKnight;
-            board[0o77] = ChessPiece::BRook;
-        }
-
-        ChessBoard { w_lock: w_lock, board: board, _w_king: 0o04, _b_king: 0o74,
-            _default_promotion: ChessPieceType::Queen, _state: ChessState::On,
-            _piece_count: _piece_count }
+            match self.board[i as usize]
+            {
+                ChessPiece::WKing => self._w_king = i,
+                ChessPiece::BKing => self._b_king = i,
+                _ => ()
+            }
+        }
+    }
 
+    // Register the number of pieces of each type on the board
+    pub(crate) fn __count_pieces(&mut self)
+    {
+        self._piece_count = [0; PIECE_KINDS];
+        for _el in self.board.iter()
+        {
+            if _el.is_empty() { continue; }
+            self._piece_count[*_el as usize] += 1;
+        }
     }
 
     // Returns true if promotion is possible. Should be used before `ChessBoard::drag`
@@ -104,8 +189,7 @@ impl ChessBoard
     // a promotion chess piece
     pub fn promotion_check(&mut self, from: u8, to: u8) -> bool
     {
-        let _from: ChessPos = ChessPos::from(from, false);
-        let _to: ChessPos = ChessPos::from(from, false);
+        let _to: ChessPos = self.__pos(to);
 
         let _from_el: ChessPiece = self.board[from as usize];
         let _to_el: ChessPiece = self.board[to as usize];
@@ -115,12 +199,27 @@ impl ChessBoard
             return false;
         }
 
-        let case1: bool = _from_el == ChessPiece::WPawn && _to.y == 7;
+        let case1: bool = _from_el == ChessPiece::WPawn && _to.y == self._height-1;
         let case2: bool = _from_el == ChessPiece::BPawn && _to.y == 0;
   
         return case1 || case2;
     }
 
+    // Set the piece type pawns are promoted to, a queen by default.
+    // Any type apart from the king and the pawn, including fairy pieces,
+    // unless the variant says otherwise
+    pub fn set_promotion(&mut self, _type: ChessPieceType)
+    {
+        assert!(self._variant.can_promote_to(_type), "Pawn cannot be promoted to this type");
+
+        self._default_promotion = _type;
+    }
+
+    pub fn get_promotion(&self) -> ChessPieceType
+    {
+        return self._default_promotion;
+    }
+
     // Main function that realizes the move of one chess piece at `from` coordinates
     // to `to` coordinate block. The function validates if the move to be made is valid
     // and moves the chess piece to the given coordinates if the move is indeed valid.
@@ -137,9 +236,24 @@ impl ChessBoard
             return;
         }
 
+        // Castling is the only move where two pieces move at once, so it's
+        // handled on its own
+        if let Some(side) = self.__castling_side(from, to)
+        {
+            if self.__can_castle(side)
+            {
+                self.__castle(side);
+                self._halfmove_clock += 1;
+                self._en_passant = None;
+                self.__end_turn();
+            }
+            return;
+        }
+
         // Checks if the move is allowed, takes in account if for example
-        // a pawn can 'eat' a enemy piece by taking a straight step
-        if !(self.__verify_move(from, to))
+        // a pawn can 'eat' a enemy piece by taking a straight step, and
+        // that the own king is not left in check
+        if !(self.__is_legal(from, to))
         {
             // Raise error that the move isn't allowed
             // assert!(false, "Illegal move");
@@ -151,508 +265,436 @@ impl ChessBoard
         let to_el: ChessPiece = self.board[to as usize];
         let from_el: ChessPiece = self.board[from as usize];
 
+        // A pawn taken en passant isn't on the block the capturing pawn
+        // lands on
+        let victim: Option<u8> = self.__en_passant_victim(from, to);
+        let captured: ChessPiece = victim.map_or(to_el, |victim| self.board[victim as usize]);
+        if let Some(victim) = victim
+        {
+            self.__remove_piece(victim);
+        }
+        let double_step: Option<u8> = self.__double_step(from, to);
+
         self.board[from as usize] = ChessPiece::Empty;
         self.board[to as usize] = from_el;
 
+        let _was_promoted: bool = self._promoted[to as usize];
+        self._promoted[to as usize] = self._promoted[from as usize];
+        self._promoted[from as usize] = false;
+
         // Kill the enemy 
         if to_el.is_enemy_to(from_el)
         {
             // A piece of this type was killed => -1 in the piece count map
-            *self._piece_count.get_mut(&to_el).unwrap() -= 1;
+            self._piece_count[to_el as usize] -= 1;
+        }
+
+        // The captured piece goes into the pocket of the capturing color,
+        // promoted pieces as the pawn they were
+        if !captured.is_empty() && self._variant.has_pockets()
+        {
+            let _type: ChessPieceType = if _was_promoted { ChessPieceType::Pawn } else { captured.get_type().unwrap() };
+            self.add_to_pocket(from_el.is_white(), _type);
         }
 
         // Promotion replacement when a pawn reaches the top/bottom
         // `__verify_move` already checks if the square above or bellow a
         // pawn is empty 
-        if from_el==ChessPiece::WPawn && ChessPos::from(to, false).y == 7
+        let _last_rank: u8 = if from_el.is_white() { self._height-1 } else { 0 };
+        let _is_pawn: bool = from_el==ChessPiece::WPawn || from_el==ChessPiece::BPawn;
+        if _is_pawn && self.__pos(to).y == _last_rank
         {
-            // Dirty lookup and conversion
-            match self._default_promotion
-            {
-                ChessPieceType::King => assert!(false, "Pawn cannot be promoted to a king"),
-                ChessPieceType::Bishop =>   self.board[to as usize] = ChessPiece::WBishop,
-                ChessPieceType::Knight =>   self.board[to as usize] = ChessPiece::WKnight,
-                ChessPieceType::Queen =>    self.board[to as usize] = ChessPiece::WQueen,
-                ChessPieceType::Rook =>     self.board[to as usize] = ChessPiece::WRook,
-                ChessPieceType::Pawn =>     (),
-            }
-        }
+            let promoted: ChessPiece = ChessPiece::from_type(self._default_promotion, from_el.is_white());
+            self.board[to as usize] = promoted;
+            self._promoted[to as usize] = true;
 
-        if from_el==ChessPiece::BPawn && ChessPos::from(to, false).y == 0
-        {
-            // Dirty lookup and conversion
-            match self._default_promotion
-            {
-                ChessPieceType::King => assert!(false, "Pawn cannot be promoted to a king"),
-                ChessPieceType::Bishop =>   self.board[to as usize] = ChessPiece::BBishop,
-                ChessPieceType::Knight =>   self.board[to as usize] = ChessPiece::BKnight,
-                ChessPieceType::Queen =>    self.board[to as usize] = ChessPiece::BQueen,
-                ChessPieceType::Rook =>     self.board[to as usize] = ChessPiece::BRook,
-                ChessPieceType::Pawn =>     (),
-            }
+            self._piece_count[from_el as usize] -= 1;
+            self._piece_count[promoted as usize] += 1;
         }
 
         // Update the coordinates of the kings when moved
         self._w_king = if from_el==ChessPiece::WKing { to } else {self._w_king};
         self._b_king = if from_el==ChessPiece::BKing { to } else {self._b_king}; 
 
-        // Check if the game has ended
-        self.__check_state();
+        // Moving the king or a castling rook, or losing the rook, takes away
+        // the right to castle
+        self.__update_castling(from, to);
+
+        // The 50 move rule counter is reset by captures and pawn moves
+        self._halfmove_clock = if _is_pawn || !to_el.is_empty() { 0 } else { self._halfmove_clock+1 };
+
+        // Rules of the variant that happen after a move, like explosions
+        let variant: Arc<dyn Variant> = self._variant.clone();
+        variant.after_move(self, from, to, captured);
+
+        // Only the move right after the two steps can take the pawn
+        self._en_passant = double_step;
+
+        self.__end_turn();
+    }
+
+    // Hands the move over to the other color and checks if the game has ended
+    fn __end_turn(&mut self)
+    {
+        if self.w_lock
+        {
+            self._fullmove += 1;
+        }
 
         // Lock/unlock the move for whites
         self.w_lock = !self.w_lock;
+        self.__update_en_passant();
+
+        // Count the checks given, for three-check
+        if self.is_check(!self.w_lock)
+        {
+            let _by_white: bool = self.w_lock;
+            self._checks[if _by_white { 0 } else { 1 }] += 1;
+        }
+
+        // Check if the game has ended
+        self.__check_state();
+    }
+
+    // Checks if the move from `from` to `to` is allowed for the color that
+    // is about to move, following the rules of the variant. Castling is not
+    // included
+    pub(crate) fn __is_legal(&mut self, from: u8, to: u8) -> bool
+    {
+        let variant: Arc<dyn Variant> = self._variant.clone();
+        return variant.is_legal(self, from, to);
+    }
+
+    // Checks if the king of the given color is in check. Always false if
+    // the variant has no king for the color, or the king is gone
+    // Public
+    pub fn is_check(&self, white: bool) -> bool
+    {
+        let k_coords: u8 = if white { self._w_king } else { self._b_king };
+        let _king: ChessPiece = if white { ChessPiece::WKing } else { ChessPiece::BKing };
+
+        if !self._variant.has_king(white) || self.board[k_coords as usize] != _king
+        {
+            return false;
+        }
+
+        return self.__is_threatened(k_coords, white) != self.__size();
+    }
+
+    // Checks if the block is attacked by a piece of the given color
+    // Public
+    pub fn is_attacked(&self, coords: u8, by_white: bool) -> bool
+    {
+        return self.__is_threatened(coords, !by_white) != self.__size();
+    }
+
+    // Takes the piece away from the board
+    pub(crate) fn __remove_piece(&mut self, coords: u8)
+    {
+        let _el: ChessPiece = self.board[coords as usize];
+        if _el.is_empty() { return; }
+
+        self.board[coords as usize] = ChessPiece::Empty;
+        self._promoted[coords as usize] = false;
+        self._piece_count[_el as usize] -= 1;
+    }
+
+    // Checks if the move from `from` to `to` would leave the king of the
+    // moving piece in check. The move itself is expected to be verified
+    pub(crate) fn __is_safe_move(&mut self, from: u8, to: u8) -> bool
+    {
+        // Nothing to keep safe
+        if !self._variant.has_king(self.board[from as usize].is_white())
+        {
+            return true;
+        }
+
+        let from_el: ChessPiece = self.board[from as usize];
+        let to_el: ChessPiece = self.board[to as usize];
+
+        let victim: Option<u8> = self.__en_passant_victim(from, to);
+
+        self.board[from as usize] = ChessPiece::Empty;
+        self.board[to as usize] = from_el;
+        if let Some(victim) = victim
+        {
+            self.board[victim as usize] = ChessPiece::Empty;
+        }
+
+        let _is_king: bool = from_el==ChessPiece::WKing || from_el==ChessPiece::BKing;
+        let _own_king: u8 = if from_el.is_white() { self._w_king } else { self._b_king };
+        let k_coords: u8 = if _is_king { to } else { _own_king };
+
+        let safe: bool = self.__is_threatened(k_coords, from_el.is_white()) == self.__size();
+
+        // Put the pieces back
+        self.board[from as usize] = from_el;
+        self.board[to as usize] = to_el;
+        if let Some(victim) = victim
+        {
+            self.board[victim as usize] = if from_el.is_white() { ChessPiece::BPawn } else { ChessPiece::WPawn };
+        }
+
+        return safe;
     }
 
 
     // Checks if the block (piece) is threatened.
     // `_wh` indicates if the piece color is white or not
     // really dirty code - sorry, didn't have so much energy to work with it
-    fn __is_threatened(&self, coords_raw: u8, _wh: bool) -> u8
+    pub(crate) fn __is_threatened(&self, coords_raw: u8, _wh: bool) -> u8
     {
-        let coords: ChessPos = ChessPos::from(coords_raw, false);
+        let coords: ChessPos = self.__pos(coords_raw);
 
-        let _pawn: ChessPiece = if _wh {ChessPiece::WPawn} else {ChessPiece::BPawn};
-        let _king: ChessPiece = if _wh {ChessPiece::WKing} else {ChessPiece::BKing};
-        let _rook: ChessPiece = if _wh {ChessPiece::WRook} else {ChessPiece::BRook};
-        let _bishop: ChessPiece = if _wh {ChessPiece::WBishop} else {ChessPiece::BBishop};
-        let _knight: ChessPiece = if _wh {ChessPiece::WKnight} else {ChessPiece::BKnight};
-        let _queen: ChessPiece = if _wh {ChessPiece::WQueen} else {ChessPiece::BQueen};
+        // The attacking pieces are of the opposite color
+        let _king: ChessPiece = if _wh {ChessPiece::BKing} else {ChessPiece::WKing};
+        let _rook: ChessPiece = if _wh {ChessPiece::BRook} else {ChessPiece::WRook};
+        let _bishop: ChessPiece = if _wh {ChessPiece::BBishop} else {ChessPiece::WBishop};
+        let _knight: ChessPiece = if _wh {ChessPiece::BKnight} else {ChessPiece::WKnight};
+        let _queen: ChessPiece = if _wh {ChessPiece::BQueen} else {ChessPiece::WQueen};
 
-        let _straight_attack: Vec<ChessPiece> = vec![_rook, _queen];
-        let _diagonal_attack: Vec<ChessPiece> = vec![_bishop, _queen];
+        let _straight_attack: [ChessPiece; 2] = [_rook, _queen];
+        let _diagonal_attack: [ChessPiece; 2] = [_bishop, _queen];
 
         // Check if horisontal line after the block threatens the block
-        for i in 1..=(7-coords.x)
+        for i in 1..=(self._width-1-coords.x)
         {
             
-            let enemy_coords: u8 = ChessPos::conv(coords.x+i, coords.y);
+            let enemy_coords: u8 = self.__conv(coords.x+i, coords.y);
             let _el: ChessPiece = self.board[enemy_coords as usize];
-            if _el == _king && i == 1 { return ChessPos::conv(coords.x+i, coords.y) }
+            if _el == _king && i == 1 { return enemy_coords; }
             
-            if !_straight_attack.contains(&_el) { break; }
+            // Look further along the line until a piece is found
+            if _el.is_empty() { continue; }
 
-            if !_el.is_empty() { return enemy_coords; }
+            if _straight_attack.contains(&_el) { return enemy_coords; }
+
+            break;
         }
 
         // Check if horizontal line before the block threatens the block
         for i in 1..=coords.x
         {
-            let enemy_coords: u8 = ChessPos::conv(coords.x-i, coords.y);
+            let enemy_coords: u8 = self.__conv(coords.x-i, coords.y);
             let _el: ChessPiece = self.board[enemy_coords as usize];   
             if _el == _king && i == 1 { return enemy_coords; }
 
-            if !_straight_attack.contains(&_el) { break; }
+            // Look further along the line until a piece is found
+            if _el.is_empty() { continue; }
+
+            if _straight_attack.contains(&_el) { return enemy_coords; }
 
-            if !_el.is_empty() { return enemy_coords; }
+            break;
         }
 
         /////////////////////////////////////////
         // Check if vertical line before the block threatens the block
         for i in 1..=coords.y
         {
-            let enemy_coords: u8 = ChessPos::conv(coords.x, coords.y-i);
+            let enemy_coords: u8 = self.__conv(coords.x, coords.y-i);
             let _el: ChessPiece = self.board[enemy_coords as usize];
             if _el == _king && i == 1 { return enemy_coords; }
 
-            if !_straight_attack.contains(&_el) { break; }
+            // Look further along the line until a piece is found
+            if _el.is_empty() { continue; }
 
-            if !_el.is_empty() { return enemy_coords; }
+            if _straight_attack.contains(&_el) { return enemy_coords; }
+
+            break;
         }
 
         // Check if vertical line above the block threatens the block
-        for i in 1..=(7-coords.y)
+        for i in 1..=(self._height-1-coords.y)
         {
-            let enemy_coords: u8 = ChessPos::conv(coords.x, coords.y+i);
+            let enemy_coords: u8 = self.__conv(coords.x, coords.y+i);
             let _el: ChessPiece = self.board[enemy_coords as usize];
             if _el == _king && i == 1 { return enemy_coords; }
 
-            if !_straight_attack.contains(&_el) { break; }
+            // Look further along the line until a piece is found
+            if _el.is_empty() { continue; }
+
+            if _straight_attack.contains(&_el) { return enemy_coords; }
 
-            if !_el.is_empty() { return enemy_coords; }
+            break;
         }
 
         /////////////////////////////////////////////
         // Check if tl diagonal line threatens the block
-        for i in 1..=u8::min(coords.x, 7-coords.y)
+        for i in 1..=u8::min(coords.x, self._height-1-coords.y)
         {
-            let enemy_coords: u8 = ChessPos::conv(coords.x-i, coords.y+i);
+            let enemy_coords: u8 = self.__conv(coords.x-i, coords.y+i);
             let _el: ChessPiece = self.board[enemy_coords as usize];
             if _el == _king && i == 1 { return enemy_coords; }
 
             let _check_pawn: bool = _el==ChessPiece::BPawn && _wh && i == 1;
             if _check_pawn { return enemy_coords; }
 
-            if !_diagonal_attack.contains(&_el) { break; }
+            if _el.is_empty() { continue; }
 
-            if !_el.is_empty() { return enemy_coords; }
+            if _diagonal_attack.contains(&_el) { return enemy_coords; }
+
+            break;
         }
 
         // Check if rb diagonal line threatens the block
-        for i in 1..=u8::min(7-coords.x, coords.y)
+        for i in 1..=u8::min(self._width-1-coords.x, coords.y)
         {
-            let enemy_coords: u8 = ChessPos::conv(coords.x+i, coords.y-i);
+            let enemy_coords: u8 = self.__conv(coords.x+i, coords.y-i);
             let _el: ChessPiece = self.board[enemy_coords as usize];
             if _el == _king && i == 1 { return enemy_coords; }
 
             let _check_pawn: bool = _el==ChessPiece::WPawn && !_wh && i == 1;
             if _check_pawn { return enemy_coords; }
 
-            if !_diagonal_attack.contains(&_el) { break; }
+            if _el.is_empty() { continue; }
+
+            if _diagonal_attack.contains(&_el) { return enemy_coords; }
 
-            if !_el.is_empty() { return enemy_coords; }
+            break;
         }
 
         // Check if tr diagonal line threatens the block
-        for i in 1..=u8::min(7-coords.x, 7-coords.y)
+        for i in 1..=u8::min(self._width-1-coords.x, self._height-1-coords.y)
         {
-            let enemy_coords: u8 = ChessPos::conv(coords.x+i, coords.y+i);
+            let enemy_coords: u8 = self.__conv(coords.x+i, coords.y+i);
             let _el: ChessPiece = self.board[enemy_coords as usize];
             if _el == _king && i == 1 { return enemy_coords; }
             
             let _check_pawn: bool = _el==ChessPiece::BPawn && _wh && i == 1;
             if _check_pawn { return enemy_coords; }
 
-            if !_diagonal_attack.contains(&_el) { break; }
+            if _el.is_empty() { continue; }
+
+            if _diagonal_attack.contains(&_el) { return enemy_coords; }
 
-            if !_el.is_empty() { return enemy_coords; }
+            break;
         }
 
         // Check if bl diagonal line threatens the block
         for i in 1..=u8::min(coords.x, coords.y)
         {
-            let enemy_coords: u8 = ChessPos::conv(coords.x-i, coords.y-i);
+            let enemy_coords: u8 = self.__conv(coords.x-i, coords.y-i);
             let _el: ChessPiece = self.board[enemy_coords as usize];
             if _el == _king && i == 1 { return enemy_coords; }
 
             let _check_pawn: bool = _el==ChessPiece::WPawn && !_wh && i == 1;
             if _check_pawn { return enemy_coords; }
 
-            if !_diagonal_attack.contains(&_el) { break; }
+            if _el.is_empty() { continue; }
 
-            if !_el.is_empty() { return enemy_coords; }
+            if _diagonal_attack.contains(&_el) { return enemy_coords; }
+
+            break;
         }
 
 
 
-        for _pos in Self::__gen_possible_gamma_moves(coords).iter()
+        for _pos in Self::__gen_possible_gamma_moves(coords)
         {
-            let enemy_coords: u8 = ChessPos::conv(_pos.x, _pos.y);
+            let enemy_coords: u8 = self.__conv(_pos.x, _pos.y);
             let _el: ChessPiece = self.board[enemy_coords as usize];
             if _el==_knight { return enemy_coords; }
         }
+
+        // Fairy pieces can attack from anywhere, so every one of them is
+        // checked on its own
+        for enemy_coords in 0..self.__size()
+        {
+            let _el: ChessPiece = self.board[enemy_coords as usize];
+            let _is_enemy: bool = if _wh { _el.is_black() } else { _el.is_white() };
+            let _is_fairy: bool = _el.get_type().is_some_and(|t| t.is_fairy());
+
+            if _is_enemy && _is_fairy && self.__betza_reaches(enemy_coords, coords_raw, true)
+            {
+                return enemy_coords;
+            }
+        }
         // out of bounds if not threatened
-        return 64;
+        return self.__size();
 
     }
 
-    // Checks if the king is stalemate or checkmate.
-    // The target king is choosed based on `self.w_lock`
-    fn __check_state(&mut self)
+    // Checks if the king is stalemate or checkmate, or if the game ended
+    // by a rule of the variant.
+    // The target king is choosed based on `self.w_lock`, so it is the
+    // king of the color that is about to move
+    pub(crate) fn __check_state(&mut self)
     {
-        let k_coords_raw: u8 = if self.w_lock {self._b_king} else {self._w_king};
-        let k_coords: ChessPos = ChessPos::from(k_coords_raw, false);
-        let k_threat_raw: u8 = self.__is_threatened(k_coords_raw, !self.w_lock);
+        let variant: Arc<dyn Variant> = self._variant.clone();
 
-        let _king: ChessPiece = self.board[k_coords_raw as usize];
+        let mut result: Option<(ChessState, Option<bool>)> = variant.check_state(self);
 
-        let r: Range<i8> = 0..8;
-
-        let mut no_moves_count: u8 = 0;
-        // Loop through the king's all possible moves and check
-        // if the king has at least 1 legal move to do
-        for i in -1..=1
+        // As long as there's a legal move to make, the game goes on
+        if result.is_none() && !self.__has_legal_move()
         {
-            for j in -1..=1
-            {
-                let x: i8 = (k_coords.x as i8)+i;
-                let y: i8 = (k_coords.y as i8)+j;
-                // Checked box out of chess board bounds - impossible
-                // for a king to move there
-                if !r.contains(&x) || !r.contains(&y)
-                {
-                    no_moves_count += 1;
-                    continue;
-                }
-
-                let __tmp: u8 = ChessPos::conv(x as u8, y as u8);
-                let _el: ChessPiece = self.board[__tmp as usize];
-                // If the king cannot kill the piece near it to make a move
-                if !_king.is_enemy_to(_el) && !_el.is_empty()
-                {
-                    no_moves_count += 1;
-                    continue;
-                }
-
-                // Or else after all tests, the block itself is being threatened
-                let _el_threat: bool = self.__is_threatened(__tmp, 
-                                        !self.w_lock) != 64;
-                no_moves_count += _el_threat as u8;
-            }
+            result = Some(variant.no_moves_state(self));
         }
 
-        // If not in check and no legal moves - set game state to stalemate and exit
-        if k_threat_raw == 64 && no_moves_count == 8
-        {
-            self._state = ChessState::Stalemate;
-            return;
-        }
-
-        // If not stalemate, but no threat - just exit
-        if k_threat_raw == 64 {return;}
-
-        // Check whether it is possible to eliminate the threat to the king
-        // if it is possible - just exit
-        if self.__is_threatened(k_threat_raw, !self.w_lock) != 64
+        if let Some((state, winner)) = result
         {
-            return;
+            self._state = state;
+            self._winner = winner;
         }
+    }
 
+    // Get the color that won the game, true for white. `None` while the
+    // game is on or when it's a draw
+    // Public
+    pub fn get_winner(&self) -> Option<bool>
+    {
+        return self._winner;
+    }
 
-        let k_threat_coords: ChessPos = ChessPos::from(k_threat_raw, false);
-        let _threat: ChessPiece = self.board[k_threat_raw as usize];
-
-        // Attacks by these pieces cannot be blocked in any way - ignore en passant for king
-        let ignored_pieces: Vec<ChessPiece> = vec![ChessPiece::WKnight, ChessPiece::BKnight,
-                                                    ChessPiece::WPawn, ChessPiece::BPawn];
-
-        // If the attack cannot be blocked - set the game state to checkmate and exit
-        if ignored_pieces.contains(&_threat)
-        {
-            self._state = ChessState::Checkmate;
-            return;
-        }
+    // Get the number of checks given by white and black
+    // Public
+    pub fn get_checks(&self) -> [u8; 2]
+    {
+        return self._checks;
+    }
 
+    // Get the variant the game is played in
+    // Public
+    pub fn get_variant(&self) -> &dyn Variant
+    {
+        return self._variant.as_ref();
+    }
 
-        for i in 0..64 as u8
+    // Checks if the color that is about to move has at least 1 legal move
+    pub(crate) fn __has_legal_move(&mut self) -> bool
+    {
+        // Yeah bad brute force - sorting out all pieces and trying to move
+        // them to every block on the board
+        for from in 0..self.__size()
         {
-            // Yeah bad brute force - sorting out all pieces and checking if they
-            // can block the attack on the king
-            let _el: ChessPiece = self.board[i as usize];
-            if !_el.is_enemy_to(_threat)
+            let _el: ChessPiece = self.board[from as usize];
+            if _el.is_empty() || _el.is_white() == self.w_lock
             {
                 continue;
             }
 
-            let _pos: ChessPos = ChessPos::from(i as u8, false);
-
-            match _el
+            for to in 0..self.__size()
             {
-                ChessPiece::Empty | ChessPiece::WKing | ChessPiece::BKing => {continue;},
-                ChessPiece::WPawn | ChessPiece::BPawn => {
-                    let x: i8 = _pos.x as i8;
-                    let mut y: i8 = _pos.y as i8;
-                    y += if _el.is_black() { -1 } else { 1 }; 
-
-                    if !r.contains(&y)
-                    {
-                        continue;
-                    }
-
-                    // No move validation is needed because of one step
-                    let _coords: ChessPos = ChessPos::from(ChessPos::conv(x as u8,y as u8),
-                                                false);
-                    let can_block_threat: bool = _coords.between(k_coords, 
-                                            ChessPos::from(k_threat_raw, false),
-                                            false);
-                    
-                    if can_block_threat { return; }
-                    
-                },
-                ChessPiece::WRook | ChessPiece::BRook => {
-                    let can_block_threat: bool = self.__can_straight_block_attack(
-                        k_coords, k_threat_coords, _pos);
-
-                    if can_block_threat { return; }
-                },
-                ChessPiece::WBishop | ChessPiece::BBishop => {
-                    let can_block_threat: bool = self.__can_diag_block_attack(
-                        k_coords, k_threat_coords, _pos);
-                    
-                    if can_block_threat { return; }
-                },
-                ChessPiece::WQueen | ChessPiece::BQueen => {
-                    let can_block_threat: bool = self.__can_straight_block_attack(
-                        k_coords, k_threat_coords, _pos) ||
-                        self.__can_diag_block_attack(
-                            k_coords, k_threat_coords, _pos);
-
-                    if can_block_threat { return; }
-                },
-                ChessPiece::WKnight | ChessPiece::BKnight => {
-                    for _pos in Self::__gen_possible_gamma_moves(_pos).iter()
-                    {
-                        let can_block_threat = _pos.between(k_coords, 
-                                                k_threat_coords, false);
-
-                        if can_block_threat { return; }
-                    }
+                if self.__is_legal(from, to)
+                {
+                    return true;
                 }
             }
         }
-        
-        // If the is not able to be blocked - set checkmate
-        self._state = ChessState::Checkmate;
-    }
-
-    fn __can_straight_block_attack(&mut self, start: ChessPos, end: ChessPos, from: ChessPos) -> bool
-    {
-        let dx: i8 = end.x as i8 - start.x as i8;
-        let dy: i8 = end.y as i8 - start.y as i8;
-
-        // derivative of the diagonal with respect to y, either -1 or +1
-        // its going to be used to calculate the collision point for a test object
-        let diag_k_y: i8 = dx/dy;
-        // same derivative of the diagonal but with respect to x
-        let diag_k_x: i8 = dy/dx;
-
-        // Straight linear pathway check
-        if (dx==0 && dy!=0) || (dx!=0 && dy==0)
-        {
-            // Test coordinates to check if the target Rook can move
-            // and block the attack; same y-coordinate 
-            let test_v: ChessPos = ChessPos::from(ChessPos::conv(
-                from.x, end.y
-            ), false);
-
-            // Test coordinates to check if the target Rook can move
-            // and block the attack; same x-coordinate
-            let test_h: ChessPos = ChessPos::from(ChessPos::conv(
-                end.x, from.y
-            ), false);
-
-            let h_between: bool = test_h.between(end, start, true) &&
-                                    self.__verify_move(from.raw(), 
-                                        ChessPos::conv(end.x, from.y)
-                                    );
-            let v_between: bool = test_v.between(end, start, true) &&
-                                    self.__verify_move(from.raw(),
-                                        ChessPos::conv(from.x, end.x));
-
-            return h_between || v_between;
-        }
-
-        // basically calculate the x value from the derivative based linear
-        // function, given the value y
-        let shift_x: i8 = diag_k_y*(from.y as i8 - start.y as i8);
-        // ...and transform it to 'easily' handled coordinates
-        let test_h: ChessPos = ChessPos::from(ChessPos::conv(
-            (start.x as i8+shift_x) as u8,from.y
-        ), true);
-
-        // Same as above but calculate the y value from the derivative based
-        // linear function, but given the value x
-        let shift_y: i8 = diag_k_x*(from.x as i8 - start.x as i8);
-        let test_v: ChessPos = ChessPos::from(ChessPos::conv(
-            start.x, (from.y as i8+shift_y) as u8
-        ), true);
-
-        // Verify if the target rook is between the elements and that moves
-        // are actually possible to make
-        let verify_h: bool = test_h.between(start, end, true) &&
-                                self.__verify_move(from.raw(), test_h.raw());
-        let verify_v: bool = test_v.between(start, end, true) &&
-                                self.__verify_move(from.raw(), test_v.raw());
-
-        return verify_v || verify_h;
-    }
-
-    fn __can_diag_block_attack(&mut self, start: ChessPos, end: ChessPos, from: ChessPos) -> bool
-    {
-        let dx: i8 = end.x as i8 - start.x as i8;
-        let dy: i8 = end.y as i8 - start.y as i8;
-        // Check if the attack pathway is straight
-        // aka straight line - diagonal 'collision' check
-        if (dx==0 && dy!=0) || (dx!=0 && dy==0)
-        {
-            // Simple algebraic checking
-            // First of all the bishop can in 2 directions
-            // Described by the equations:
-            // x+y_0-x_0
-            // &&
-            // -x+y_0+x_0
-            // Where x_0 and y_0 are the coordinates of the
-            // bishop.
-
-            // If the attack pathway is straight vertical
-            // it's pretty simple to check if the bishop
-            // can move in a way to block the attack.
-
-            let _y1: u8 = start.x + (from.y as i8 - from.x as i8) as u8;
-            let _y2: u8 = (((from.y+from.x) as i8)-(start.x as i8)) as u8;
-            let test_v1: ChessPos = ChessPos::from(ChessPos::conv(
-                start.x, _y1
-            ), true);
-            let test_v2: ChessPos = ChessPos::from(ChessPos::conv(
-                start.x, _y2
-            ), true);
-
-            let _x1: u8 = (start.y as i8 - from.x as i8 + from.y as i8) as u8;
-            let _x2: u8 = (-(start.y as i8) + from.x as i8 + from.y as i8) as u8;
-            let test_h1: ChessPos = ChessPos::from(ChessPos::conv(
-                _x1, start.y
-            ), true);
-            let test_h2: ChessPos = ChessPos::from(ChessPos::conv(
-                _x1, start.y 
-            ), true);
-
-            // Check if the function pos, is inside the attack pathway or not
-            let mut valid_v: bool = dx==0;
-            valid_v &= test_v1.between(start, end, true) ||
-                        test_v2.between(start, end, true);
-            
-            valid_v &= self.__verify_move(from.raw(), test_v1.raw()) ||
-                        self.__verify_move(from.raw(), test_v2.raw());
-
-            let mut valid_h: bool = dy==0;
-            valid_h &= test_h1.between(start, end, true) ||
-                        test_h2.between(start, end, true);
-            
-            valid_h &= self.__verify_move(from.raw(), test_h1.raw()) ||
-                        self.__verify_move(from.raw(), test_h2.raw());
 
-            return valid_v || valid_h;
+        // Castling might be the only move left
+        let sides: Range<usize> = if self.w_lock { 2..4 } else { 0..2 };
+        for side in sides
+        {
+            if self.__can_castle(side) { return true; }
         }
 
-        // kx+m, m value for the diagonal attack linear equation
-        let k_attack: i8 = dy/dx;
-        let m_attack: i8 = -(start.x as i8)*k_attack+(start.y as i8);
-
-        let k1: i8 = 1;
-        // m value for the bishop diagonal linear equation with a derivative of +1
-        let m1: i8 = (from.y as i8)-(from.x as i8);
-
-        let k2: i8 = -1;
-        // ... m value for the linear equation with a derivative of -1
-        let m2: i8 = (from.y as i8)+(from.x as i8);
-
-        // The determinant between the attack pathway and bishop's diagonal equations
-        let det_1_attack: i8 = -k_attack + k1;
-        let det_2_attack: i8 = -k_attack + k2;
-
-        // if determinant == 0, lines are parallel, and in the case of this game, this
-        // cannot be possible because otherwise it would block the attack per automatica
-        let x_intercept1: i8 = if det_1_attack==0{64}else{(m_attack-m1)/det_1_attack};
-        let y_intercept1: i8 = if det_1_attack==0{64}else{(-k_attack*m1+k1*m_attack)/det_1_attack};
-
-        let x_intercept2: i8 = if det_2_attack==0{64}else{(m_attack-m2)/det_2_attack};
-        let y_intercept2: i8 = if det_2_attack==0{64}else{(-k_attack*m2+k2*m_attack)/det_2_attack};
-
-        let intercept1: ChessPos = ChessPos::from(ChessPos::conv(
-            x_intercept1 as u8, y_intercept1 as u8
-        ), true);
-        let intercept2: ChessPos = ChessPos::from(ChessPos::conv(
-            x_intercept2 as u8, y_intercept2 as u8
-        ), true);
-
-        // Check if the iterception pos is inside the attack pathway
-        let verify_intercept1 = intercept1.between(
-            start, end, true
-        ) && self.__verify_move(from.raw(), intercept1.raw());
-        let verify_intercept2 = intercept2.between(
-            start, end, true
-        ) && self.__verify_move(from.raw(), intercept2.raw());
+        // Or dropping a piece from the pocket
+        if self.__has_legal_drop()
+        {
+            return true;
+        }
 
-        return verify_intercept1 || verify_intercept2;
+        return false;
     }
 
     // Checks if the given pathway is empty.
@@ -662,8 +704,8 @@ impl ChessBoard
     fn __empty_pathway(&mut self, from: u8, to: u8,
         include_enemy: bool, path: ChessPathway) -> bool
     {
-        let f: ChessPos = ChessPos::from(from as u8, false);
-        let t: ChessPos = ChessPos::from(to as u8, false);
+        let f: ChessPos = self.__pos(from);
+        let t: ChessPos = self.__pos(to);
 
         // I have no energy and time in researching on how custom exceptions
         // are created in Rust
@@ -707,7 +749,7 @@ impl ChessBoard
                 {
                     let __check_x: u8 = ((f.x as i8) + i*dx_sign) as u8;
                     let __check_y: u8 = ((f.y as i8) + i*dy_sign) as u8;
-                    let __coords: u8 = ChessPos::conv(__check_x, __check_y);
+                    let __coords: u8 = self.__conv(__check_x, __check_y);
 
                     let _el: ChessPiece = self.board[__coords as usize];
                     if !_el.is_empty() { return false; }
@@ -729,7 +771,7 @@ impl ChessBoard
                     let __x: u8 = ((f.x as i8)+i*dx_sign*(h_case as i8)) as u8;
                     let __y: u8 = ((f.y as i8)+i*dy_sign*(v_case as i8)) as u8;
 
-                    let _el: ChessPiece = self.board[ChessPos::conv(__x, __y) as usize];
+                    let _el: ChessPiece = self.board[self.__conv(__x, __y) as usize];
 
                     if !_el.is_empty() { return false; }
                 }
@@ -746,7 +788,7 @@ impl ChessBoard
     }
 
     // Verifies if the move is allowed 
-    fn __verify_move(&mut self, from: u8, to: u8) -> bool
+    pub(crate) fn __verify_move(&mut self, from: u8, to: u8) -> bool
     {
         // Cannot move a piece to the same location
         // assert!(from != to, "Given pathway: from = to");
@@ -755,8 +797,8 @@ impl ChessBoard
         // `ChessPos` constructor automatically checks if position
         // is inside the chess board or not. If not, it asserts an
         // error.
-        let f: ChessPos = ChessPos::from(from as u8, false);
-        let t: ChessPos = ChessPos::from(to as u8, false);
+        let f: ChessPos = self.__pos(from);
+        let t: ChessPos = self.__pos(to);
 
         let fx: i8 = f.x as i8;
         let fy: i8 = f.y as i8;
@@ -774,6 +816,8 @@ impl ChessBoard
         if !_wrong_color { return false; }
 
         let to_el: ChessPiece = self.board[to as usize];
+        // Pawns can take a diagonal step onto an empty block en passant
+        let _en_passant: bool = self.__en_passant_victim(from, to).is_some();
 
         match from_el {
             // Raise error when trying to move an empty piece
@@ -797,8 +841,8 @@ impl ChessBoard
                 let _v_1down: bool  = _is_1down && to_el.is_empty();
                 let _v_2down: bool  = _is_2down && self.__empty_pathway(from, to, 
                     false,ChessPathway::Straight) && fy==1; 
-                let _v_ldiag: bool  = _is_ldiag && to_el.is_enemy_to(from_el);
-                let _v_rdiag: bool  = _is_rdiag && to_el.is_enemy_to(from_el);
+                let _v_ldiag: bool  = _is_ldiag && (to_el.is_enemy_to(from_el) || _en_passant);
+                let _v_rdiag: bool  = _is_rdiag && (to_el.is_enemy_to(from_el) || _en_passant);
 
                 return _v_1down || _v_2down || _v_ldiag || _v_rdiag;
             },
@@ -815,9 +859,9 @@ impl ChessBoard
                 
                 let _v_1up: bool    = _is_1up && to_el.is_empty();
                 let _v_2up: bool    = _is_2up && self.__empty_pathway(from, to, 
-                    false,ChessPathway::Straight) && f.y==6;
-                let _v_ldiag: bool  = _is_ldiag && to_el.is_enemy_to(from_el);
-                let _v_rdiag: bool  = _is_rdiag && to_el.is_enemy_to(from_el);
+                    false,ChessPathway::Straight) && f.y==self._height-2;
+                let _v_ldiag: bool  = _is_ldiag && (to_el.is_enemy_to(from_el) || _en_passant);
+                let _v_rdiag: bool  = _is_rdiag && (to_el.is_enemy_to(from_el) || _en_passant);
                 
                 return _v_1up || _v_2up || _v_ldiag || _v_rdiag;
             },
@@ -867,7 +911,7 @@ impl ChessBoard
                 // to==from is already checked in the begining of this method
                 // assert!(_abs_dx < 2 && _abs_dy < 2, "Illegal move for a king");
                 if !(_abs_dx < 2 && _abs_dy < 2) { return false; }
-                return !to_el.is_enemy_to(from_el);
+                return from_el.is_enemy_to(to_el);
             },
             ChessPiece::BQueen | ChessPiece::WQueen => {
                 let _is_diag: bool  = _abs_dx==_abs_dy;
@@ -888,62 +932,29 @@ impl ChessBoard
                 // Checks if the pathway is empty vertically and horisontally
                 return self.__empty_pathway(from, to, true, 
                     ChessPathway::Straight);
+            },
+            // Fairy pieces move as described by their Betza notation
+            _ => {
+                if !from_el.is_enemy_to(to_el) { return false; }
+
+                return self.__betza_reaches(from, to, !to_el.is_empty());
             }
         }
     }
 
-    fn __gen_possible_gamma_moves(from: ChessPos) -> Vec<ChessPos>
+    // The blocks a knight on `from` could come from, without allocating
+    fn __gen_possible_gamma_moves(from: ChessPos) -> impl Iterator<Item = ChessPos>
     {
-        let r: Range<i8> = 0..8;
+        const STEPS: [(i8, i8); 8] = [(2, 1), (2, -1), (-2, 1), (-2, -1), (1, 2), (-1, 2), (1, -2), (-1, -2)];
+
+        let rx: Range<i8> = 0..(from.w as i8);
+        let ry: Range<i8> = 0..(from.h as i8);
         let _x: i8 = from.x as i8;
         let _y: i8 = from.y as i8;
 
-        let mut pos_vec: Vec<ChessPos> = Vec::new();
-
-        ////////////////////////////////////////////////
-        // Check if knight threatens the block
-        if r.contains(&(_x+2)) && r.contains(&(_y+1))
-        {
-            pos_vec.push(ChessPos{x:(_x+2) as u8, y: (_y+1) as u8});
-        }
-
-        if r.contains(&(_x+2)) && r.contains(&(_y-1))
-        {
-            pos_vec.push(ChessPos{x:(_x+2) as u8, y: (_y-1) as u8});
-        }
-
-        if r.contains(&(_x-2)) && r.contains(&(_y+1))
-        {
-            pos_vec.push(ChessPos{x:(_x-2) as u8, y:(_y+1) as u8});
-        }
-
-        if r.contains(&(_x-2)) && r.contains(&(_y-1))
-        {
-            pos_vec.push(ChessPos{x:(_x-2) as u8, y:(_y-1) as u8});
-        }
-
-        //////////////////////////////////////
-        if r.contains(&(_x+1)) && r.contains(&(_y+2))
-        {
-            pos_vec.push(ChessPos{x:(_x+1) as u8, y: (_y+2) as u8});
-        }
-
-        if r.contains(&(_x-1)) && r.contains(&(_y+2))
-        {
-            pos_vec.push(ChessPos{x:(_x-1) as u8, y: (_y+2) as u8});
-        }
-
-        if r.contains(&(_x+1)) && r.contains(&(_y-2))
-        {
-            pos_vec.push(ChessPos{x:(_x+1) as u8, y: (_y-2) as u8});
-        }
-
-        if r.contains(&(_x-1)) && r.contains(&(_y-2))
-        {
-            pos_vec.push(ChessPos{x:(_x-1) as u8, y: (_y-2) as u8});
-        }
-
-        return pos_vec;
+        return STEPS.into_iter()
+            .filter(move |(dx, dy)| rx.contains(&(_x+dx)) && ry.contains(&(_y+dy)))
+            .map(move |(dx, dy)| ChessPos{x: (_x+dx) as u8, y: (_y+dy) as u8, w: from.w, h: from.h});
     }
 
 }
\ No newline at end of file
@@ -0,0 +1,167 @@
+use super::*;
+
+impl ChessBoard
+{
+    // Get every legal move of the color that is about to move, including
+    // castling, one move per promotion type and drops from the pocket
+    pub fn legal_moves(&mut self) -> Vec<ChessMove>
+    {
+        let mut moves: Vec<ChessMove> = Vec::new();
+        self.__generate_moves(|mv| { moves.push(mv); true });
+
+        return moves;
+    }
+
+    // Same as `ChessBoard::legal_moves` without allocating: the list is
+    // cleared and filled with the moves. Returns false if they didn't all
+    // fit, the list then holds the first `N` of them
+    pub fn generate_moves<const N: usize>(&mut self, list: &mut MoveList<N>) -> bool
+    {
+        list.clear();
+        return self.__generate_moves(|mv| list.push(mv));
+    }
+
+    // Calls `add` with every legal move until it returns false, returns
+    // false if it was stopped
+    fn __generate_moves(&mut self, mut add: impl FnMut(ChessMove) -> bool) -> bool
+    {
+        if self._state != ChessState::On
+        {
+            return true;
+        }
+
+        let (promotions, count) = self.__promotion_types();
+
+        for from in 0..self.__size()
+        {
+            let _el: ChessPiece = self.board[from as usize];
+            if _el.is_empty() || _el.is_white() == self.w_lock
+            {
+                continue;
+            }
+
+            for to in 0..self.__size()
+            {
+                // Dragging the king onto its own rook is castling
+                if self.__castling_side(from, to).is_some() || !self.__is_legal(from, to)
+                {
+                    continue;
+                }
+
+                if self.__is_promotion(from, to)
+                {
+                    for _type in promotions[..count].iter()
+                    {
+                        if !add(ChessMove::Drag { from, to, promotion: Some(*_type) }) { return false; }
+                    }
+                    continue;
+                }
+
+                if !add(ChessMove::drag(from, to)) { return false; }
+            }
+        }
+
+        // Castling is written as a drag of the king onto its destination,
+        // or onto the rook in chess960 mode and when the king would only
+        // take a single step
+        let sides: Range<usize> = if self.w_lock { 2..4 } else { 0..2 };
+        for side in sides
+        {
+            if !self.__can_castle(side) { continue; }
+
+            let king: u8 = if side < 2 { self._w_king } else { self._b_king };
+            let (k_dest, _) = self.__castling_destinations(side);
+            let to: u8 = if self.__castling_side(king, k_dest) == Some(side) { k_dest } else { self._castling[side].unwrap() };
+
+            if !add(ChessMove::drag(king, to)) { return false; }
+        }
+
+        let mut complete: bool = true;
+        self.__for_each_drop_type(|board, piece| {
+            for to in 0..board.__size()
+            {
+                if board.__is_legal_drop(piece, to) && !add(ChessMove::Drop { piece, to })
+                {
+                    complete = false;
+                    return false;
+                }
+            }
+            return true;
+        });
+
+        return complete;
+    }
+
+    // Plays the move, returns false if it isn't legal
+    pub fn make_move(&mut self, mv: ChessMove) -> bool
+    {
+        let w_lock: bool = self.w_lock;
+
+        match mv
+        {
+            ChessMove::Drag { from, to, promotion } => {
+                let _default: ChessPieceType = self._default_promotion;
+                if let Some(_type) = promotion
+                {
+                    if !self._variant.can_promote_to(_type) { return false; }
+                    self._default_promotion = _type;
+                }
+
+                self.drag(from, to);
+                self._default_promotion = _default;
+            },
+            ChessMove::Drop { piece, to } => self.drop_piece(piece, to),
+        }
+
+        // The turn is only handed over after a move was made
+        return self.w_lock != w_lock;
+    }
+
+    // Half moves since the last capture or pawn move
+    // Public
+    pub fn halfmove_clock(&self) -> u16
+    {
+        return self._halfmove_clock;
+    }
+
+    // Number of the current full move, starting at 1
+    // Public
+    pub fn fullmove(&self) -> u16
+    {
+        return self._fullmove;
+    }
+
+    // Same as `ChessBoard::promotion_check`, captures included
+    fn __is_promotion(&self, from: u8, to: u8) -> bool
+    {
+        let _from_el: ChessPiece = self.board[from as usize];
+        let _y: u8 = self.__pos(to).y;
+
+        let case1: bool = _from_el == ChessPiece::WPawn && _y == self._height-1;
+        let case2: bool = _from_el == ChessPiece::BPawn && _y == 0;
+
+        return case1 || case2;
+    }
+
+    // The piece types a pawn can be promoted to when listing the moves,
+    // the default ones and the default promotion of the board, and how
+    // many of them there are
+    fn __promotion_types(&self) -> ([ChessPieceType; 6], usize)
+    {
+        let mut types: [ChessPieceType; 6] = [ChessPieceType::Queen, ChessPieceType::Rook,
+            ChessPieceType::Bishop, ChessPieceType::Knight, ChessPieceType::King, self._default_promotion];
+        let candidates: usize = if types[..5].contains(&self._default_promotion) { 5 } else { 6 };
+
+        let mut count: usize = 0;
+        for i in 0..candidates
+        {
+            if self._variant.can_promote_to(types[i])
+            {
+                types[count] = types[i];
+                count += 1;
+            }
+        }
+
+        return (types, count);
+    }
+}
@@ -5,4 +5,371 @@ fn test_chessboard_drag()
 {
     let mut board: ChessBoard = ChessBoard::init_position();
     board.drag(0o01, 0o22);
-}
\ No newline at end of file
+}
+#[test]
+fn test_chessboard_checkmate()
+{
+    // Fool's mate
+    let mut board: ChessBoard = ChessBoard::init_position();
+    board.drag(0o15, 0o25);
+    board.drag(0o64, 0o44);
+    board.drag(0o16, 0o36);
+    assert!(board.get_state() == ChessState::On);
+    board.drag(0o73, 0o37);
+    assert!(board.get_state() == ChessState::Checkmate);
+}
+
+#[test]
+fn test_chessboard_king_safety()
+{
+    let mut board: ChessBoard = ChessBoard::init_position();
+    board.drag(0o14, 0o34);
+    board.drag(0o65, 0o45);
+    board.drag(0o03, 0o47);
+
+    // The f7 pawn is pinned, g6 is the only way to block
+    board.drag(0o66, 0o56);
+    assert!(board.get_piece(0o56) == ChessPiece::BPawn);
+    assert!(board.white_turn());
+
+    // The king cannot walk into the pawn's attack
+    let mut board: ChessBoard = ChessBoard::init_position();
+    board.drag(0o14, 0o34);
+    board.drag(0o63, 0o43);
+    board.drag(0o04, 0o14);
+    board.drag(0o43, 0o33);
+    board.drag(0o14, 0o24);
+    assert!(board.get_piece(0o14) == ChessPiece::WKing);
+    assert!(board.white_turn());
+}
+
+#[test]
+fn test_chessboard_castling()
+{
+    let mut board: ChessBoard = ChessBoard::init_position();
+    board.drag(0o14, 0o34);
+    board.drag(0o64, 0o44);
+    board.drag(0o06, 0o25);
+    board.drag(0o76, 0o55);
+    board.drag(0o05, 0o14);
+    board.drag(0o75, 0o64);
+
+    // King two steps towards the rook
+    board.drag(0o04, 0o06);
+    assert!(board.get_piece(0o06) == ChessPiece::WKing);
+    assert!(board.get_piece(0o05) == ChessPiece::WRook);
+    assert!(board.get_piece(0o07) == ChessPiece::Empty);
+    assert!(board.castling_rights() == [None, None, Some(0o77), Some(0o70)]);
+
+    // King takes own rook
+    board.drag(0o74, 0o77);
+    assert!(board.get_piece(0o76) == ChessPiece::BKing);
+    assert!(board.get_piece(0o75) == ChessPiece::BRook);
+    assert!(board.castling_rights() == [None; 4]);
+}
+
+#[test]
+fn test_chessboard_castling_through_check()
+{
+    // The bishop on c4 attacks f1
+    let mut board: ChessBoard = ChessBoard::from_fen("4k3/8/8/8/2b5/8/8/4K2R w K - 0 1").unwrap();
+    board.drag(0o04, 0o06);
+    assert!(board.get_piece(0o04) == ChessPiece::WKing);
+    assert!(board.white_turn());
+
+    // Not allowed in check either
+    let mut board: ChessBoard = ChessBoard::from_fen("4k3/8/8/8/1b6/8/8/4K2R w K - 0 1").unwrap();
+    board.drag(0o04, 0o07);
+    assert!(board.get_piece(0o04) == ChessPiece::WKing);
+
+    // Attacks on the rook don't matter
+    let mut board: ChessBoard = ChessBoard::from_fen("b3k3/8/8/8/8/8/8/4K2R w K - 0 1").unwrap();
+    board.drag(0o04, 0o06);
+    assert!(board.get_piece(0o06) == ChessPiece::WKing);
+}
+
+#[test]
+fn test_chessboard_chess960()
+{
+    // 518 is the default setup
+    assert!(ChessBoard::chess960_rank(518) == [ChessPieceType::Rook, ChessPieceType::Knight,
+        ChessPieceType::Bishop, ChessPieceType::Queen, ChessPieceType::King,
+        ChessPieceType::Bishop, ChessPieceType::Knight, ChessPieceType::Rook]);
+    assert!(ChessBoard::init_chess960(518).get_board() == ChessBoard::init_position().get_board());
+
+    let mut ranks: Vec<[ChessPieceType; 8]> = Vec::new();
+    for i in 0..960
+    {
+        let rank: [ChessPieceType; 8] = ChessBoard::chess960_rank(i);
+        let find = |t: ChessPieceType| -> Vec<usize> {
+            (0..8).filter(|x| rank[*x] == t).collect()
+        };
+
+        // Bishops on opposite colors, the king between the rooks
+        let bishops: Vec<usize> = find(ChessPieceType::Bishop);
+        let rooks: Vec<usize> = find(ChessPieceType::Rook);
+        let king: Vec<usize> = find(ChessPieceType::King);
+        assert!(bishops.len() == 2 && bishops[0] % 2 != bishops[1] % 2);
+        assert!(rooks.len() == 2 && king.len() == 1);
+        assert!(rooks[0] < king[0] && king[0] < rooks[1]);
+        assert!(find(ChessPieceType::Queen).len() == 1 && find(ChessPieceType::Knight).len() == 2);
+
+        ranks.push(rank);
+    }
+
+    // ... and every setup is different
+    ranks.sort_by_key(|rank| format!("{:?}", rank));
+    ranks.dedup();
+    assert!(ranks.len() == 960);
+}
+
+#[test]
+fn test_chessboard_chess960_castling()
+{
+    // Position 0: BBQNNRKR, the king castles queen side with the rook on f1
+    // and ends up on c1 with the rook on d1
+    let mut board: ChessBoard = ChessBoard::from_fen(
+        "1r1k1r2/pppppppp/8/8/8/8/PPPPPPPP/1R1K1R2 w FBfb - 0 1").unwrap();
+    board.set_chess960(true);
+
+    // A normal king step, not castling
+    board.drag(0o03, 0o02);
+    assert!(board.get_piece(0o02) == ChessPiece::WKing);
+    assert!(board.castling_rights()[..2] == [None, None]);
+
+    let mut board: ChessBoard = ChessBoard::from_fen(
+        "1r1k1r2/pppppppp/8/8/8/8/PPPPPPPP/1R1K1R2 w FBfb - 0 1").unwrap();
+    board.set_chess960(true);
+
+    // King takes the b1 rook
+    board.drag(0o03, 0o01);
+    assert!(board.get_piece(0o02) == ChessPiece::WKing);
+    assert!(board.get_piece(0o03) == ChessPiece::WRook);
+    assert!(board.get_piece(0o01) == ChessPiece::Empty);
+    assert!(board.get_piece(0o05) == ChessPiece::WRook);
+
+    // The king stays on g1 while castling king side
+    let mut board: ChessBoard = ChessBoard::from_fen(
+        "4k3/8/8/8/8/8/8/R5KR w HA - 0 1").unwrap();
+    board.set_chess960(true);
+    board.drag(0o06, 0o07);
+    assert!(board.get_piece(0o06) == ChessPiece::WKing);
+    assert!(board.get_piece(0o05) == ChessPiece::WRook);
+}
+
+#[test]
+fn test_chessboard_dimensions()
+{
+    // Los Alamos chess, 6 x 6 without bishops
+    let mut board: ChessBoard = ChessBoard::init_from_rank(&[ChessPieceType::Rook,
+        ChessPieceType::Knight, ChessPieceType::Queen, ChessPieceType::King,
+        ChessPieceType::Knight, ChessPieceType::Rook], 6);
+    assert!(board.width() == 6 && board.height() == 6);
+    assert!(board.get_squares()[3] == ChessPiece::WKing);
+    assert!(board.get_squares()[33] == ChessPiece::BKing);
+    assert!(board.get_squares()[6..12].iter().all(|p| *p == ChessPiece::WPawn));
+
+    // The knight jumps over the pawns, the pawn walks to the 3rd rank
+    board.drag(1, 14);
+    assert!(board.get_piece(14) == ChessPiece::WKnight);
+    board.drag(26, 20);
+    assert!(board.get_piece(20) == ChessPiece::BPawn);
+
+    // Promotion happens on the last rank of the board
+    let mut board: ChessBoard = ChessBoard::from_fen("k5/4P1/6/6/6/K5 w - - 0 1").unwrap();
+    assert!(board.promotion_check(28, 34));
+    board.drag(28, 34);
+    assert!(board.get_piece(34) == ChessPiece::WQueen);
+
+    // Attacks reach across the whole board: the rook on j10 pins the knight
+    let mut board: ChessBoard = ChessBoard::from_fen(
+        "k8r/10/10/10/10/10/10/10/9N/9K w - - 0 1").unwrap();
+    board.drag(19, 38);
+    assert!(board.get_piece(19) == ChessPiece::WKnight);
+    board.drag(9, 8);
+    assert!(board.get_piece(8) == ChessPiece::WKing);
+}
+
+#[test]
+fn test_chessboard_dimensions_castling()
+{
+    // On 10 files the king castles to the i-file, the rook to the h-file
+    let mut board: ChessBoard = ChessBoard::from_fen(
+        "r3k4r/pppppppppp/10/10/10/10/PPPPPPPPPP/R3K4R w KQkq - 0 1").unwrap();
+    board.drag(4, 8);
+    assert!(board.get_piece(8) == ChessPiece::WKing);
+    assert!(board.get_piece(7) == ChessPiece::WRook);
+    assert!(board.get_piece(9) == ChessPiece::Empty);
+
+    board.drag(74, 72);
+    assert!(board.get_piece(72) == ChessPiece::BKing);
+    assert!(board.get_piece(73) == ChessPiece::BRook);
+    assert!(board.get_piece(70) == ChessPiece::Empty);
+}
+
+#[test]
+fn test_chessboard_fairy_pieces()
+{
+    // Capablanca chess, the archbishop on c1 and the chancellor on h1
+    let mut board: ChessBoard = ChessBoard::init_from_rank(&[ChessPieceType::Rook,
+        ChessPieceType::Knight, ChessPieceType::Archbishop, ChessPieceType::Bishop,
+        ChessPieceType::Queen, ChessPieceType::King, ChessPieceType::Bishop,
+        ChessPieceType::Chancellor, ChessPieceType::Knight, ChessPieceType::Rook], 8);
+    assert_eq!(board.to_fen(), "rnabqkbcnr/pppppppppp/10/10/10/10/PPPPPPPPPP/RNABQKBCNR w KQkq - 0 1");
+
+    // The archbishop jumps like a knight, the chancellor too
+    board.drag(2, 21);
+    assert!(board.get_piece(21) == ChessPiece::WArchbishop);
+    board.drag(77, 56);
+    assert!(board.get_piece(56) == ChessPiece::BChancellor);
+
+    // ... but the archbishop doesn't move like a rook
+    board.drag(21, 41);
+    assert!(board.get_piece(21) == ChessPiece::WArchbishop);
+
+    // Camel and zebra leap over everything
+    let mut board: ChessBoard = ChessBoard::from_fen("k7/8/8/8/8/8/PPPPPPPP/K1L1Z3 w - - 0 1").unwrap();
+    board.drag(2, 27);
+    assert!(board.get_piece(27) == ChessPiece::WCamel);
+    board.drag(56, 57);
+    board.drag(4, 26);
+    assert!(board.get_piece(26) == ChessPiece::WZebra);
+
+    // The grasshopper lands right behind the piece it hops over
+    let mut board: ChessBoard = ChessBoard::from_fen("k7/8/8/8/8/8/8/K1GP2p1 w - - 0 1").unwrap();
+    board.drag(2, 5);
+    assert!(board.get_piece(2) == ChessPiece::WGrasshopper);
+    board.drag(2, 4);
+    assert!(board.get_piece(4) == ChessPiece::WGrasshopper);
+    board.drag(56, 57);
+    board.drag(4, 7);
+    assert!(board.get_piece(7) == ChessPiece::WGrasshopper);
+    assert!(board.get_piece(6) == ChessPiece::BPawn);
+}
+
+#[test]
+fn test_chessboard_fairy_threats()
+{
+    // The amazon checks like a knight and covers the blocks around
+    let board: ChessBoard = ChessBoard::from_fen("k7/8/1M6/8/8/8/8/K7 b - - 0 1").unwrap();
+    assert!(board.get_state() == ChessState::Checkmate);
+
+    // Like a bishop on b8, like a knight on b7
+    let mut board: ChessBoard = ChessBoard::from_fen("k7/8/3M4/8/8/8/8/K7 b - - 0 1").unwrap();
+    board.drag(56, 57);
+    assert!(board.get_piece(56) == ChessPiece::BKing);
+    board.drag(56, 49);
+    assert!(board.get_piece(56) == ChessPiece::BKing);
+    board.drag(56, 48);
+    assert!(board.get_piece(48) == ChessPiece::BKing);
+
+    // The grasshopper attacks a1 by hopping over the rook
+    let mut board: ChessBoard = ChessBoard::from_fen("k7/8/8/8/g7/8/R7/1K6 w - - 0 1").unwrap();
+    board.drag(1, 0);
+    assert!(board.get_piece(1) == ChessPiece::WKing);
+    board.drag(8, 9);
+    board.drag(56, 48);
+    board.drag(1, 0);
+    assert!(board.get_piece(0) == ChessPiece::WKing);
+}
+
+#[test]
+fn test_chessboard_fairy_registry()
+{
+    let mut board: ChessBoard = ChessBoard::from_fen("k7/8/8/8/8/8/8/K1Z5 w - - 0 1").unwrap();
+    assert!(board.register_piece(ChessPieceType::Queen, "N").is_err());
+    assert!(board.register_piece(ChessPieceType::Zebra, "X").is_err());
+
+    // The zebra becomes a nightrider
+    board.register_piece(ChessPieceType::Zebra, "NN").unwrap();
+    assert!(board.get_movement(ChessPieceType::Zebra) == Some(&betza::PieceMovement::parse("NN").unwrap()));
+    board.drag(2, 22);
+    assert!(board.get_piece(22) == ChessPiece::WZebra);
+
+    // Promotion into a fairy piece
+    let mut board: ChessBoard = ChessBoard::from_fen("k7/4P3/8/8/8/8/8/K7 w - - 0 1").unwrap();
+    board.set_promotion(ChessPieceType::Chancellor);
+    board.drag(52, 60);
+    assert!(board.get_piece(60) == ChessPiece::WChancellor);
+    assert!(board.to_fen().starts_with("k3C3/"));
+}
+
+#[test]
+fn test_chessboard_generate_moves()
+{
+    let mut board: ChessBoard = ChessBoard::from_fen("r3k2r/1P6/8/8/8/8/8/R3K2R w KQkq - 0 1").unwrap();
+    let moves: Vec<ChessMove> = board.legal_moves();
+
+    let mut list: MoveList = MoveList::new();
+    assert!(board.generate_moves(&mut list));
+    assert_eq!(list.as_slice(), moves.as_slice());
+
+    // The list is cleared first and keeps the first moves when it's full
+    let mut small: MoveList<8> = MoveList::new();
+    small.push(ChessMove::drag(0o00, 0o01));
+    assert!(!board.generate_moves(&mut small));
+    assert_eq!(small.as_slice(), &moves[..8]);
+
+    let mut board: ChessBoard = ChessBoard::init_variant(variant::from_name("crazyhouse").unwrap());
+    board.add_to_pocket(true, ChessPieceType::Knight);
+    board.add_to_pocket(true, ChessPieceType::Knight);
+    assert!(board.generate_moves(&mut list));
+    assert_eq!(list.len(), 20 + 32);
+    assert_eq!(list.as_slice(), board.legal_moves().as_slice());
+}
+
+#[test]
+fn test_chessboard_en_passant()
+{
+    let mut board: ChessBoard = ChessBoard::from_fen("rnbqkbnr/ppp1pppp/8/8/3p4/8/PPPPPPPP/RNBQKBNR w KQkq - 0 1").unwrap();
+    board.drag(0o14, 0o34);
+    assert!(board.get_en_passant() == Some(0o24));
+    assert!(board.is_capture(0o33, 0o24) && !board.is_capture(0o33, 0o23));
+
+    board.drag(0o33, 0o24);
+    assert!(board.get_piece(0o24) == ChessPiece::BPawn);
+    assert!(board.get_piece(0o34).is_empty() && board.get_piece(0o33).is_empty());
+    assert!(board.piece_count(ChessPiece::WPawn) == 7);
+    assert!(board.get_en_passant().is_none());
+
+    // Only right after the two steps
+    let mut board: ChessBoard = ChessBoard::from_fen("4k3/8/8/8/3p4/8/4P3/4K3 w - - 0 1").unwrap();
+    board.drag(0o14, 0o34);
+    board.drag(0o74, 0o64);
+    board.drag(0o04, 0o03);
+    assert!(!board.legal_moves().contains(&ChessMove::drag(0o33, 0o24)));
+
+    // Taking the pawn would leave the king in check along the rank
+    let mut board: ChessBoard = ChessBoard::from_fen("8/8/8/8/k2p3R/8/4P3/4K3 w - - 0 1").unwrap();
+    board.drag(0o14, 0o34);
+    assert!(board.get_en_passant().is_none());
+    assert!(!board.legal_moves().contains(&ChessMove::drag(0o33, 0o24)));
+}
+
+#[test]
+fn test_chessboard_perft()
+{
+    fn perft(board: &ChessBoard, depth: u8) -> u64
+    {
+        let mut board: ChessBoard = board.clone();
+        let moves: Vec<ChessMove> = board.legal_moves();
+        if depth == 1
+        {
+            return moves.len() as u64;
+        }
+
+        return moves.iter().map(|mv|
+        {
+            let mut child: ChessBoard = board.clone();
+            child.make_move(*mv);
+            perft(&child, depth-1)
+        }).sum();
+    }
+
+    // Position 3 of the perft results of the chess programming wiki, full
+    // of en passant captures and pins along the rank
+    let board: ChessBoard = ChessBoard::from_fen("8/2p5/3p4/KP5r/1R3p1k/8/4P1P1/8 w - - 0 1").unwrap();
+    assert_eq!(perft(&board, 1), 14);
+    assert_eq!(perft(&board, 2), 191);
+    assert_eq!(perft(&board, 3), 2812);
+}
@@ -0,0 +1,351 @@
+// Searching stored games for positions that match a pattern rather than an
+// exact position: the material on the board, pieces on given blocks and
+// the structure of the pawns. A query is a list of `Pattern`s that all
+// have to hold, and the search gives the first position of every game
+// where they do.
+//
+// Material is written like `KRPPPPvKRPPP`, the pieces of white before the
+// `v` and the ones of black after it. It's checked against the piece
+// counts kept by the board, so it's a cheap test to put first in a query.
+//
+// Pawn masks have a bit for every block of an 8 x 8 board, the bit of
+// block `i` being `1 << i`. Boards of other sizes never match them
+use super::*;
+use game::{Game, PgnReader};
+use std::io::{self, BufRead};
+
+#[cfg(test)]
+mod tests;
+
+// Every piece apart from `Empty`
+const PIECES: [ChessPiece; PIECE_KINDS - 1] = [
+    ChessPiece::BKing, ChessPiece::BQueen, ChessPiece::BRook, ChessPiece::BBishop, ChessPiece::BKnight, ChessPiece::BPawn,
+    ChessPiece::WKing, ChessPiece::WQueen, ChessPiece::WRook, ChessPiece::WBishop, ChessPiece::WKnight, ChessPiece::WPawn,
+    ChessPiece::BArchbishop, ChessPiece::BChancellor, ChessPiece::BAmazon, ChessPiece::BCamel, ChessPiece::BZebra, ChessPiece::BGrasshopper,
+    ChessPiece::WArchbishop, ChessPiece::WChancellor, ChessPiece::WAmazon, ChessPiece::WCamel, ChessPiece::WZebra, ChessPiece::WGrasshopper,
+];
+
+// A position of a stored game: the index of the game and the number of
+// half moves played, 0 for its start position
+#[derive(Debug, PartialEq, Eq, Clone, Copy)]
+pub struct GameRef
+{
+    game: usize,
+    ply: usize,
+}
+
+impl GameRef
+{
+    pub fn new(game: usize, ply: usize) -> GameRef
+    {
+        return GameRef { game, ply };
+    }
+
+    pub fn get_game(&self) -> usize
+    {
+        return self.game;
+    }
+
+    pub fn get_ply(&self) -> usize
+    {
+        return self.ply;
+    }
+}
+
+// How many of every piece there are on the board, kings included
+#[derive(Debug, PartialEq, Eq, Clone, Copy)]
+pub struct Material
+{
+    counts: [u8; PIECE_KINDS],
+}
+
+impl Material
+{
+    // Reads a signature like `KRPPPPvKRPPP` or `KQvKR`. The letters are
+    // the ones of FEN, in either case. `None` for more than 255 of a piece
+    pub fn parse(signature: &str) -> Option<Material>
+    {
+        let (white, black) = signature.split_once(['v', 'V'])?;
+        let mut counts: [u8; PIECE_KINDS] = [0; PIECE_KINDS];
+
+        for (side, white) in [(white, true), (black, false)]
+        {
+            for c in side.chars()
+            {
+                let c: char = if white { c.to_ascii_uppercase() } else { c.to_ascii_lowercase() };
+                let piece: ChessPiece = fen::__char_to_piece(c)?;
+                counts[piece as usize] = counts[piece as usize].checked_add(1)?;
+            }
+        }
+
+        return Some(Material { counts });
+    }
+
+    pub fn from_board(board: &ChessBoard) -> Material
+    {
+        let mut counts: [u8; PIECE_KINDS] = [0; PIECE_KINDS];
+        for piece in PIECES
+        {
+            counts[piece as usize] = board.piece_count(piece);
+        }
+        return Material { counts };
+    }
+
+    pub fn get_count(&self, piece: ChessPiece) -> u8
+    {
+        return self.counts[piece as usize];
+    }
+
+    // The same material with the colors swapped
+    pub fn flipped(&self) -> Material
+    {
+        let mut counts: [u8; PIECE_KINDS] = [0; PIECE_KINDS];
+        for piece in PIECES
+        {
+            let other: ChessPiece = ChessPiece::from_type(piece.get_type().unwrap(), !piece.is_white());
+            counts[other as usize] = self.counts[piece as usize];
+        }
+        return Material { counts };
+    }
+
+    pub fn is_on(&self, board: &ChessBoard) -> bool
+    {
+        return PIECES.iter().all(|piece| board.piece_count(*piece) == self.counts[*piece as usize]);
+    }
+}
+
+#[derive(Debug, PartialEq, Eq, Clone, Copy)]
+pub enum Pattern
+{
+    // Exactly this material, or with `either_color` also the same one with
+    // the colors swapped
+    Material { material: Material, either_color: bool },
+    // The piece is on the block
+    PieceOn { block: u8, piece: ChessPiece },
+    // The pawns of the color are exactly the ones of the mask
+    PawnStructure { white: bool, mask: u64 },
+    // At least one pawn of the color is on a block of the mask
+    PawnIn { white: bool, mask: u64 },
+    // No pawn of the color is on a block of the mask
+    NoPawnIn { white: bool, mask: u64 },
+}
+
+impl Pattern
+{
+    pub fn matches(&self, board: &ChessBoard) -> bool
+    {
+        let pawns = |white: bool| pawn_mask(board, white);
+        return match *self
+        {
+            Pattern::Material { material, either_color } => material.is_on(board) || (either_color && material.flipped().is_on(board)),
+            Pattern::PieceOn { block, piece } => (block as usize) < board.get_squares().len() && board.get_piece(block) == piece,
+            Pattern::PawnStructure { white, mask } => pawns(white) == Some(mask),
+            Pattern::PawnIn { white, mask } => pawns(white).is_some_and(|pawns| pawns & mask != 0),
+            Pattern::NoPawnIn { white, mask } => pawns(white).is_some_and(|pawns| pawns & mask == 0),
+        };
+    }
+}
+
+// The blocks of the pawns of the color, `None` if the board isn't 8 x 8
+pub fn pawn_mask(board: &ChessBoard, white: bool) -> Option<u64>
+{
+    if board.width() != 8 || board.height() != 8
+    {
+        return None;
+    }
+
+    let pawn: ChessPiece = if white { ChessPiece::WPawn } else { ChessPiece::BPawn };
+    let mask: u64 = board.get_squares().iter().enumerate()
+        .filter(|(_, piece)| **piece == pawn)
+        .fold(0, |mask, (i, _)| mask | 1 << i);
+
+    return Some(mask);
+}
+
+// The blocks of a file, 0 for the a file
+pub fn file_mask(file: u8) -> u64
+{
+    return 0x0101_0101_0101_0101 << file;
+}
+
+// The patterns that all have to hold
+#[derive(Debug, Clone, Default)]
+pub struct PositionQuery
+{
+    patterns: Vec<Pattern>,
+}
+
+impl PositionQuery
+{
+    pub fn new() -> PositionQuery
+    {
+        return PositionQuery { patterns: Vec::new() };
+    }
+
+    pub fn add(&mut self, pattern: Pattern)
+    {
+        self.patterns.push(pattern);
+    }
+
+    // The material of the signature, see `Material::parse`. Returns false
+    // if it can't be read
+    pub fn add_material(&mut self, signature: &str, either_color: bool) -> bool
+    {
+        return match Material::parse(signature)
+        {
+            Some(material) => {
+                self.add(Pattern::Material { material, either_color });
+                true
+            },
+            None => false,
+        };
+    }
+
+    // A pawn of the color on the file with none on the files next to it,
+    // file 3 for an isolated queen pawn
+    pub fn add_isolated_pawn(&mut self, white: bool, file: u8)
+    {
+        let mut neighbours: u64 = 0;
+        if file > 0 { neighbours |= file_mask(file - 1); }
+        if file < 7 { neighbours |= file_mask(file + 1); }
+
+        self.add(Pattern::PawnIn { white, mask: file_mask(file) });
+        self.add(Pattern::NoPawnIn { white, mask: neighbours });
+    }
+
+    // The pawns of both colors exactly as on the board
+    pub fn add_pawn_structure(&mut self, board: &ChessBoard)
+    {
+        for white in [true, false]
+        {
+            // No mask matches a board of another size
+            let mask: u64 = pawn_mask(board, white).unwrap_or(u64::MAX);
+            self.add(Pattern::PawnStructure { white, mask });
+        }
+    }
+
+    pub fn get_patterns(&self) -> &[Pattern]
+    {
+        return &self.patterns;
+    }
+
+    pub fn matches(&self, board: &ChessBoard) -> bool
+    {
+        return self.patterns.iter().all(|pattern| pattern.matches(board));
+    }
+}
+
+// What came of `GameDatabase::read_pgn`
+#[derive(Debug, PartialEq, Eq, Clone, Copy, Default)]
+pub struct ReadSummary
+{
+    added: usize,
+    // Games that couldn't be read
+    skipped: usize,
+}
+
+impl ReadSummary
+{
+    pub fn get_added(&self) -> usize
+    {
+        return self.added;
+    }
+
+    pub fn get_skipped(&self) -> usize
+    {
+        return self.skipped;
+    }
+}
+
+#[derive(Default)]
+pub struct GameDatabase
+{
+    games: Vec<Game>,
+}
+
+impl GameDatabase
+{
+    pub fn new() -> GameDatabase
+    {
+        return GameDatabase { games: Vec::new() };
+    }
+
+    // Returns the index of the game, the one of `GameRef::get_game`
+    pub fn add_game(&mut self, game: Game) -> usize
+    {
+        self.games.push(game);
+        return self.games.len() - 1;
+    }
+
+    // Adds the games of a PGN file one at a time, see `PgnReader`. Games
+    // that can't be read are skipped
+    pub fn read_pgn(&mut self, reader: impl BufRead) -> io::Result<ReadSummary>
+    {
+        let mut summary: ReadSummary = ReadSummary::default();
+        for pgn in PgnReader::new(reader)
+        {
+            match Game::from_pgn(&pgn?)
+            {
+                Ok(game) => {
+                    self.add_game(game);
+                    summary.added += 1;
+                },
+                Err(_) => summary.skipped += 1,
+            };
+        }
+
+        return Ok(summary);
+    }
+
+    pub fn get_game(&self, index: usize) -> Option<&Game>
+    {
+        return self.games.get(index);
+    }
+
+    pub fn len(&self) -> usize
+    {
+        return self.games.len();
+    }
+
+    pub fn is_empty(&self) -> bool
+    {
+        return self.games.is_empty();
+    }
+
+    // The first position of every game that matches the query, in the
+    // order of the games
+    pub fn search(&self, query: &PositionQuery) -> Vec<GameRef>
+    {
+        let mut found: Vec<GameRef> = Vec::new();
+        for (index, game) in self.games.iter().enumerate()
+        {
+            let mut board: ChessBoard = game.get_start().clone();
+            for ply in 0..=game.get_moves().len()
+            {
+                if query.matches(&board)
+                {
+                    found.push(GameRef { game: index, ply });
+                    break;
+                }
+                if let Some(mv) = game.get_moves().get(ply)
+                {
+                    board.make_move(*mv);
+                }
+            }
+        }
+
+        return found;
+    }
+
+    // The board of the game after the half moves of the reference
+    pub fn get_board(&self, at: GameRef) -> Option<ChessBoard>
+    {
+        let game: &Game = self.games.get(at.game)?;
+        let mut board: ChessBoard = game.get_start().clone();
+        for mv in game.get_moves().get(..at.ply)?
+        {
+            board.make_move(*mv);
+        }
+        return Some(board);
+    }
+}
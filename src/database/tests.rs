@@ -0,0 +1,63 @@
+use super::*;
+
+const PGN: &str = r#"[White "Ann"]
+[Black "Bob"]
+
+1. e4 c6 2. d4 d5 3. exd5 cxd5 4. c4 Nf6 5. Nc3 e6 6. Nf3 Be7 7. cxd5 Nxd5 *
+
+[White "Bob"]
+[Black "Ann"]
+
+1. d4 d5 2. c4 e6 3. Nc3 Nf6 *
+
+[FEN "r5k1/5ppp/8/8/8/8/PPPP4/R5K1 w - - 0 1"]
+[SetUp "1"]
+
+1. a4 h6 *
+
+[White "?"]
+
+1. e4 e4 *
+"#;
+
+#[test]
+fn test_database_search()
+{
+    let mut database: GameDatabase = GameDatabase::new();
+    let summary: ReadSummary = database.read_pgn(PGN.as_bytes()).unwrap();
+    assert!(summary.get_added() == 3 && summary.get_skipped() == 1 && database.len() == 3);
+
+    let found = |query: &PositionQuery| database.search(query).iter().map(|r| (r.get_game(), r.get_ply())).collect::<Vec<(usize, usize)>>();
+
+    // The isolated queen pawn of white after 7. cxd5
+    let mut query: PositionQuery = PositionQuery::new();
+    query.add_isolated_pawn(true, 3);
+    assert!(found(&query) == [(0, 13)]);
+
+    // Rook endings with four pawns against three
+    let mut query: PositionQuery = PositionQuery::new();
+    assert!(query.add_material("KRPPPPvKRPPP", false));
+    assert!(found(&query) == [(2, 0)]);
+    let mut query: PositionQuery = PositionQuery::new();
+    assert!(query.add_material("krpppvkrpppp", false));
+    assert!(found(&query).is_empty());
+    query = PositionQuery::new();
+    query.add_material("krpppvkrpppp", true);
+    query.add(Pattern::PieceOn { block: 0o00, piece: ChessPiece::WRook });
+    assert!(found(&query) == [(2, 0)]);
+    assert!(!query.add_material("KRvK?", false) && !query.add_material("KR", false));
+    // More of a piece than a count holds
+    assert!(Material::parse(&format!("K{}vK", "P".repeat(255))).unwrap().get_count(ChessPiece::WPawn) == 255);
+    assert!(Material::parse(&format!("K{}vK", "P".repeat(256))).is_none());
+
+    // The structure of the queen's gambit declined after 2... e6
+    let reached: ChessBoard = database.get_board(GameRef::new(1, 4)).unwrap();
+    let mut query: PositionQuery = PositionQuery::new();
+    query.add_pawn_structure(&reached);
+    assert!(query.get_patterns().len() == 2);
+    assert!(found(&query) == [(1, 4)]);
+
+    assert!(Material::from_board(&ChessBoard::init_position()).get_count(ChessPiece::BPawn) == 8);
+    assert!(file_mask(7) == 0x8080_8080_8080_8080);
+    assert!(database.get_board(GameRef::new(2, 3)).is_none());
+}
@@ -0,0 +1,136 @@
+// Text output of the board and the pieces.
+//
+// `ChessPiece` is written and read as its FEN letter. `ChessBoard` is
+// written as a diagram with the FEN letters, or with Unicode pieces when
+// formatted with `{:#}`, and its `Debug` output is the FEN and the hash of
+// the position, so failing `assert_eq!`s show where the pieces are
+use super::*;
+use fen::FenError;
+use core::fmt;
+use core::str::FromStr;
+
+#[cfg(test)]
+mod tests;
+
+impl fmt::Display for ChessPiece
+{
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result
+    {
+        return write!(f, "{}", self.to_fen_char());
+    }
+}
+
+// Reads a FEN letter, `.` is an empty block
+impl FromStr for ChessPiece
+{
+    type Err = FenError;
+
+    fn from_str(s: &str) -> Result<ChessPiece, FenError>
+    {
+        let mut chars = s.chars();
+        let c: char = chars.next().ok_or(FenError::MissingField)?;
+
+        if let Some(extra) = chars.next()
+        {
+            return Err(FenError::InvalidPiece(extra));
+        }
+
+        if c == '.'
+        {
+            return Ok(ChessPiece::Empty);
+        }
+
+        return fen::__char_to_piece(c).ok_or(FenError::InvalidPiece(c));
+    }
+}
+
+impl fmt::Display for ChessBoard
+{
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result
+    {
+        let unicode: bool = f.alternate();
+        let w: usize = self._width as usize;
+        let border: String = format!("    +{}+", "-".repeat(2*w + 1));
+        let files: String = (0..self._width).map(|x| format!(" {}", (b'a' + x) as char)).collect();
+
+        writeln!(f, "{}", border)?;
+        for y in (0..self._height).rev()
+        {
+            write!(f, "{:>3} |", y+1)?;
+            for x in 0..self._width
+            {
+                let _el: ChessPiece = self.board[self.__conv(x, y) as usize];
+                write!(f, " {}", if unicode { _el.to_unicode_char() } else { _el.to_fen_char() })?;
+            }
+            writeln!(f, " |")?;
+        }
+        writeln!(f, "{}", border)?;
+        writeln!(f, "     {}", files)?;
+
+        for white in [true, false]
+        {
+            let pocket: String = self.get_pocket(white).iter()
+                .map(|t| ChessPiece::from_type(*t, white).to_fen_char()).collect();
+            if !pocket.is_empty()
+            {
+                writeln!(f, "{} pocket: {}", if white { "White" } else { "Black" }, pocket)?;
+            }
+        }
+
+        let winner: &str = match self._winner
+        {
+            Some(true) => ", white wins",
+            Some(false) => ", black wins",
+            None => "",
+        };
+
+        return match self._state
+        {
+            ChessState::On => write!(f, "{} to move", if self.white_turn() { "White" } else { "Black" }),
+            ChessState::Checkmate => write!(f, "Checkmate{}", winner),
+            ChessState::Stalemate => write!(f, "Stalemate"),
+            ChessState::Aborted => write!(f, "Aborted"),
+            ChessState::VariantWin => write!(f, "{} is over{}", self._variant.name(), winner),
+            ChessState::VariantDraw => write!(f, "{} is over, draw", self._variant.name()),
+        };
+    }
+}
+
+impl fmt::Debug for ChessBoard
+{
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result
+    {
+        return f.debug_struct("ChessBoard")
+            .field("fen", &self.to_fen())
+            .field("hash", &format_args!("{:#018x}", self.hash()))
+            .field("variant", &self._variant.name())
+            .field("state", &self._state)
+            .finish();
+    }
+}
+
+impl ChessPiece
+{
+    // The Unicode chess symbol of the piece, `·` for an empty block. Fairy
+    // pieces don't have a symbol of their own and keep their FEN letter
+    pub fn to_unicode_char(self) -> char
+    {
+        return match self
+        {
+            ChessPiece::WKing =>    '♔',
+            ChessPiece::WQueen =>   '♕',
+            ChessPiece::WRook =>    '♖',
+            ChessPiece::WBishop =>  '♗',
+            ChessPiece::WKnight =>  '♘',
+            ChessPiece::WPawn =>    '♙',
+            ChessPiece::BKing =>    '♚',
+            ChessPiece::BQueen =>   '♛',
+            ChessPiece::BRook =>    '♜',
+            ChessPiece::BBishop =>  '♝',
+            ChessPiece::BKnight =>  '♞',
+            ChessPiece::BPawn =>    '♟',
+            ChessPiece::Empty =>    '·',
+            _ => self.to_fen_char(),
+        };
+    }
+}
@@ -0,0 +1,93 @@
+use super::*;
+use variant::Crazyhouse;
+
+#[test]
+fn test_chesspiece_display_from_str()
+{
+    assert_eq!(ChessPiece::WKnight.to_string(), "N");
+    assert_eq!(ChessPiece::BQueen.to_string(), "q");
+    assert_eq!(ChessPiece::Empty.to_string(), ".");
+    assert_eq!(ChessPiece::WKing.to_unicode_char(), '♔');
+
+    for piece in [ChessPiece::WPawn, ChessPiece::BKing, ChessPiece::WArchbishop, ChessPiece::BGrasshopper, ChessPiece::Empty]
+    {
+        assert_eq!(piece.to_string().parse::<ChessPiece>(), Ok(piece));
+    }
+
+    assert_eq!("".parse::<ChessPiece>(), Err(FenError::MissingField));
+    assert_eq!("x".parse::<ChessPiece>(), Err(FenError::InvalidPiece('x')));
+    assert_eq!("Nb".parse::<ChessPiece>(), Err(FenError::InvalidPiece('b')));
+}
+
+#[test]
+fn test_chessboard_display()
+{
+    let board: ChessBoard = ChessBoard::from_fen("4k3/8/8/8/8/8/4P3/4K3 b - - 0 1").unwrap();
+    let diagram: String = board.to_string();
+    let lines: Vec<&str> = diagram.lines().collect();
+
+    assert_eq!(lines[0], "    +-----------------+");
+    assert_eq!(lines[1], "  8 | . . . . k . . . |");
+    assert_eq!(lines[7], "  2 | . . . . P . . . |");
+    assert_eq!(lines[10], "      a b c d e f g h");
+    assert_eq!(lines[11], "Black to move");
+
+    assert!(format!("{:#}", board).contains("  1 | · · · · ♔ · · · |"));
+
+    // Fool's mate
+    let mut board: ChessBoard = ChessBoard::init_position();
+    for (from, to) in [(0o15, 0o25), (0o64, 0o44), (0o16, 0o36), (0o73, 0o37)]
+    {
+        board.drag(from, to);
+    }
+    assert!(board.to_string().ends_with("\nCheckmate, black wins"));
+}
+
+#[test]
+fn test_chessboard_debug_and_eq()
+{
+    let board: ChessBoard = ChessBoard::init_position();
+    let debug: String = format!("{:?}", board);
+    assert!(debug.contains(&board.to_fen()));
+    assert!(debug.contains(&format!("{:#018x}", board.hash())));
+
+    // The same position reached in two ways
+    let mut a: ChessBoard = ChessBoard::init_position();
+    a.drag(0o06, 0o25);
+    a.drag(0o76, 0o55);
+    a.drag(0o01, 0o22);
+    let mut b: ChessBoard = ChessBoard::init_position();
+    b.drag(0o01, 0o22);
+    b.drag(0o76, 0o55);
+    b.drag(0o06, 0o25);
+    assert_eq!(a.hash(), b.hash());
+    assert_eq!(a, b);
+
+    // Only the color to move differs
+    let white: ChessBoard = ChessBoard::from_fen("4k3/8/8/8/8/8/8/4K3 w - - 0 1").unwrap();
+    let black: ChessBoard = ChessBoard::from_fen("4k3/8/8/8/8/8/8/4K3 b - - 0 1").unwrap();
+    assert_ne!(white.hash(), black.hash());
+    assert_ne!(white, black);
+
+    // The pawn can be taken en passant, or it can't anymore
+    let fen: &str = "4k3/8/8/3pP3/8/8/8/4K3 w - {} 0 2";
+    let en_passant: ChessBoard = ChessBoard::from_fen(&fen.replace("{}", "d6")).unwrap();
+    let gone: ChessBoard = ChessBoard::from_fen(&fen.replace("{}", "-")).unwrap();
+    assert_ne!(en_passant.hash(), gone.hash());
+    assert_ne!(en_passant, gone);
+
+    // A promoted queen only counts where it goes back into a pocket
+    let fen: &str = "4k3/8/8/8/8/8/8/4K2Q{} w - - 0 1";
+    let promoted: ChessBoard = ChessBoard::from_fen(&fen.replace("{}", "~")).unwrap();
+    let queen: ChessBoard = ChessBoard::from_fen(&fen.replace("{}", "")).unwrap();
+    assert_eq!(promoted.hash(), queen.hash());
+    let promoted: ChessBoard = ChessBoard::from_fen_variant(&fen.replace("{}", "~[]"), Arc::new(Crazyhouse)).unwrap();
+    let queen: ChessBoard = ChessBoard::from_fen_variant(&fen.replace("{}", "[]"), Arc::new(Crazyhouse)).unwrap();
+    assert_ne!(promoted.hash(), queen.hash());
+
+    // The 17th knight of a pocket isn't taken for a pawn
+    let fen: &str = "4k3/8/8/8/8/8/8/4K3[NNNNNNNNNNNNNNNN{}] w - - 0 1";
+    let knights: ChessBoard = ChessBoard::from_fen_variant(&fen.replace("{}", "N"), Arc::new(Crazyhouse)).unwrap();
+    let pawn: ChessBoard = ChessBoard::from_fen_variant(&fen.replace("{}", "P"), Arc::new(Crazyhouse)).unwrap();
+    assert_ne!(knights.hash(), pawn.hash());
+}
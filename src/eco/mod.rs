@@ -0,0 +1,189 @@
+// ECO classification: the code and the name of the opening of a position
+// or a game. The table covers every code from A00 to E99 with its named
+// variations, each with the moves that lead to it. Positions are looked up
+// by their hash rather than by the moves, so a game that transposes into
+// an opening is found.
+//
+// `EcoTable::new` plays the moves of the whole table, with `std` the free
+// functions share one table built on first use
+use super::*;
+use game::Game;
+
+mod table;
+#[cfg(test)]
+mod tests;
+
+use table::OPENINGS;
+
+#[derive(Debug, PartialEq, Eq, Clone, Copy)]
+pub struct Opening
+{
+    eco: &'static str,
+    name: &'static str,
+    // In SAN, separated by spaces
+    moves: &'static str,
+}
+
+impl Opening
+{
+    // Like `B90`
+    pub fn get_eco(&self) -> &'static str
+    {
+        return self.eco;
+    }
+
+    // Like `Sicilian Defense: Najdorf Variation`
+    pub fn get_name(&self) -> &'static str
+    {
+        return self.name;
+    }
+
+    // The moves of the opening from the start position, in SAN
+    pub fn get_moves(&self) -> &'static str
+    {
+        return self.moves;
+    }
+
+    // The number of half moves of `get_moves`
+    pub fn plies(&self) -> usize
+    {
+        return self.moves.split_whitespace().count();
+    }
+}
+
+pub struct EcoTable
+{
+    // The index in `OPENINGS` by the hash of the position it leads to
+    positions: BTreeMap<u64, usize>,
+}
+
+impl EcoTable
+{
+    pub fn new() -> EcoTable
+    {
+        let mut positions: BTreeMap<u64, usize> = BTreeMap::new();
+
+        // The moves of the previous line with the board after each, lines
+        // next to each other mostly share their first moves
+        let mut path: Vec<(&str, ChessBoard)> = Vec::new();
+        for (index, (_, _, moves)) in OPENINGS.iter().enumerate()
+        {
+            let sans: Vec<&str> = moves.split_whitespace().collect();
+            let common: usize = path.iter().zip(sans.iter()).take_while(|((played, _), san)| played == *san).count();
+            path.truncate(common);
+
+            for san in sans[common..].iter()
+            {
+                let mut board: ChessBoard = match path.last()
+                {
+                    Some((_, board)) => board.clone(),
+                    None => ChessBoard::init_position(),
+                };
+
+                // Every line of the table is checked by the tests
+                let mv: ChessMove = board.from_san(san).unwrap();
+                board.make_move(mv);
+                path.push((san, board));
+            }
+
+            // The first entry wins when two lines reach the same position
+            if let Some((_, board)) = path.last()
+            {
+                positions.entry(board.hash()).or_insert(index);
+            }
+        }
+
+        return EcoTable { positions };
+    }
+
+    // The opening of the position itself, `None` if it isn't in the table
+    pub fn classify(&self, board: &ChessBoard) -> Option<Opening>
+    {
+        if board.get_variant().name() != "Standard"
+        {
+            return None;
+        }
+        return self.positions.get(&board.hash()).map(|index| __opening(*index));
+    }
+
+    // The deepest opening of the table that the game reached, the one
+    // with the most moves
+    pub fn classify_game(&self, game: &Game) -> Option<Opening>
+    {
+        let mut board: ChessBoard = game.get_start().clone();
+        let mut best: Option<Opening> = self.classify(&board);
+
+        for mv in game.get_moves()
+        {
+            board.make_move(*mv);
+            if let Some(opening) = self.classify(&board)
+            {
+                if best.is_none_or(|best| opening.plies() >= best.plies())
+                {
+                    best = Some(opening);
+                }
+            }
+        }
+
+        return best;
+    }
+
+    // Sets the `ECO` and `Opening` tags of the game to its opening, if it
+    // has one
+    pub fn tag_game(&self, game: &mut Game) -> Option<Opening>
+    {
+        let opening: Opening = self.classify_game(game)?;
+        game.set_tag("ECO", opening.eco);
+        game.set_tag("Opening", opening.name);
+
+        return Some(opening);
+    }
+
+    // The openings of the table, in order of their codes
+    pub fn openings(&self) -> impl Iterator<Item = Opening>
+    {
+        return (0..OPENINGS.len()).map(__opening);
+    }
+}
+
+impl Default for EcoTable
+{
+    fn default() -> Self
+    {
+        return EcoTable::new();
+    }
+}
+
+fn __opening(index: usize) -> Opening
+{
+    let (eco, name, moves) = OPENINGS[index];
+    return Opening { eco, name, moves };
+}
+
+#[cfg(feature = "std")]
+fn __table() -> &'static EcoTable
+{
+    static TABLE: std::sync::OnceLock<EcoTable> = std::sync::OnceLock::new();
+    return TABLE.get_or_init(EcoTable::new);
+}
+
+// See `EcoTable::classify`
+#[cfg(feature = "std")]
+pub fn classify(board: &ChessBoard) -> Option<Opening>
+{
+    return __table().classify(board);
+}
+
+// See `EcoTable::classify_game`
+#[cfg(feature = "std")]
+pub fn classify_game(game: &Game) -> Option<Opening>
+{
+    return __table().classify_game(game);
+}
+
+// See `EcoTable::tag_game`
+#[cfg(feature = "std")]
+pub fn tag_game(game: &mut Game) -> Option<Opening>
+{
+    return __table().tag_game(game);
+}
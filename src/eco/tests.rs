@@ -0,0 +1,81 @@
+use super::*;
+
+fn play(moves: &str) -> Game
+{
+    let mut game: Game = Game::new();
+    for san in moves.split_whitespace()
+    {
+        assert!(game.play_san(san), "{}", san);
+    }
+    return game;
+}
+
+#[test]
+fn test_eco_table()
+{
+    // Every line is legal and leads to its own position
+    let table: EcoTable = EcoTable::new();
+    for opening in table.openings()
+    {
+        let game: Game = play(opening.get_moves());
+        assert!(table.classify(game.get_board()) == Some(opening), "{}", opening.get_name());
+    }
+    assert!(table.positions.len() == OPENINGS.len());
+    assert!(table.openings().all(|o| o.get_eco().len() == 3));
+
+    // Every code from A00 to E99 has at least one opening
+    for volume in ['A', 'B', 'C', 'D', 'E']
+    {
+        for number in 0..100
+        {
+            let eco: String = format!("{}{:02}", volume, number);
+            assert!(table.openings().any(|o| o.get_eco() == eco), "{}", eco);
+        }
+    }
+}
+
+#[test]
+fn test_eco_classify()
+{
+    let table: EcoTable = EcoTable::new();
+    assert!(table.classify(&ChessBoard::init_position()).is_none());
+
+    // The English transposing into the Nimzo-Indian
+    let game: Game = play("c4 Nf6 Nc3 e6 d4 Bb4");
+    assert!(table.classify_game(&game).map(|o| o.get_eco()) == Some("E20"));
+
+    // The deepest opening, also after leaving the table
+    let mut game: Game = play("e4 c5 Nf3 d6 d4 cxd4 Nxd4 Nf6 Nc3 a6 Be3 e5 Nb3");
+    let opening: Opening = table.tag_game(&mut game).unwrap();
+    assert!(opening.get_name() == "Sicilian Defense: Najdorf Variation, English Attack" && opening.plies() == 11);
+    assert!(game.get_tag("ECO") == Some("B90") && game.get_tag("Opening") == Some(opening.get_name()));
+
+    let atomic: ChessBoard = ChessBoard::init_variant(Arc::new(variant::Atomic));
+    assert!(table.classify(&atomic).is_none());
+
+    // Deep lines of the table, each one played on past its end
+    let deep: [(&str, &str, &str); 8] = [
+        ("e4 e5 Nc3 Nf6 Bc4 Nxe4 Qh5 Nd6 Bb3 Nc6 Nb5 g6 Qf3 f5 Qd5 Qe7 Nxc7+ Kd8 Nxa8 b6 d3", "C27", "Vienna Game: Frankenstein-Dracula Variation"),
+        ("e4 e5 Nf3 Nc6 Bb5 Nf6 O-O Nxe4 d4 Nd6 Bxc6 dxc6 dxe5 Nf5 Qxd8+ Kxd8 Nc3 Ke8", "C67", "Ruy Lopez: Berlin Defense, Berlin Wall"),
+        ("e4 e5 Nf3 Nc6 Bc4 Bc5 c3 Nf6 d4 exd4 cxd4 Bb4+ Nc3 Nxe4 O-O Bxc3 d5 Bf6 Re1", "C53", "Italian Game: Giuoco Piano, Møller Gambit"),
+        ("e4 c5 Nf3 d6 d4 cxd4 Nxd4 Nf6 Nc3 a6 Bg5 e6 f4 Qb6 Qd2 Qxb2 Rb1 Qa3 f5", "B97", "Sicilian Defense: Najdorf Variation, Poisoned Pawn Accepted, Main Line"),
+        ("e4 e5 Nf3 Nc6 Bb5 a6 Ba4 Nf6 O-O Be7 Re1 b5 Bb3 O-O c3 d5 exd5 Nxd5 Nxe5 Nxe5 Rxe5 Nf6 d4", "C89", "Ruy Lopez: Marshall Attack, Original Marshall Attack"),
+        ("e4 e5 Nf3 Nc6 Bb5 a6 Ba4 Nf6 O-O Be7 Re1 b5 Bb3 d6 c3 O-O h3 Nb8 d4 Nbd7 Nbd2 Bb7 Bc2 Re8 Nf1 Bf8 Ng3 g6 Bg5 h6 Bd2 Bg7 a4 c5 d5 c4 b4 Nh7", "C95", "Ruy Lopez: Closed, Breyer Defense, Simagin Variation"),
+        ("d4 Nf6 c4 g6 Nc3 d5 cxd5 Nxd5 e4 Nxc3 bxc3 Bg7 Bc4 O-O Ne2 c5 O-O Nc6 Be3 Bg4 f3 Na5 Bxf7+ Rxf7 fxg4", "D87", "Grünfeld Defense: Exchange Variation, Seville Variation"),
+        ("d4 Nf6 c4 g6 Nc3 Bg7 e4 d6 Nf3 O-O Be2 e5 O-O Nc6 d5 Ne7 b4 Nh5 Re1 f5", "E97", "King's Indian Defense: Orthodox Variation, Bayonet Attack, Sokolov's Line"),
+    ];
+    for (moves, eco, name) in deep
+    {
+        let opening: Opening = table.classify_game(&play(moves)).unwrap();
+        assert!(opening.get_eco() == eco && opening.get_name() == name, "{}", moves);
+    }
+}
+
+#[test]
+#[cfg(feature = "std")]
+fn test_eco_shared_table()
+{
+    assert!(classify_game(&play("d4 d5 c4 e6 Nc3 Nf6 cxd5 exd5 Bg5")).map(|o| o.get_eco()) == Some("D35"));
+    assert!(classify(play("e4 e5").get_board()).map(|o| o.get_name()) == Some("King's Pawn Game"));
+    assert!(tag_game(&mut Game::new()).is_none());
+}
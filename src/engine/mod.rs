@@ -0,0 +1,320 @@
+// A small built in engine: iterative deepening alpha-beta search over the
+// legal moves with a material evaluation, a transposition table and a
+// capture search at the leaves. Good enough to play against and to get
+// hints from.
+//
+// A search can run on several threads sharing the transposition table,
+// see `search`. With one thread, the default, the same position always
+// gets the same result
+use super::*;
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::Arc;
+use std::thread;
+use std::time::{Duration, Instant};
+use search::Worker;
+use tt::TranspositionTable;
+
+mod search;
+#[cfg(test)]
+mod tests;
+pub mod tt;
+pub mod uci;
+
+// Score of a checkmate, reduced by the number of half moves it takes
+pub const MATE: i32 = 100_000;
+// Scores at least `MATE - MATE_PLIES` from zero are mates
+const MATE_PLIES: i32 = 1000;
+
+pub const MAX_THREADS: usize = 256;
+
+// How long the engine searches
+#[derive(Debug, PartialEq, Eq, Clone, Copy)]
+pub enum SearchLimit
+{
+    // Search this many half moves deep
+    Depth(u8),
+    // Search as deep as possible in the given time
+    Time(Duration),
+}
+
+impl SearchLimit
+{
+    // A part of the time left on the clock, so the engine doesn't run out
+    // of time over a whole game
+    pub fn from_clock(left: Duration, increment: Duration) -> SearchLimit
+    {
+        return SearchLimit::Time(Duration::min(left/30 + increment/2, left/2));
+    }
+}
+
+#[derive(Debug, Clone)]
+pub struct SearchResult
+{
+    best_move: Option<ChessMove>,
+    // In centipawns from the side of the color to move
+    score: i32,
+    depth: u8,
+    // Of all the threads
+    nodes: u64,
+    time: Duration,
+}
+
+impl SearchResult
+{
+    fn new(best_move: Option<ChessMove>, score: i32, depth: u8) -> SearchResult
+    {
+        return SearchResult { best_move, score, depth, nodes: 0, time: Duration::ZERO };
+    }
+
+    // The best move found, `None` if there are no legal moves
+    pub fn get_move(&self) -> Option<ChessMove>
+    {
+        return self.best_move;
+    }
+
+    pub fn get_score(&self) -> i32
+    {
+        return self.score;
+    }
+
+    // The depth of the last search that was completed
+    pub fn get_depth(&self) -> u8
+    {
+        return self.depth;
+    }
+
+    // The positions searched by all the threads together
+    pub fn get_nodes(&self) -> u64
+    {
+        return self.nodes;
+    }
+
+    pub fn get_time(&self) -> Duration
+    {
+        return self.time;
+    }
+
+    // Nodes per second
+    pub fn get_nps(&self) -> u64
+    {
+        let micros: u128 = u128::max(1, self.time.as_micros());
+        return (self.nodes as u128 * 1_000_000 / micros) as u64;
+    }
+}
+
+pub struct Engine
+{
+    threads: usize,
+    hash_mb: usize,
+    table: TranspositionTable,
+    // Stops the search from another thread, see `get_stop`
+    stop: Arc<AtomicBool>,
+}
+
+impl Engine
+{
+    // One thread and a table of `tt::DEFAULT_HASH_MB`
+    pub fn new() -> Engine
+    {
+        return Engine
+        {
+            threads: 1,
+            hash_mb: tt::DEFAULT_HASH_MB,
+            table: TranspositionTable::new(tt::DEFAULT_HASH_MB),
+            stop: Arc::new(AtomicBool::new(false)),
+        };
+    }
+
+    // The number of threads of a search, from 1 to `MAX_THREADS`
+    pub fn set_threads(&mut self, threads: usize)
+    {
+        self.threads = threads.clamp(1, MAX_THREADS);
+    }
+
+    pub fn get_threads(&self) -> usize
+    {
+        return self.threads;
+    }
+
+    // The size of the transposition table in megabytes, from 1 to
+    // `tt::MAX_HASH_MB`. The table is made again, empty
+    pub fn set_hash_size(&mut self, megabytes: usize)
+    {
+        self.hash_mb = megabytes.clamp(1, tt::MAX_HASH_MB);
+        self.table = TranspositionTable::new(self.hash_mb);
+    }
+
+    pub fn get_hash_size(&self) -> usize
+    {
+        return self.hash_mb;
+    }
+
+    // Setting the flag stops the running search, which returns what it
+    // found so far. It's never cleared by the engine: every search stops
+    // right away until it is
+    pub fn get_stop(&self) -> Arc<AtomicBool>
+    {
+        return self.stop.clone();
+    }
+
+    // Searches the best move for the color that is about to move. Helper
+    // threads search the same position until the main thread is done, the
+    // result is the one of the main thread with the nodes of all of them
+    pub fn search(&mut self, board: &ChessBoard, limit: SearchLimit) -> SearchResult
+    {
+        let started: Instant = Instant::now();
+        self.table.new_search();
+
+        let (max_depth, deadline) = match limit
+        {
+            SearchLimit::Depth(depth) => (depth, None),
+            SearchLimit::Time(time) => (u8::MAX, Some(started + time)),
+        };
+
+        let mut root: ChessBoard = board.clone();
+        let mut moves: Vec<ChessMove> = root.legal_moves();
+        Self::__order_moves(&root, &mut moves);
+
+        if moves.len() < 2
+        {
+            return SearchResult::new(moves.first().copied(), 0, 0);
+        }
+
+        let table: &TranspositionTable = &self.table;
+        let stop: &AtomicBool = &self.stop;
+        let done: AtomicBool = AtomicBool::new(false);
+
+        let (mut result, nodes) = thread::scope(|scope|
+        {
+            let helpers: Vec<thread::ScopedJoinHandle<u64>> = (1..self.threads).map(|i|
+            {
+                let mut moves: Vec<ChessMove> = moves.clone();
+                let count: usize = moves.len();
+                moves.rotate_left(i % count);
+                let (root, done) = (&root, &done);
+
+                scope.spawn(move ||
+                {
+                    let mut worker: Worker = Worker::new(table, [stop, done], deadline);
+                    worker.iterate(root, moves, 1 + (i % 2) as u8, u8::MAX);
+                    return worker.get_nodes();
+                })
+            }).collect();
+
+            let mut worker: Worker = Worker::new(table, [stop, &done], deadline);
+            let result: SearchResult = worker.iterate(&root, moves, 1, max_depth);
+            done.store(true, Ordering::Relaxed);
+
+            let helper_nodes: u64 = helpers.into_iter().map(|helper| helper.join().unwrap()).sum();
+            return (result, worker.get_nodes() + helper_nodes);
+        });
+
+        result.nodes = nodes;
+        result.time = started.elapsed();
+        return result;
+    }
+
+    // Evaluates the position from the side of the color to move, without
+    // searching
+    pub fn evaluate(board: &ChessBoard) -> i32
+    {
+        let mut score: i32 = 0;
+        let w: i32 = board.width() as i32;
+        let h: i32 = board.height() as i32;
+
+        for (i, _el) in board.get_squares().iter().enumerate()
+        {
+            let _type: ChessPieceType = match _el.get_type()
+            {
+                Some(_type) => _type,
+                None => continue,
+            };
+
+            // Pieces in the center and pawns that got further are worth a
+            // bit more
+            let x: i32 = i as i32 % w;
+            let y: i32 = i as i32 / w;
+            let center: i32 = (w - 1 - (2*x - w + 1).abs()) + (h - 1 - (2*y - h + 1).abs());
+            let advance: i32 = if _el.is_white() { y } else { h - 1 - y };
+            let bonus: i32 = match _type
+            {
+                ChessPieceType::Pawn => advance * 5 + center,
+                ChessPieceType::King => 0,
+                _ => center * 2,
+            };
+
+            let value: i32 = piece_value(_type) + bonus;
+            score += if _el.is_white() { value } else { -value };
+        }
+
+        for _type in board.get_pocket(true)
+        {
+            score += piece_value(*_type);
+        }
+
+        for _type in board.get_pocket(false)
+        {
+            score -= piece_value(*_type);
+        }
+
+        return if board.white_turn() { score } else { -score };
+    }
+
+    // The score of a finished game from the side of the color to move
+    fn __game_over_score(board: &ChessBoard, ply: i32) -> Option<i32>
+    {
+        if board.get_state() == ChessState::On
+        {
+            return None;
+        }
+
+        return Some(match board.get_winner()
+        {
+            Some(white) if white == board.white_turn() => MATE - ply,
+            Some(_) => -(MATE - ply),
+            None => 0,
+        });
+    }
+
+    // Captures of the most valuable pieces first
+    fn __order_moves(board: &ChessBoard, moves: &mut [ChessMove])
+    {
+        moves.sort_by_key(|mv| match *mv
+        {
+            ChessMove::Drag { to, promotion, .. } => {
+                let captured: i32 = board.get_piece(to).get_type().map_or(0, piece_value);
+                let promoted: i32 = promotion.map_or(0, piece_value);
+                -(captured + promoted)
+            },
+            ChessMove::Drop { .. } => 0,
+        });
+    }
+}
+
+impl Default for Engine
+{
+    fn default() -> Self
+    {
+        return Self::new();
+    }
+}
+
+// Value of a piece type in centipawns
+pub fn piece_value(_type: ChessPieceType) -> i32
+{
+    return match _type
+    {
+        ChessPieceType::Pawn =>         100,
+        ChessPieceType::Knight =>       320,
+        ChessPieceType::Bishop =>       330,
+        ChessPieceType::Rook =>         500,
+        ChessPieceType::Queen =>        900,
+        ChessPieceType::King =>         0,
+        ChessPieceType::Archbishop =>   800,
+        ChessPieceType::Chancellor =>   850,
+        ChessPieceType::Amazon =>       1200,
+        ChessPieceType::Camel =>        250,
+        ChessPieceType::Zebra =>        250,
+        ChessPieceType::Grasshopper =>  200,
+    };
+}
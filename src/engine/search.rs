@@ -0,0 +1,197 @@
+// One thread of a search. Every thread runs its own iterative deepening
+// over the same position, they only share the transposition table and the
+// flags that stop them (Lazy SMP). The helper threads start at other
+// depths and with the root moves in another order, so they fill the table
+// with positions the main thread needs soon.
+use super::*;
+use std::sync::atomic::{AtomicBool, Ordering};
+use tt::{score_from_table, score_to_table, Bound, Entry, TranspositionTable};
+
+pub struct Worker<'a>
+{
+    table: &'a TranspositionTable,
+    // Set by the caller of the search, and when the main thread is done
+    stops: [&'a AtomicBool; 2],
+    deadline: Option<Instant>,
+    nodes: u64,
+    stopped: bool,
+}
+
+impl<'a> Worker<'a>
+{
+    pub fn new(table: &'a TranspositionTable, stops: [&'a AtomicBool; 2], deadline: Option<Instant>) -> Worker<'a>
+    {
+        return Worker { table, stops, deadline, nodes: 0, stopped: false };
+    }
+
+    pub fn get_nodes(&self) -> u64
+    {
+        return self.nodes;
+    }
+
+    // Searches the root moves from `first_depth` to `max_depth`, the best
+    // one found first next time. The moves are the legal ones, at least two
+    pub fn iterate(&mut self, root: &ChessBoard, mut moves: Vec<ChessMove>, first_depth: u8, max_depth: u8) -> SearchResult
+    {
+        let mut result: SearchResult = SearchResult::new(moves.first().copied(), 0, 0);
+
+        for depth in first_depth..=max_depth
+        {
+            let mut alpha: i32 = -MATE-1;
+            let mut best: ChessMove = moves[0];
+
+            for mv in moves.iter()
+            {
+                let mut child: ChessBoard = root.clone();
+                child.make_move(*mv);
+
+                let score: i32 = -self.__negamax(&mut child, depth-1, 1, -MATE-1, -alpha);
+                if self.stopped { break; }
+
+                if score > alpha
+                {
+                    alpha = score;
+                    best = *mv;
+                }
+            }
+
+            // A search cut short is only used for the moves it looked at
+            if self.stopped && depth > first_depth
+            {
+                break;
+            }
+
+            result = SearchResult::new(Some(best), alpha, depth);
+
+            let i: usize = moves.iter().position(|mv| *mv == best).unwrap();
+            moves[..=i].rotate_right(1);
+
+            if self.stopped || alpha.abs() >= MATE - depth as i32
+            {
+                break;
+            }
+        }
+
+        return result;
+    }
+
+    fn __negamax(&mut self, board: &mut ChessBoard, depth: u8, ply: i32, mut alpha: i32, beta: i32) -> i32
+    {
+        self.nodes += 1;
+        if self.stops.iter().any(|stop| stop.load(Ordering::Relaxed)) || self.deadline.is_some_and(|deadline| Instant::now() >= deadline)
+        {
+            self.stopped = true;
+        }
+        if self.stopped
+        {
+            return 0;
+        }
+
+        if let Some(score) = Engine::__game_over_score(board, ply)
+        {
+            return score;
+        }
+
+        if depth == 0
+        {
+            return self.__quiescence(board, ply, alpha, beta, 4);
+        }
+
+        let hash: u64 = board.hash();
+        let entry: Option<Entry> = self.table.probe(hash);
+        if let Some(entry) = entry.filter(|entry| entry.get_depth() >= depth)
+        {
+            let score: i32 = score_from_table(entry.get_score(), ply);
+            match entry.get_bound()
+            {
+                Bound::Exact => return score,
+                Bound::Lower if score >= beta => return beta,
+                Bound::Upper if score <= alpha => return alpha,
+                _ => (),
+            };
+        }
+
+        let legal: Vec<ChessMove> = board.legal_moves();
+        let mut moves: Vec<ChessMove> = legal.clone();
+        Engine::__order_moves(board, &mut moves);
+
+        // The best move of an earlier search first
+        if let Some(hint) = entry.and_then(|entry| entry.get_best()).and_then(|i| legal.get(i))
+        {
+            let i: usize = moves.iter().position(|mv| mv == hint).unwrap();
+            moves[..=i].rotate_right(1);
+        }
+
+        let start: i32 = alpha;
+        let mut best: Option<ChessMove> = None;
+        for mv in moves
+        {
+            let mut child: ChessBoard = board.clone();
+            child.make_move(mv);
+
+            let score: i32 = -self.__negamax(&mut child, depth-1, ply+1, -beta, -alpha);
+            if self.stopped
+            {
+                return 0;
+            }
+
+            if score >= beta
+            {
+                self.table.store(hash, Entry::new(score_to_table(beta, ply), depth, Bound::Lower, __index_of(&legal, Some(mv))));
+                return beta;
+            }
+            if score > alpha
+            {
+                alpha = score;
+                best = Some(mv);
+            }
+        }
+
+        let bound: Bound = if alpha > start { Bound::Exact } else { Bound::Upper };
+        self.table.store(hash, Entry::new(score_to_table(alpha, ply), depth, bound, __index_of(&legal, best)));
+        return alpha;
+    }
+
+    // Only looks at captures, so the search doesn't stop in the middle of
+    // an exchange
+    fn __quiescence(&mut self, board: &mut ChessBoard, ply: i32, mut alpha: i32, beta: i32, depth: u8) -> i32
+    {
+        let stand: i32 = Engine::evaluate(board);
+        if stand >= beta || depth == 0
+        {
+            return stand;
+        }
+        alpha = i32::max(alpha, stand);
+
+        let mut moves: Vec<ChessMove> = board.legal_moves();
+        moves.retain(|mv| matches!(mv, ChessMove::Drag { from, to, .. } if board.is_capture(*from, *to)));
+        Engine::__order_moves(board, &mut moves);
+
+        for mv in moves
+        {
+            self.nodes += 1;
+            let mut child: ChessBoard = board.clone();
+            child.make_move(mv);
+
+            let score: i32 = match Engine::__game_over_score(&child, ply+1)
+            {
+                Some(score) => -score,
+                None => -self.__quiescence(&mut child, ply+1, -beta, -alpha, depth-1),
+            };
+
+            if score >= beta
+            {
+                return beta;
+            }
+            alpha = i32::max(alpha, score);
+        }
+
+        return alpha;
+    }
+}
+
+// Entries keep the index of the move in `legal_moves`
+fn __index_of(legal: &[ChessMove], mv: Option<ChessMove>) -> Option<usize>
+{
+    return mv.and_then(|mv| legal.iter().position(|other| *other == mv));
+}
@@ -0,0 +1,160 @@
+use super::*;
+
+#[test]
+fn test_engine_mate_in_one()
+{
+    let board: ChessBoard = ChessBoard::from_fen("6k1/5ppp/8/8/8/8/8/R5K1 w - - 0 1").unwrap();
+    let result: SearchResult = Engine::new().search(&board, SearchLimit::Depth(2));
+    assert!(result.get_move() == Some(ChessMove::drag(0o00, 0o70)));
+    assert!(result.get_score() == MATE - 1);
+}
+
+#[test]
+fn test_engine_takes_free_piece()
+{
+    let board: ChessBoard = ChessBoard::from_fen("4k3/8/8/3q4/8/8/3R4/4K3 w - - 0 1").unwrap();
+    let result: SearchResult = Engine::new().search(&board, SearchLimit::Depth(2));
+    assert!(result.get_move() == Some(ChessMove::drag(0o13, 0o43)));
+    assert!(result.get_score() > 0);
+
+    // Equal material from both sides
+    assert!(Engine::evaluate(&ChessBoard::init_position()) == 0);
+}
+
+#[test]
+fn test_engine_time_limit()
+{
+    let board: ChessBoard = ChessBoard::init_position();
+    let result: SearchResult = Engine::new().search(&board, SearchLimit::Time(Duration::from_millis(200)));
+    assert!(result.get_move().is_some());
+    assert!(result.get_depth() >= 1);
+}
+
+#[test]
+fn test_engine_threads()
+{
+    let board: ChessBoard = ChessBoard::from_fen("6k1/5ppp/8/8/8/8/8/R5K1 w - - 0 1").unwrap();
+    let mut engine: Engine = Engine::new();
+    engine.set_threads(4);
+    assert!(engine.get_threads() == 4);
+
+    let result: SearchResult = engine.search(&board, SearchLimit::Depth(3));
+    assert!(result.get_move() == Some(ChessMove::drag(0o00, 0o70)));
+    assert!(result.get_score() == MATE - 1);
+    assert!(result.get_nodes() > 0 && result.get_nps() > 0);
+
+    engine.set_threads(0);
+    assert!(engine.get_threads() == 1);
+    engine.set_threads(100_000);
+    assert!(engine.get_threads() == MAX_THREADS);
+}
+
+#[test]
+fn test_engine_deterministic()
+{
+    // One thread searches the same way every time, whatever it searched
+    // before
+    let board: ChessBoard = ChessBoard::from_fen("r1bqkbnr/pppp1ppp/2n5/4p3/4P3/5N2/PPPP1PPP/RNBQKB1R w KQkq - 2 3").unwrap();
+    let mut engine: Engine = Engine::new();
+    let first: SearchResult = engine.search(&board, SearchLimit::Depth(3));
+    engine.search(&ChessBoard::init_position(), SearchLimit::Depth(3));
+    let second: SearchResult = engine.search(&board, SearchLimit::Depth(3));
+
+    assert!(first.get_move() == second.get_move());
+    assert!(first.get_score() == second.get_score());
+    assert!(first.get_nodes() == second.get_nodes());
+}
+
+#[test]
+fn test_engine_table()
+{
+    let mut table: tt::TranspositionTable = tt::TranspositionTable::new(1);
+    assert!(table.entries() == 65536);
+    assert!(table.probe(42).is_none());
+
+    table.store(42, tt::Entry::new(-35, 3, tt::Bound::Lower, Some(7)));
+    let entry: tt::Entry = table.probe(42).unwrap();
+    assert!(entry.get_score() == -35 && entry.get_depth() == 3);
+    assert!(entry.get_bound() == tt::Bound::Lower && entry.get_best() == Some(7));
+    assert!(table.probe(42 + table.entries() as u64).is_none());
+
+    // A shallower result doesn't replace a deeper one
+    table.store(42, tt::Entry::new(10, 1, tt::Bound::Exact, None));
+    assert!(table.probe(42).unwrap().get_depth() == 3);
+
+    table.new_search();
+    assert!(table.probe(42).is_none());
+
+    // Mates are kept from the position
+    assert!(tt::score_to_table(MATE - 5, 2) == MATE - 3);
+    assert!(tt::score_from_table(MATE - 3, 4) == MATE - 7);
+    assert!(tt::score_to_table(-(MATE - 5), 2) == -(MATE - 3));
+    assert!(tt::score_to_table(250, 2) == 250);
+}
+
+#[test]
+fn test_engine_uci()
+{
+    let mut uci: uci::UciEngine<Vec<u8>> = uci::UciEngine::new(Vec::new());
+    let input: &str = "uci\nsetoption name Threads value 2\nisready\nposition startpos moves e2e4 e7e5\ngo depth 2\n";
+    uci.run(input.as_bytes());
+    let output: String = String::from_utf8(uci.into_output()).unwrap();
+    let lines: Vec<&str> = output.lines().collect();
+
+    assert!(lines.contains(&"option name Threads type spin default 1 min 1 max 256"));
+    assert!(lines.contains(&"uciok") && lines.contains(&"readyok"));
+    assert!(lines.iter().any(|line| line.starts_with("info depth 2 score cp ") && line.contains(" nps ")));
+    assert!(lines.last().unwrap().starts_with("bestmove "));
+    assert!(lines.last().unwrap().len() == "bestmove g1f3".len());
+
+    let board: ChessBoard = uci::parse_position(&["fen", "6k1/5ppp/8/8/8/8/8/R5K1", "w", "-", "-", "0", "1", "moves", "a1a2"], false).unwrap();
+    assert!(!board.white_turn());
+    assert!(uci::parse_position(&["startpos", "moves", "e2e5"], false).is_none());
+
+    assert!(uci::parse_limit(&["depth", "5"], true) == SearchLimit::Depth(5));
+    assert!(uci::parse_limit(&["movetime", "250"], true) == SearchLimit::Time(Duration::from_millis(250)));
+    assert!(uci::parse_limit(&["wtime", "60000", "btime", "3000"], false) == SearchLimit::Time(Duration::from_millis(100)));
+    assert!(uci::parse_limit(&["infinite"], true) == SearchLimit::Depth(u8::MAX));
+    assert!(uci::format_score(MATE - 1) == "mate 1" && uci::format_score(-(MATE - 2)) == "mate -1");
+    assert!(uci::format_score(-40) == "cp -40");
+}
+
+#[test]
+fn test_engine_uci_chess960()
+{
+    // Castling is the king taking its rook in Chess960
+    let fen: [&str; 7] = ["fen", "bqnbrkrn/pppppppp/8/8/8/8/PPPPPPPP/BQNBRKRN", "w", "KQkq", "-", "0", "1"];
+    let mut tokens: Vec<&str> = fen.to_vec();
+    tokens.extend(["moves", "f1g1"]);
+    let board: ChessBoard = uci::parse_position(&tokens, true).unwrap();
+    assert!(board.is_chess960());
+    assert!(board.get_piece(0o06) == ChessPiece::WKing && board.get_piece(0o05) == ChessPiece::WRook);
+    assert!(uci::parse_position(&["fen", "4k3/8/8/8/8/8/8/4K2R", "w", "K", "-", "0", "1", "moves", "e1g1"], true).is_none());
+
+    let castling: ChessMove = ChessMove::drag(0o04, 0o07);
+    let board: ChessBoard = uci::parse_position(&["fen", "4k3/8/8/8/8/8/8/4K2R", "w", "K", "-", "0", "1"], true).unwrap();
+    assert!(board.to_uci(castling) == "e1h1");
+    let board: ChessBoard = uci::parse_position(&["fen", "4k3/8/8/8/8/8/8/4K2R", "w", "K", "-", "0", "1"], false).unwrap();
+    assert!(board.to_uci(ChessMove::drag(0o04, 0o06)) == "e1g1");
+
+    let mut uci: uci::UciEngine<Vec<u8>> = uci::UciEngine::new(Vec::new());
+    uci.run("uci\nsetoption name UCI_Chess960 value true\nposition fen 4k3/8/8/8/8/8/8/4K2R w K - 0 1 moves e1h1\ngo depth 1\n".as_bytes());
+    let output: String = String::from_utf8(uci.into_output()).unwrap();
+    assert!(output.contains("option name UCI_Chess960 type check default false\n"));
+    assert!(output.lines().last().unwrap().starts_with("bestmove "));
+}
+
+#[test]
+fn test_engine_uci_stop()
+{
+    // An infinite search only ends with `stop`
+    let mut uci: uci::UciEngine<Vec<u8>> = uci::UciEngine::new(Vec::new());
+    assert!(uci.handle("go infinite"));
+    std::thread::sleep(Duration::from_millis(100));
+    assert!(uci.handle("isready"));
+    assert!(uci.handle("stop"));
+
+    let output: String = String::from_utf8(uci.into_output()).unwrap();
+    assert!(output.starts_with("readyok\n"));
+    assert!(output.lines().last().unwrap().starts_with("bestmove "));
+}
@@ -0,0 +1,196 @@
+// The transposition table: what the search found out about a position,
+// kept by its hash. One table is shared by all the threads of a search,
+// without locks. Every entry is two atomic words, the key xor the data
+// and the data, so an entry torn by two threads writing at once has the
+// wrong key and is never read.
+//
+// The data of an entry, from the low bits:
+//
+//      score       32 bits, mates counted from the position itself
+//      depth        8 bits
+//      bound        2 bits
+//      move        12 bits, the index in `legal_moves` plus one, 0 for none
+//      generation  10 bits
+//
+// Entries of older searches are ignored, so a search always starts from an
+// empty table as far as it can tell
+use super::*;
+use std::sync::atomic::{AtomicU64, Ordering};
+
+// The size of a table in megabytes
+pub const DEFAULT_HASH_MB: usize = 16;
+pub const MAX_HASH_MB: usize = 4096;
+
+const GENERATIONS: u16 = 1 << 10;
+const MAX_MOVE_INDEX: usize = (1 << 12) - 2;
+
+#[derive(Debug, PartialEq, Eq, Clone, Copy)]
+pub enum Bound
+{
+    // The score is exact
+    Exact,
+    // The score is at least this much
+    Lower,
+    // The score is at most this much
+    Upper,
+}
+
+#[derive(Debug, PartialEq, Eq, Clone, Copy)]
+pub struct Entry
+{
+    score: i32,
+    depth: u8,
+    bound: Bound,
+    // The index of the best move in `legal_moves`
+    best: Option<usize>,
+}
+
+impl Entry
+{
+    pub fn new(score: i32, depth: u8, bound: Bound, best: Option<usize>) -> Entry
+    {
+        return Entry { score, depth, bound, best };
+    }
+
+    pub fn get_score(&self) -> i32
+    {
+        return self.score;
+    }
+
+    pub fn get_depth(&self) -> u8
+    {
+        return self.depth;
+    }
+
+    pub fn get_bound(&self) -> Bound
+    {
+        return self.bound;
+    }
+
+    pub fn get_best(&self) -> Option<usize>
+    {
+        return self.best;
+    }
+}
+
+pub struct TranspositionTable
+{
+    // Pairs of the key xor the data and the data
+    words: Vec<AtomicU64>,
+    generation: u16,
+}
+
+impl TranspositionTable
+{
+    pub fn new(megabytes: usize) -> TranspositionTable
+    {
+        let entries: usize = usize::max(1, megabytes.clamp(1, MAX_HASH_MB) * 1024 * 1024 / 16);
+        let words: Vec<AtomicU64> = (0..entries * 2).map(|_| AtomicU64::new(0)).collect();
+
+        return TranspositionTable { words, generation: 1 };
+    }
+
+    pub fn entries(&self) -> usize
+    {
+        return self.words.len() / 2;
+    }
+
+    // Starts a search: the entries of the ones before it are forgotten
+    pub fn new_search(&mut self)
+    {
+        self.generation = (self.generation + 1) % GENERATIONS;
+
+        // An entry that old would look new again
+        if self.generation == 0
+        {
+            self.clear();
+            self.generation = 1;
+        }
+    }
+
+    pub fn clear(&mut self)
+    {
+        for word in self.words.iter_mut()
+        {
+            *word.get_mut() = 0;
+        }
+    }
+
+    pub fn probe(&self, hash: u64) -> Option<Entry>
+    {
+        let i: usize = self.__index(hash);
+        let check: u64 = self.words[i].load(Ordering::Relaxed);
+        let data: u64 = self.words[i + 1].load(Ordering::Relaxed);
+
+        if check ^ data != hash || (data >> 54) as u16 != self.generation
+        {
+            return None;
+        }
+
+        let bound: Bound = match (data >> 40) & 3
+        {
+            0 => Bound::Exact,
+            1 => Bound::Lower,
+            _ => Bound::Upper,
+        };
+        let best: usize = ((data >> 42) & 0xfff) as usize;
+
+        return Some(Entry
+        {
+            score: data as u32 as i32,
+            depth: (data >> 32) as u8,
+            bound,
+            best: best.checked_sub(1),
+        });
+    }
+
+    // Replaces the entry of the slot, unless it's one of this search for
+    // the same position that went deeper
+    pub fn store(&self, hash: u64, entry: Entry)
+    {
+        if self.probe(hash).is_some_and(|old| old.depth > entry.depth)
+        {
+            return;
+        }
+
+        let bound: u64 = match entry.bound
+        {
+            Bound::Exact => 0,
+            Bound::Lower => 1,
+            Bound::Upper => 2,
+        };
+        let best: u64 = entry.best.filter(|best| *best <= MAX_MOVE_INDEX).map_or(0, |best| best as u64 + 1);
+        let data: u64 = entry.score as u32 as u64 | (entry.depth as u64) << 32 | bound << 40 | best << 42 | (self.generation as u64) << 54;
+
+        let i: usize = self.__index(hash);
+        self.words[i].store(hash ^ data, Ordering::Relaxed);
+        self.words[i + 1].store(data, Ordering::Relaxed);
+    }
+
+    fn __index(&self, hash: u64) -> usize
+    {
+        return (hash % self.entries() as u64) as usize * 2;
+    }
+}
+
+// Mate scores are kept from the position they're stored for, not from the
+// root, so the same entry works at another ply
+pub fn score_to_table(score: i32, ply: i32) -> i32
+{
+    return match score
+    {
+        s if s >= MATE - MATE_PLIES => s + ply,
+        s if s <= -(MATE - MATE_PLIES) => s - ply,
+        s => s,
+    };
+}
+
+pub fn score_from_table(score: i32, ply: i32) -> i32
+{
+    return match score
+    {
+        s if s >= MATE - MATE_PLIES => s - ply,
+        s if s <= -(MATE - MATE_PLIES) => s + ply,
+        s => s,
+    };
+}
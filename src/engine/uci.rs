@@ -0,0 +1,259 @@
+// The engine side of UCI, for GUIs and match runners. The search runs on
+// its own thread, so `stop` and `isready` are answered while it thinks.
+//
+// Understood commands:
+//
+//      uci, isready, ucinewgame, quit, stop
+//      setoption name Threads value <n>
+//      setoption name Hash value <megabytes>
+//      setoption name UCI_Chess960 value true|false
+//      position startpos|fen <fen> [moves <uci>...]
+//      go [depth <n>] [movetime <ms>] [wtime <ms>] [btime <ms>] [winc <ms>] [binc <ms>] [infinite]
+//
+// Before `bestmove` the engine prints one `info` line with the depth, the
+// score, the nodes of all the threads and the nodes per second
+use super::*;
+use std::io::{BufRead, Write};
+use std::sync::Mutex;
+use std::thread::JoinHandle;
+
+pub struct UciEngine<W: Write + Send + 'static>
+{
+    // `None` while it searches on its thread
+    engine: Option<Engine>,
+    search: Option<JoinHandle<Engine>>,
+    stop: Arc<AtomicBool>,
+    board: ChessBoard,
+    // Castling is written as the king taking its rook, like `e1h1`
+    chess960: bool,
+    output: Arc<Mutex<W>>,
+}
+
+impl<W: Write + Send + 'static> UciEngine<W>
+{
+    pub fn new(output: W) -> UciEngine<W>
+    {
+        let engine: Engine = Engine::new();
+        return UciEngine
+        {
+            stop: engine.get_stop(),
+            engine: Some(engine),
+            search: None,
+            board: ChessBoard::init_position(),
+            chess960: false,
+            output: Arc::new(Mutex::new(output)),
+        };
+    }
+
+    // Handles the commands until `quit` or the end of the input, then
+    // waits for the search
+    pub fn run(&mut self, input: impl BufRead)
+    {
+        for line in input.lines().map_while(Result::ok)
+        {
+            if !self.handle(&line)
+            {
+                break;
+            }
+        }
+        self.wait();
+    }
+
+    // Handles one line of input. Returns false for `quit`, which stops the
+    // search. Unknown commands are ignored, as the protocol wants
+    pub fn handle(&mut self, line: &str) -> bool
+    {
+        let tokens: Vec<&str> = line.split_whitespace().collect();
+        match tokens.first().copied()
+        {
+            Some("uci") => {
+                self.__send(&format!("id name dynchess {}", env!("CARGO_PKG_VERSION")));
+                self.__send("id author the dynchess developers");
+                self.__send(&format!("option name Threads type spin default 1 min 1 max {}", MAX_THREADS));
+                self.__send(&format!("option name Hash type spin default {} min 1 max {}", tt::DEFAULT_HASH_MB, tt::MAX_HASH_MB));
+                self.__send("option name UCI_Chess960 type check default false");
+                self.__send("uciok");
+            },
+            Some("isready") => self.__send("readyok"),
+            Some("setoption") => self.__set_option(&tokens[1..]),
+            Some("position") => {
+                if let Some(board) = parse_position(&tokens[1..], self.chess960)
+                {
+                    self.board = board;
+                }
+            },
+            Some("go") => self.__go(&tokens[1..]),
+            Some("stop") => {
+                self.stop.store(true, Ordering::Relaxed);
+                self.wait();
+            },
+            Some("quit") => {
+                self.stop.store(true, Ordering::Relaxed);
+                return false;
+            },
+            _ => (),
+        };
+
+        return true;
+    }
+
+    // Waits for the running search to print its move
+    pub fn wait(&mut self)
+    {
+        if let Some(search) = self.search.take()
+        {
+            self.engine = Some(search.join().unwrap());
+        }
+    }
+
+    // Waits for the search and gives back the output
+    pub fn into_output(mut self) -> W
+    {
+        self.wait();
+        let output: Arc<Mutex<W>> = self.output.clone();
+        drop(self);
+
+        return match Arc::try_unwrap(output)
+        {
+            Ok(output) => output.into_inner().unwrap(),
+            Err(_) => unreachable!(),
+        };
+    }
+
+    fn __set_option(&mut self, tokens: &[&str])
+    {
+        let (name, value) = match tokens
+        {
+            ["name", name, "value", value] => (name.to_ascii_lowercase(), *value),
+            _ => return,
+        };
+
+        if name == "uci_chess960"
+        {
+            if let Ok(chess960) = value.parse::<bool>()
+            {
+                self.chess960 = chess960;
+                self.board.set_chess960(chess960);
+            }
+            return;
+        }
+
+        // The options of the engine can't change during a search
+        let engine: &mut Engine = match self.engine.as_mut()
+        {
+            Some(engine) => engine,
+            None => return,
+        };
+        match (name.as_str(), value.parse::<usize>())
+        {
+            ("threads", Ok(threads)) => engine.set_threads(threads),
+            ("hash", Ok(megabytes)) => engine.set_hash_size(megabytes),
+            _ => (),
+        };
+    }
+
+    fn __go(&mut self, tokens: &[&str])
+    {
+        self.wait();
+        let mut engine: Engine = self.engine.take().unwrap();
+        let limit: SearchLimit = parse_limit(tokens, self.board.white_turn());
+        let board: ChessBoard = self.board.clone();
+        let output: Arc<Mutex<W>> = self.output.clone();
+
+        self.stop.store(false, Ordering::Relaxed);
+        self.search = Some(thread::spawn(move ||
+        {
+            let result: SearchResult = engine.search(&board, limit);
+            let mut output = output.lock().unwrap();
+
+            let _ = writeln!(
+                output, "info depth {} score {} nodes {} nps {} time {}{}",
+                result.get_depth(), format_score(result.get_score()), result.get_nodes(), result.get_nps(),
+                result.get_time().as_millis(),
+                result.get_move().map_or(String::new(), |mv| format!(" pv {}", board.to_uci(mv))),
+            );
+            let _ = writeln!(output, "bestmove {}", result.get_move().map_or("0000".to_string(), |mv| board.to_uci(mv)));
+            let _ = output.flush();
+
+            return engine;
+        }));
+    }
+
+    fn __send(&self, line: &str)
+    {
+        let mut output = self.output.lock().unwrap();
+        let _ = writeln!(output, "{}", line);
+        let _ = output.flush();
+    }
+}
+
+impl<W: Write + Send + 'static> Drop for UciEngine<W>
+{
+    fn drop(&mut self)
+    {
+        self.stop.store(true, Ordering::Relaxed);
+        self.wait();
+    }
+}
+
+// The board of `position startpos|fen <fen> [moves ...]`, without the
+// `position`. With `chess960` castling moves are read and written as the
+// king taking its rook. `None` if the position or a move can't be read
+pub fn parse_position(tokens: &[&str], chess960: bool) -> Option<ChessBoard>
+{
+    let moves: usize = tokens.iter().position(|token| *token == "moves").unwrap_or(tokens.len());
+    let mut board: ChessBoard = match tokens[..moves]
+    {
+        ["startpos"] => ChessBoard::init_position(),
+        ["fen", ref fen @ ..] if !fen.is_empty() => ChessBoard::from_fen(&fen.join(" ")).ok()?,
+        _ => return None,
+    };
+    board.set_chess960(chess960);
+
+    for uci in tokens.iter().skip(moves + 1)
+    {
+        let mv: ChessMove = board.from_uci(uci)?;
+        board.make_move(mv);
+    }
+
+    return Some(board);
+}
+
+// The limit of `go`, without the `go`. Without a limit, or with
+// `infinite`, the engine searches until `stop`
+pub fn parse_limit(tokens: &[&str], white: bool) -> SearchLimit
+{
+    let value = |name: &str| -> Option<u64>
+    {
+        let i: usize = tokens.iter().position(|token| *token == name)?;
+        return tokens.get(i + 1)?.parse().ok();
+    };
+    let millis = |name: &str| value(name).map(Duration::from_millis);
+
+    if let Some(depth) = value("depth")
+    {
+        return SearchLimit::Depth(depth.clamp(1, u8::MAX as u64) as u8);
+    }
+    if let Some(time) = millis("movetime")
+    {
+        return SearchLimit::Time(time);
+    }
+
+    let (clock, increment) = if white { ("wtime", "winc") } else { ("btime", "binc") };
+    return match millis(clock)
+    {
+        Some(left) => SearchLimit::from_clock(left, millis(increment).unwrap_or(Duration::ZERO)),
+        None => SearchLimit::Depth(u8::MAX),
+    };
+}
+
+// `cp <centipawns>` or `mate <moves>`, negative when the engine is mated
+pub fn format_score(score: i32) -> String
+{
+    if score.abs() >= MATE - MATE_PLIES
+    {
+        let moves: i32 = (MATE - score.abs() + 1) / 2;
+        return format!("mate {}", if score < 0 { -moves } else { moves });
+    }
+    return format!("cp {}", score);
+}
@@ -0,0 +1,280 @@
+// Extended Position Description, the format of test suites like WAC, STS
+// or ECM: the first four fields of a FEN followed by operations, each an
+// opcode and its operands ending with `;`
+//
+//      r1b1kb1r/3q1ppp/pBp1pn2/8/Np3P2/5B2/PPP3PP/R2Q1RK1 w kq - bm Bxc6; id "WAC.014";
+//
+// The moves of `bm`, `am` and `pv` are in SAN and checked when the line is
+// read, `pv` one move after the other. `hmvc` and `fmvn` give the move
+// counters. Any other operation is kept as it's written, see
+// `Epd::get_operation`
+use super::*;
+use fen::FenError;
+
+#[cfg(feature = "std")]
+mod suite;
+#[cfg(test)]
+mod tests;
+
+#[cfg(feature = "std")]
+pub use suite::{run_position, PositionReport, SuiteReport};
+
+#[derive(Debug, PartialEq, Eq, Clone)]
+pub enum EpdError
+{
+    // Less than the four fields of the position
+    MissingField,
+    InvalidFen(FenError),
+    // A string that isn't closed
+    Unterminated,
+    // A move that can't be read or isn't legal
+    IllegalMove(String),
+    // A number that can't be read, like the one of `dm`
+    InvalidOperand(String),
+}
+
+#[derive(Debug, Clone)]
+pub struct Epd
+{
+    board: ChessBoard,
+    // The opcodes and their operands in order, strings without the quotes
+    operations: Vec<(String, Vec<String>)>,
+    best_moves: Vec<ChessMove>,
+    avoid_moves: Vec<ChessMove>,
+    pv: Vec<ChessMove>,
+}
+
+impl Epd
+{
+    pub fn parse(line: &str) -> Result<Epd, EpdError>
+    {
+        let line: &str = line.trim();
+        let mut fields: Vec<&str> = Vec::new();
+        let mut rest: &str = line;
+        for _ in 0..4
+        {
+            rest = rest.trim_start();
+            let end: usize = rest.find(char::is_whitespace).unwrap_or(rest.len());
+            if end == 0
+            {
+                return Err(EpdError::MissingField);
+            }
+            fields.push(&rest[..end]);
+            rest = &rest[end..];
+        }
+
+        let operations: Vec<(String, Vec<String>)> = __read_operations(rest)?;
+        let counter = |opcode: &str, default: &str| -> Result<String, EpdError>
+        {
+            return match operations.iter().find(|(o, _)| o == opcode).and_then(|(_, operands)| operands.first())
+            {
+                Some(value) if value.parse::<u16>().is_ok() => Ok(value.clone()),
+                Some(value) => Err(EpdError::InvalidOperand(value.clone())),
+                None => Ok(default.to_string()),
+            };
+        };
+
+        let fen: String = format!("{} {} {}", fields.join(" "), counter("hmvc", "0")?, counter("fmvn", "1")?);
+        let board: ChessBoard = ChessBoard::from_fen(&fen).map_err(EpdError::InvalidFen)?;
+
+        let mut epd: Epd = Epd { board, operations, best_moves: Vec::new(), avoid_moves: Vec::new(), pv: Vec::new() };
+        epd.best_moves = epd.__read_moves("bm", false)?;
+        epd.avoid_moves = epd.__read_moves("am", false)?;
+        epd.pv = epd.__read_moves("pv", true)?;
+
+        for opcode in ["dm", "ce", "acd", "acn"]
+        {
+            if let Some(value) = epd.get_operation(opcode).and_then(|operands| operands.first())
+            {
+                value.parse::<i64>().map_err(|_| EpdError::InvalidOperand(value.clone()))?;
+            }
+        }
+
+        return Ok(epd);
+    }
+
+    pub fn get_board(&self) -> &ChessBoard
+    {
+        return &self.board;
+    }
+
+    pub fn get_operations(&self) -> &[(String, Vec<String>)]
+    {
+        return &self.operations;
+    }
+
+    // The operands of the first operation with the opcode
+    pub fn get_operation(&self, opcode: &str) -> Option<&[String]>
+    {
+        return self.operations.iter().find(|(o, _)| o == opcode).map(|(_, operands)| operands.as_slice());
+    }
+
+    pub fn get_id(&self) -> Option<&str>
+    {
+        return self.get_operation("id").and_then(|operands| operands.first()).map(|id| id.as_str());
+    }
+
+    // One of the comments `c0` to `c9`
+    pub fn get_comment(&self, index: u8) -> Option<&str>
+    {
+        return self.get_operation(&format!("c{}", index)).and_then(|operands| operands.first()).map(|c| c.as_str());
+    }
+
+    // The moves of `bm`, any of them is right
+    pub fn get_best_moves(&self) -> &[ChessMove]
+    {
+        return &self.best_moves;
+    }
+
+    // The moves of `am`, none of them should be played
+    pub fn get_avoid_moves(&self) -> &[ChessMove]
+    {
+        return &self.avoid_moves;
+    }
+
+    // The predicted variation of `pv`
+    pub fn get_pv(&self) -> &[ChessMove]
+    {
+        return &self.pv;
+    }
+
+    // The moves of `dm`, the side to move mates in that many moves
+    pub fn get_direct_mate(&self) -> Option<u16>
+    {
+        return self.__number("dm").map(|n| n as u16);
+    }
+
+    // The score of `ce` in centipawns, from the side of the color to move
+    pub fn get_centipawns(&self) -> Option<i32>
+    {
+        return self.__number("ce").map(|n| n as i32);
+    }
+
+    // Writes the position and the operations back in EPD
+    pub fn to_epd(&self) -> String
+    {
+        let fen: String = self.board.to_fen();
+        let mut epd: String = fen.split_whitespace().take(4).collect::<Vec<&str>>().join(" ");
+
+        for (opcode, operands) in self.operations.iter()
+        {
+            epd.push(' ');
+            epd.push_str(opcode);
+            for operand in operands
+            {
+                match operand.contains(char::is_whitespace) || operand.contains(';') || operand.is_empty()
+                {
+                    true => epd.push_str(&format!(" \"{}\"", operand)),
+                    false => epd.push_str(&format!(" {}", operand)),
+                };
+            }
+            epd.push(';');
+        }
+
+        return epd;
+    }
+
+    fn __number(&self, opcode: &str) -> Option<i64>
+    {
+        // Checked when the line was read
+        return self.get_operation(opcode).and_then(|operands| operands.first()).map(|value| value.parse().unwrap());
+    }
+
+    // The moves of the operation, all from the position or, for a
+    // variation, each after the one before
+    fn __read_moves(&self, opcode: &str, line: bool) -> Result<Vec<ChessMove>, EpdError>
+    {
+        let mut board: ChessBoard = self.board.clone();
+        let mut moves: Vec<ChessMove> = Vec::new();
+
+        for text in self.get_operation(opcode).unwrap_or(&[])
+        {
+            let mv: ChessMove = match board.from_san(text).or_else(|| board.from_uci(text))
+            {
+                Some(mv) => mv,
+                None => return Err(EpdError::IllegalMove(text.clone())),
+            };
+            if line
+            {
+                board.make_move(mv);
+            }
+            moves.push(mv);
+        }
+
+        return Ok(moves);
+    }
+}
+
+// Reads the positions of a suite, one per line. Empty lines and lines
+// starting with `#` are skipped. Fails on the first line that can't be
+// read, with its number counting from 1
+pub fn read_epd(text: &str) -> Result<Vec<Epd>, (usize, EpdError)>
+{
+    let mut positions: Vec<Epd> = Vec::new();
+    for (index, line) in text.lines().enumerate()
+    {
+        if line.trim().is_empty() || line.trim_start().starts_with('#')
+        {
+            continue;
+        }
+
+        positions.push(Epd::parse(line).map_err(|error| (index + 1, error))?);
+    }
+
+    return Ok(positions);
+}
+
+// Splits `bm Qd1+ Qc2; id "WAC 1";` into its operations. A string in
+// quotes is one operand, and may hold `;`
+fn __read_operations(text: &str) -> Result<Vec<(String, Vec<String>)>, EpdError>
+{
+    let mut operations: Vec<(String, Vec<String>)> = Vec::new();
+    let mut words: Vec<String> = Vec::new();
+    let mut word: Option<String> = None;
+    let mut chars = text.chars();
+
+    while let Some(c) = chars.next()
+    {
+        match c
+        {
+            '"' => {
+                let mut string: String = String::new();
+                loop
+                {
+                    match chars.next()
+                    {
+                        Some('"') => break,
+                        Some(c) => string.push(c),
+                        None => return Err(EpdError::Unterminated),
+                    };
+                }
+                words.push(string);
+            },
+            ';' | ' ' | '\t' => {
+                if let Some(word) = word.take()
+                {
+                    words.push(word);
+                }
+                if c == ';' && !words.is_empty()
+                {
+                    let opcode: String = words.remove(0);
+                    operations.push((opcode, core::mem::take(&mut words)));
+                }
+            },
+            c => word.get_or_insert_with(String::new).push(c),
+        };
+    }
+
+    // The last `;` is sometimes left out
+    if let Some(word) = word.take()
+    {
+        words.push(word);
+    }
+    if !words.is_empty()
+    {
+        let opcode: String = words.remove(0);
+        operations.push((opcode, words));
+    }
+
+    return Ok(operations);
+}
@@ -0,0 +1,173 @@
+// Running a test suite with the built in engine. A position is passed if
+// the engine plays one of the `bm` moves and none of the `am` moves, or for
+// `dm` alone if it finds a mate that fast. Positions without any of them
+// aren't scored
+use super::*;
+use engine::{Engine, SearchLimit, SearchResult, MATE};
+use std::time::{Duration, Instant};
+
+#[derive(Debug, Clone)]
+pub struct PositionReport
+{
+    // The `id` of the position, or its number in the suite
+    id: String,
+    // What was asked for, like `bm Qxf7+`
+    expected: String,
+    // The move of the engine in SAN
+    found: Option<String>,
+    score: i32,
+    depth: u8,
+    nodes: u64,
+    time: Duration,
+    passed: Option<bool>,
+}
+
+impl PositionReport
+{
+    pub fn get_id(&self) -> &str
+    {
+        return &self.id;
+    }
+
+    pub fn get_found(&self) -> Option<&str>
+    {
+        return self.found.as_deref();
+    }
+
+    pub fn get_score(&self) -> i32
+    {
+        return self.score;
+    }
+
+    pub fn get_depth(&self) -> u8
+    {
+        return self.depth;
+    }
+
+    pub fn get_nodes(&self) -> u64
+    {
+        return self.nodes;
+    }
+
+    pub fn get_time(&self) -> Duration
+    {
+        return self.time;
+    }
+
+    // `None` if the position has nothing to check the move against
+    pub fn is_passed(&self) -> Option<bool>
+    {
+        return self.passed;
+    }
+
+    // One line for the report, like
+    //      WAC.001    ok    Qg6      bm Qg6         depth 5  nodes 12345  0.20s
+    pub fn to_text(&self) -> String
+    {
+        let status: &str = match self.passed
+        {
+            Some(true) => "ok",
+            Some(false) => "FAIL",
+            None => "-",
+        };
+
+        return format!(
+            "{:<12} {:<5} {:<8} {:<20} depth {:<3} nodes {:<10} {:.2}s",
+            self.id, status, self.found.as_deref().unwrap_or("none"), self.expected, self.depth, self.nodes, self.time.as_secs_f64(),
+        );
+    }
+}
+
+// Searches the position and checks the move of the engine. `index` names
+// the position if it has no `id`
+pub fn run_position(engine: &mut Engine, epd: &Epd, index: usize, limit: SearchLimit) -> PositionReport
+{
+    let start: Instant = Instant::now();
+    let result: SearchResult = engine.search(epd.get_board(), limit);
+    let time: Duration = start.elapsed();
+
+    let mut board: ChessBoard = epd.get_board().clone();
+    let mut expected: Vec<String> = Vec::new();
+    for (opcode, moves) in [("bm", epd.get_best_moves()), ("am", epd.get_avoid_moves())]
+    {
+        if !moves.is_empty()
+        {
+            let moves: Vec<String> = moves.iter().map(|mv| board.to_san(*mv)).collect();
+            expected.push(format!("{} {}", opcode, moves.join(" ")));
+        }
+    }
+    if let Some(mate) = epd.get_direct_mate()
+    {
+        expected.push(format!("dm {}", mate));
+    }
+
+    let mv: Option<ChessMove> = result.get_move();
+    let passed: Option<bool> = match (epd.get_best_moves(), epd.get_avoid_moves(), epd.get_direct_mate())
+    {
+        ([], [], None) => None,
+        ([], [], Some(mate)) => Some(result.get_score() >= MATE - (2*mate as i32 - 1)),
+        (best, avoid, _) => Some(mv.is_some_and(|mv| (best.is_empty() || best.contains(&mv)) && !avoid.contains(&mv))),
+    };
+
+    return PositionReport
+    {
+        id: epd.get_id().map_or_else(|| format!("#{}", index + 1), |id| id.to_string()),
+        expected: expected.join("; "),
+        found: mv.map(|mv| board.to_san(mv)),
+        score: result.get_score(),
+        depth: result.get_depth(),
+        nodes: result.get_nodes(),
+        time,
+        passed,
+    };
+}
+
+// The reports of a whole suite
+#[derive(Debug, Clone, Default)]
+pub struct SuiteReport
+{
+    positions: Vec<PositionReport>,
+}
+
+impl SuiteReport
+{
+    pub fn new() -> SuiteReport
+    {
+        return SuiteReport { positions: Vec::new() };
+    }
+
+    pub fn add(&mut self, report: PositionReport)
+    {
+        self.positions.push(report);
+    }
+
+    pub fn get_positions(&self) -> &[PositionReport]
+    {
+        return &self.positions;
+    }
+
+    pub fn passed(&self) -> usize
+    {
+        return self.positions.iter().filter(|p| p.passed == Some(true)).count();
+    }
+
+    // The positions that could be checked
+    pub fn scored(&self) -> usize
+    {
+        return self.positions.iter().filter(|p| p.passed.is_some()).count();
+    }
+
+    // Like `solved 250/300 (83.3%), 1234567 nodes in 12.34s`
+    pub fn summary(&self) -> String
+    {
+        let nodes: u64 = self.positions.iter().map(|p| p.nodes).sum();
+        let time: Duration = self.positions.iter().map(|p| p.time).sum();
+        let percent: f64 = match self.scored()
+        {
+            0 => 0.0,
+            scored => 100.0 * self.passed() as f64 / scored as f64,
+        };
+
+        return format!("solved {}/{} ({:.1}%), {} nodes in {:.2}s", self.passed(), self.scored(), percent, nodes, time.as_secs_f64());
+    }
+}
@@ -0,0 +1,56 @@
+use super::*;
+
+const SUITE: &str = "\
+# Two positions for the built in engine
+6k1/5ppp/8/8/8/8/8/R5K1 w - - bm Ra8#; id \"mate\"; c0 \"back; rank\"; dm 1;
+4k3/8/8/3q4/8/8/3R4/4K3 w - - am Kd1 Ke2; id \"free queen\"; pv Rxd5 Ke7; hmvc 3; fmvn 40
+4k3/8/8/8/8/8/8/4K3 b - - ce 0;
+";
+
+#[test]
+fn test_epd_parse()
+{
+    let positions: Vec<Epd> = read_epd(SUITE).unwrap();
+    assert!(positions.len() == 3);
+
+    let mate: &Epd = &positions[0];
+    assert!(mate.get_id() == Some("mate") && mate.get_comment(0) == Some("back; rank"));
+    assert!(mate.get_best_moves() == [ChessMove::drag(0o00, 0o70)]);
+    assert!(mate.get_direct_mate() == Some(1) && mate.get_centipawns().is_none());
+
+    let free: &Epd = &positions[1];
+    assert!(free.get_avoid_moves() == [ChessMove::drag(0o04, 0o03), ChessMove::drag(0o04, 0o14)]);
+    assert!(free.get_pv() == [ChessMove::drag(0o13, 0o43), ChessMove::drag(0o74, 0o64)]);
+    assert!(free.get_board().halfmove_clock() == 3 && free.get_board().fullmove() == 40);
+    assert!(free.get_operation("hmvc") == Some(&["3".to_string()][..]));
+    assert!(positions[2].get_centipawns() == Some(0) && positions[2].get_id().is_none());
+
+    assert!(mate.to_epd() == "6k1/5ppp/8/8/8/8/8/R5K1 w - - bm Ra8#; id mate; c0 \"back; rank\"; dm 1;");
+    assert!(Epd::parse(&mate.to_epd()).unwrap().get_operations() == mate.get_operations());
+
+    assert!(Epd::parse("4k3/8/8/8/8/8/8/4K3 w -").unwrap_err() == EpdError::MissingField);
+    assert!(Epd::parse("4k3/8/8/8/8/8/8/4K3 w - - bm Ke3;").unwrap_err() == EpdError::IllegalMove("Ke3".to_string()));
+    assert!(Epd::parse("4k3/8/8/8/8/8/8/4K3 w - - id \"open;").unwrap_err() == EpdError::Unterminated);
+    assert!(Epd::parse("4k3/8/8/8/8/8/8/4K3 w - - dm one;").unwrap_err() == EpdError::InvalidOperand("one".to_string()));
+    assert!(read_epd("\n4k3/8/8/8/8/8/8/4K3 w - - bm Ke3;").err().map(|(line, _)| line) == Some(2));
+}
+
+#[test]
+#[cfg(feature = "std")]
+fn test_epd_suite()
+{
+    let positions: Vec<Epd> = read_epd(SUITE).unwrap();
+    let mut engine: engine::Engine = engine::Engine::new();
+    let mut report: SuiteReport = SuiteReport::new();
+    for (index, epd) in positions.iter().enumerate()
+    {
+        report.add(run_position(&mut engine, epd, index, engine::SearchLimit::Depth(2)));
+    }
+
+    let reports: &[PositionReport] = report.get_positions();
+    assert!(reports[0].is_passed() == Some(true) && reports[0].get_found() == Some("Ra8#"));
+    assert!(reports[1].is_passed() == Some(true) && reports[1].get_found() == Some("Rxd5"));
+    assert!(reports[2].is_passed().is_none() && reports[2].get_id() == "#3");
+    assert!(reports[1].to_text().starts_with("free queen   ok    Rxd5     am Kd1 Ke2"));
+    assert!(report.summary().starts_with("solved 2/2 (100.0%)"));
+}
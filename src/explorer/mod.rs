@@ -0,0 +1,495 @@
+// An opening explorer: the moves played from every position of a PGN
+// collection, with how they scored. Positions are keyed by their hash, so
+// the same position reached by different move orders is one entry.
+//
+// PGN files are read game by game, see `Explorer::index_pgn`, only the
+// index is kept in memory while it's built. The index is saved as text,
+// one line per move of a position:
+//
+//      move    <hash>  <move in UCI>  <games>  <white wins>  <draws>  <black wins>  <sum of ratings>  <rated games>
+//
+// with tabs between the fields, the hash in 16 hex digits and the lines
+// sorted by it. An index too big to load is queried from its file with
+// `ExplorerFile`, which looks a position up with a binary search over the
+// bytes of the file
+use super::*;
+use game::{Game, PgnReader};
+use std::io::{self, BufRead, BufReader, BufWriter, Seek, SeekFrom, Write};
+use std::path::Path;
+
+#[cfg(test)]
+mod tests;
+
+#[derive(Debug, PartialEq, Eq, Clone)]
+pub enum ExplorerError
+{
+    // The line of the index, from 1, that can't be read
+    InvalidLine(usize),
+    // The line at the byte offset, for an index queried from its file
+    InvalidOffset(u64),
+    Io(io::ErrorKind),
+}
+
+// How a move did from a position
+#[derive(Debug, PartialEq, Eq, Clone, Copy, Default)]
+pub struct MoveStats
+{
+    games: u32,
+    white_wins: u32,
+    draws: u32,
+    black_wins: u32,
+    // The ratings of the players who played the move, when the game had
+    // them
+    rating_sum: u64,
+    rated: u32,
+}
+
+impl MoveStats
+{
+    pub fn get_games(&self) -> u32
+    {
+        return self.games;
+    }
+
+    pub fn get_white_wins(&self) -> u32
+    {
+        return self.white_wins;
+    }
+
+    pub fn get_draws(&self) -> u32
+    {
+        return self.draws;
+    }
+
+    pub fn get_black_wins(&self) -> u32
+    {
+        return self.black_wins;
+    }
+
+    // The average rating of the players who played the move, `None` if
+    // none of the games had ratings
+    pub fn get_average_rating(&self) -> Option<u32>
+    {
+        return (self.rated > 0).then(|| (self.rating_sum / self.rated as u64) as u32);
+    }
+
+    // The points of white per game, from 0 to 1
+    pub fn white_score(&self) -> f64
+    {
+        if self.games == 0
+        {
+            return 0.5;
+        }
+        return (self.white_wins as f64 + self.draws as f64 / 2.0) / self.games as f64;
+    }
+
+    fn __add(&mut self, other: &MoveStats)
+    {
+        self.games += other.games;
+        self.white_wins += other.white_wins;
+        self.draws += other.draws;
+        self.black_wins += other.black_wins;
+        self.rating_sum += other.rating_sum;
+        self.rated += other.rated;
+    }
+}
+
+// What came of `Explorer::index_pgn`
+#[derive(Debug, PartialEq, Eq, Clone, Copy, Default)]
+pub struct IndexSummary
+{
+    indexed: usize,
+    // Games that couldn't be read or have no result
+    skipped: usize,
+}
+
+impl IndexSummary
+{
+    pub fn get_indexed(&self) -> usize
+    {
+        return self.indexed;
+    }
+
+    pub fn get_skipped(&self) -> usize
+    {
+        return self.skipped;
+    }
+}
+
+#[derive(Debug, Clone)]
+pub struct Explorer
+{
+    // The moves of every position by the hash of the position, the moves
+    // in UCI
+    positions: BTreeMap<u64, BTreeMap<String, MoveStats>>,
+    // How many half moves of a game are indexed
+    max_plies: usize,
+}
+
+impl Explorer
+{
+    // The first 40 half moves of every game are indexed
+    pub fn new() -> Explorer
+    {
+        return Explorer { positions: BTreeMap::new(), max_plies: 40 };
+    }
+
+    pub fn set_max_plies(&mut self, plies: usize)
+    {
+        self.max_plies = plies;
+    }
+
+    pub fn get_max_plies(&self) -> usize
+    {
+        return self.max_plies;
+    }
+
+    pub fn position_count(&self) -> usize
+    {
+        return self.positions.len();
+    }
+
+    // Adds the moves of a finished game. Returns false for a game without
+    // a result, which isn't added
+    pub fn add_game(&mut self, game: &Game) -> bool
+    {
+        let (white_wins, draws, black_wins) = match game.result()
+        {
+            "1-0" => (1, 0, 0),
+            "0-1" => (0, 0, 1),
+            "1/2-1/2" => (0, 1, 0),
+            _ => return false,
+        };
+        let ratings: [Option<u64>; 2] = ["WhiteElo", "BlackElo"].map(|tag| game.get_tag(tag).and_then(|elo| elo.parse().ok()));
+
+        let mut board: ChessBoard = game.get_start().clone();
+        for mv in game.get_moves().iter().take(self.max_plies)
+        {
+            let rating: Option<u64> = ratings[if board.white_turn() { 0 } else { 1 }];
+            let played: MoveStats = MoveStats
+            {
+                games: 1,
+                white_wins,
+                draws,
+                black_wins,
+                rating_sum: rating.unwrap_or(0),
+                rated: rating.is_some() as u32,
+            };
+
+            self.positions.entry(board.hash()).or_default().entry(board.to_uci(*mv)).or_default().__add(&played);
+            board.make_move(*mv);
+        }
+
+        return true;
+    }
+
+    // Indexes the games of a PGN file one at a time, so the file is never
+    // read whole. Games that can't be read are skipped
+    pub fn index_pgn(&mut self, reader: impl BufRead) -> Result<IndexSummary, ExplorerError>
+    {
+        let mut summary: IndexSummary = IndexSummary::default();
+        for pgn in PgnReader::new(reader)
+        {
+            let pgn: String = pgn.map_err(|e| ExplorerError::Io(e.kind()))?;
+            match Game::from_pgn(&pgn)
+            {
+                Ok(game) if self.add_game(&game) => summary.indexed += 1,
+                _ => summary.skipped += 1,
+            };
+        }
+
+        return Ok(summary);
+    }
+
+    pub fn index_pgn_file(&mut self, path: impl AsRef<Path>) -> Result<IndexSummary, ExplorerError>
+    {
+        let file: std::fs::File = std::fs::File::open(path).map_err(|e| ExplorerError::Io(e.kind()))?;
+        return self.index_pgn(BufReader::new(file));
+    }
+
+    // The moves played from the position and how they did, the most played
+    // first
+    pub fn query(&self, board: &ChessBoard) -> Vec<(ChessMove, MoveStats)>
+    {
+        let moves: &BTreeMap<String, MoveStats> = match self.positions.get(&board.hash())
+        {
+            Some(moves) => moves,
+            None => return Vec::new(),
+        };
+
+        return __legal_moves(board, moves.iter().map(|(uci, stats)| (uci.as_str(), *stats)));
+    }
+
+    // All the games that reached the position, summed over its moves
+    pub fn position_stats(&self, board: &ChessBoard) -> MoveStats
+    {
+        return __total(&self.query(board));
+    }
+
+    // Writes the index in the format of the module
+    pub fn write_to(&self, writer: impl Write) -> Result<(), ExplorerError>
+    {
+        let mut writer: BufWriter<_> = BufWriter::new(writer);
+        let io_error = |e: io::Error| ExplorerError::Io(e.kind());
+
+        writeln!(writer, "# dynchess opening explorer").map_err(io_error)?;
+        writeln!(writer, "plies\t{}", self.max_plies).map_err(io_error)?;
+        for (hash, moves) in self.positions.iter()
+        {
+            for (uci, s) in moves.iter()
+            {
+                writeln!(
+                    writer, "move\t{:016x}\t{}\t{}\t{}\t{}\t{}\t{}\t{}",
+                    hash, uci, s.games, s.white_wins, s.draws, s.black_wins, s.rating_sum, s.rated,
+                ).map_err(io_error)?;
+            }
+        }
+
+        return writer.flush().map_err(io_error);
+    }
+
+    // Reads an index written by `Explorer::write_to`, line by line. Empty
+    // lines and lines starting with `#` are skipped
+    pub fn read_from(reader: impl BufRead) -> Result<Explorer, ExplorerError>
+    {
+        let mut explorer: Explorer = Explorer::new();
+        for (i, line) in reader.lines().enumerate()
+        {
+            let line: String = line.map_err(|e| ExplorerError::Io(e.kind()))?;
+            if line.trim().is_empty() || line.starts_with('#')
+            {
+                continue;
+            }
+
+            if explorer.__read_line(&line).is_none()
+            {
+                return Err(ExplorerError::InvalidLine(i + 1));
+            }
+        }
+
+        return Ok(explorer);
+    }
+
+    pub fn save(&self, path: impl AsRef<Path>) -> Result<(), ExplorerError>
+    {
+        let file: std::fs::File = std::fs::File::create(path).map_err(|e| ExplorerError::Io(e.kind()))?;
+        return self.write_to(file);
+    }
+
+    pub fn load(path: impl AsRef<Path>) -> Result<Explorer, ExplorerError>
+    {
+        let file: std::fs::File = std::fs::File::open(path).map_err(|e| ExplorerError::Io(e.kind()))?;
+        return Explorer::read_from(BufReader::new(file));
+    }
+
+    fn __read_line(&mut self, line: &str) -> Option<()>
+    {
+        if let Some(plies) = line.strip_prefix("plies\t")
+        {
+            self.max_plies = plies.parse().ok()?;
+            return Some(());
+        }
+
+        let (hash, uci, stats) = __read_move(line)?;
+        self.positions.entry(hash).or_default().entry(uci.to_string()).or_default().__add(&stats);
+        return Some(());
+    }
+}
+
+impl Default for Explorer
+{
+    fn default() -> Self
+    {
+        return Explorer::new();
+    }
+}
+
+// An index saved by `Explorer::save`, queried without loading it. Only the
+// lines of the position asked for and the ones the binary search passes
+// are read, so the index can be much bigger than the memory
+#[derive(Debug)]
+pub struct ExplorerFile<R: BufRead + Seek>
+{
+    reader: R,
+    len: u64,
+    max_plies: usize,
+}
+
+impl ExplorerFile<BufReader<std::fs::File>>
+{
+    pub fn open(path: impl AsRef<Path>) -> Result<ExplorerFile<BufReader<std::fs::File>>, ExplorerError>
+    {
+        let file: std::fs::File = std::fs::File::open(path).map_err(|e| ExplorerError::Io(e.kind()))?;
+        return ExplorerFile::new(BufReader::new(file));
+    }
+}
+
+impl<R: BufRead + Seek> ExplorerFile<R>
+{
+    // The index has to be written by `Explorer::write_to`, the lines of
+    // the moves sorted by their hash
+    pub fn new(mut reader: R) -> Result<ExplorerFile<R>, ExplorerError>
+    {
+        let io_error = |e: io::Error| ExplorerError::Io(e.kind());
+        let len: u64 = reader.seek(SeekFrom::End(0)).map_err(io_error)?;
+        reader.seek(SeekFrom::Start(0)).map_err(io_error)?;
+
+        // The plies are written before the moves
+        let mut max_plies: usize = Explorer::new().max_plies;
+        let mut line: String = String::new();
+        for i in 1..
+        {
+            line.clear();
+            if reader.read_line(&mut line).map_err(io_error)? == 0 || line.starts_with("move\t")
+            {
+                break;
+            }
+            if let Some(plies) = line.trim_end().strip_prefix("plies\t")
+            {
+                max_plies = plies.parse().map_err(|_| ExplorerError::InvalidLine(i))?;
+            }
+        }
+
+        return Ok(ExplorerFile { reader, len, max_plies });
+    }
+
+    pub fn get_max_plies(&self) -> usize
+    {
+        return self.max_plies;
+    }
+
+    // Same as `Explorer::query`
+    pub fn query(&mut self, board: &ChessBoard) -> Result<Vec<(ChessMove, MoveStats)>, ExplorerError>
+    {
+        let hash: u64 = board.hash();
+        let mut offset: u64 = self.__first_line(hash)?;
+        let mut moves: Vec<(String, MoveStats)> = Vec::new();
+
+        while let Some(line) = self.__line_at(offset)?
+        {
+            match __read_move(line.trim_end())
+            {
+                Some((other, uci, stats)) if other == hash => moves.push((uci.to_string(), stats)),
+                Some(_) => break,
+                None => return Err(ExplorerError::InvalidOffset(offset)),
+            };
+            offset += line.len() as u64;
+        }
+
+        return Ok(__legal_moves(board, moves.iter().map(|(uci, stats)| (uci.as_str(), *stats))));
+    }
+
+    // Same as `Explorer::position_stats`
+    pub fn position_stats(&mut self, board: &ChessBoard) -> Result<MoveStats, ExplorerError>
+    {
+        return Ok(__total(&self.query(board)?));
+    }
+
+    // The offset of the first line with a hash of at least `hash`. Lines
+    // starting before `low` have a smaller hash, the ones starting at
+    // `high` or after don't. Lines that aren't moves come first, so they
+    // count as smaller
+    fn __first_line(&mut self, hash: u64) -> Result<u64, ExplorerError>
+    {
+        let (mut low, mut high) = (0, self.len);
+        while low < high
+        {
+            let middle: u64 = low + (high - low) / 2;
+            let start: u64 = self.__line_start(low, middle)?;
+            if start >= high
+            {
+                high = middle;
+                continue;
+            }
+
+            let line: String = self.__line_at(start)?.unwrap_or_default();
+            match line.starts_with("move\t").then(|| __read_move(line.trim_end()))
+            {
+                Some(None) => return Err(ExplorerError::InvalidOffset(start)),
+                Some(Some((other, _, _))) if other >= hash => high = start,
+                _ => low = start + line.len() as u64,
+            };
+        }
+
+        return Ok(low);
+    }
+
+    // The start of the first line at `offset` or after, `low` being the
+    // start of a line
+    fn __line_start(&mut self, low: u64, offset: u64) -> Result<u64, ExplorerError>
+    {
+        if offset == low
+        {
+            return Ok(low);
+        }
+
+        // The line the byte before belongs to ends at the start of the next
+        let mut skipped: Vec<u8> = Vec::new();
+        self.reader.seek(SeekFrom::Start(offset - 1)).map_err(|e| ExplorerError::Io(e.kind()))?;
+        self.reader.read_until(b'\n', &mut skipped).map_err(|e| ExplorerError::Io(e.kind()))?;
+
+        return Ok(offset - 1 + skipped.len() as u64);
+    }
+
+    // The line starting at the offset with its end of line, `None` at the
+    // end of the file
+    fn __line_at(&mut self, offset: u64) -> Result<Option<String>, ExplorerError>
+    {
+        let mut line: String = String::new();
+        self.reader.seek(SeekFrom::Start(offset)).map_err(|e| ExplorerError::Io(e.kind()))?;
+        self.reader.read_line(&mut line).map_err(|e| match e.kind()
+        {
+            io::ErrorKind::InvalidData => ExplorerError::InvalidOffset(offset),
+            kind => ExplorerError::Io(kind),
+        })?;
+
+        return Ok((!line.is_empty()).then_some(line));
+    }
+}
+
+// Reads a `move` line of the index
+fn __read_move(line: &str) -> Option<(u64, &str, MoveStats)>
+{
+    let fields: Vec<&str> = line.split('\t').collect();
+    let (hash, uci, games, white_wins, draws, black_wins, rating_sum, rated) = match fields.as_slice()
+    {
+        ["move", hash, uci, games, white_wins, draws, black_wins, rating_sum, rated] =>
+            (hash, *uci, games, white_wins, draws, black_wins, rating_sum, rated),
+        _ => return None,
+    };
+
+    let stats: MoveStats = MoveStats
+    {
+        games: games.parse().ok()?,
+        white_wins: white_wins.parse().ok()?,
+        draws: draws.parse().ok()?,
+        black_wins: black_wins.parse().ok()?,
+        rating_sum: rating_sum.parse().ok()?,
+        rated: rated.parse().ok()?,
+    };
+
+    return Some((u64::from_str_radix(hash, 16).ok()?, uci, stats));
+}
+
+// The moves of the position, the most played first. Moves that aren't
+// legal come from another position with the same hash
+fn __legal_moves<'a>(board: &ChessBoard, moves: impl Iterator<Item = (&'a str, MoveStats)>) -> Vec<(ChessMove, MoveStats)>
+{
+    let mut board: ChessBoard = board.clone();
+    let mut stats: Vec<(ChessMove, MoveStats)> = moves
+        .filter_map(|(uci, stats)| Some((board.from_uci(uci)?, stats)))
+        .collect();
+    stats.sort_by_key(|(_, stats)| core::cmp::Reverse(stats.games));
+
+    return stats;
+}
+
+fn __total(moves: &[(ChessMove, MoveStats)]) -> MoveStats
+{
+    let mut total: MoveStats = MoveStats::default();
+    for (_, stats) in moves
+    {
+        total.__add(stats);
+    }
+    return total;
+}
@@ -0,0 +1,118 @@
+use super::*;
+
+const PGN: &str = r#"[White "Ann"]
+[Black "Bob"]
+[WhiteElo "2000"]
+[BlackElo "1800"]
+[Result "1-0"]
+
+1. e4 e5 2. Nf3 Nc6 1-0
+
+[White "Bob"]
+[Black "Ann"]
+[WhiteElo "1900"]
+[Result "1/2-1/2"]
+
+1. Nf3 e5 2. e4 Nf6 1/2-1/2
+
+[White "Eve"]
+[Black "Tom"]
+[Result "0-1"]
+
+1. d4 d5 0-1
+
+[Result "1-0"]
+
+1. e4 e4 1-0
+
+[Result "*"]
+
+1. e4 *
+"#;
+
+fn uci(stats: &[(ChessMove, MoveStats)], board: &ChessBoard) -> Vec<String>
+{
+    return stats.iter().map(|(mv, _)| board.to_uci(*mv)).collect();
+}
+
+#[test]
+fn test_explorer_index()
+{
+    let mut explorer: Explorer = Explorer::new();
+    let summary: IndexSummary = explorer.index_pgn(PGN.as_bytes()).unwrap();
+    assert!(summary.get_indexed() == 3 && summary.get_skipped() == 2);
+
+    let start: ChessBoard = ChessBoard::init_position();
+    let moves: Vec<(ChessMove, MoveStats)> = explorer.query(&start);
+    assert!(uci(&moves, &start) == ["d2d4", "e2e4", "g1f3"]);
+    let e4: MoveStats = moves.iter().find(|(mv, _)| *mv == ChessMove::drag(0o14, 0o34)).unwrap().1;
+    assert!(e4.get_games() == 1 && e4.get_white_wins() == 1 && e4.get_average_rating() == Some(2000));
+    assert!(explorer.position_stats(&start).get_games() == 3);
+
+    // Both move orders reach the same position
+    let mut board: ChessBoard = start.clone();
+    for mv in ["e2e4", "e7e5", "g1f3"]
+    {
+        let mv: ChessMove = board.from_uci(mv).unwrap();
+        board.make_move(mv);
+    }
+    let replies: Vec<(ChessMove, MoveStats)> = explorer.query(&board);
+    assert!(replies.len() == 2);
+    let total: MoveStats = explorer.position_stats(&board);
+    assert!(total.get_white_wins() == 1 && total.get_draws() == 1 && total.white_score() == 0.75);
+    assert!(total.get_average_rating() == Some(1800));
+
+    let mut text: Vec<u8> = Vec::new();
+    explorer.write_to(&mut text).unwrap();
+    let read: Explorer = Explorer::read_from(&text[..]).unwrap();
+    assert!(read.position_count() == explorer.position_count() && read.query(&board) == replies);
+    assert!(Explorer::read_from("plies\t40\nmove\tzz\te2e4\t1\t1\t0\t0\t0\t0\n".as_bytes()).unwrap_err() == ExplorerError::InvalidLine(2));
+
+    let mut shallow: Explorer = Explorer::new();
+    shallow.set_max_plies(1);
+    shallow.index_pgn(PGN.as_bytes()).unwrap();
+    assert!(shallow.position_count() == 1);
+}
+
+#[test]
+fn test_explorer_file()
+{
+    let mut explorer: Explorer = Explorer::new();
+    explorer.index_pgn(PGN.as_bytes()).unwrap();
+    let path = std::env::temp_dir().join(format!("dynchess-explorer-{}.txt", std::process::id()));
+    explorer.save(&path).unwrap();
+    let mut file: ExplorerFile<_> = ExplorerFile::open(&path).unwrap();
+    assert!(file.get_max_plies() == 40);
+
+    // Every position of the games, and ones that aren't in the index
+    for pgn in ["1. e4 e5 2. Nf3 Nc6 3. Bb5", "1. Nf3 e5 2. e4 Nf6 3. Nc3", "1. d4 d5 2. c4", "1. a3 h6"]
+    {
+        let game: Game = Game::from_pgn(pgn).unwrap();
+        let mut board: ChessBoard = game.get_start().clone();
+        for mv in game.get_moves()
+        {
+            assert!(file.query(&board).unwrap() == explorer.query(&board));
+            assert!(file.position_stats(&board).unwrap() == explorer.position_stats(&board));
+            board.make_move(*mv);
+        }
+        assert!(file.query(&board).unwrap() == explorer.query(&board));
+    }
+
+    let start: ChessBoard = ChessBoard::init_position();
+    assert!(file.position_stats(&start).unwrap().get_games() == 3);
+    drop(file);
+    std::fs::remove_file(&path).unwrap();
+
+    // Without the header, and with a line that can't be read
+    let mut text: Vec<u8> = Vec::new();
+    explorer.write_to(&mut text).unwrap();
+    let moves: &str = core::str::from_utf8(&text).unwrap().split_once("move").map(|(_, rest)| rest).unwrap();
+    let mut file: ExplorerFile<_> = ExplorerFile::new(std::io::Cursor::new(format!("move{}", moves))).unwrap();
+    assert!(file.query(&start).unwrap() == explorer.query(&start));
+
+    let broken: String = format!("plies\t2\nmove\t{:016x}\te2e4\tx\n", start.hash());
+    let mut file: ExplorerFile<_> = ExplorerFile::new(std::io::Cursor::new(broken)).unwrap();
+    assert!(file.get_max_plies() == 2);
+    assert!(file.query(&start).unwrap_err() == ExplorerError::InvalidOffset(8));
+    assert!(ExplorerFile::new(std::io::Cursor::new("plies\tx\n")).unwrap_err() == ExplorerError::InvalidLine(1));
+}
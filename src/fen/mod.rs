@@ -0,0 +1,473 @@
+use super::*;
+use variant::{Standard, Variant};
+
+#[cfg(test)]
+mod tests;
+
+// The most files a board can have, `a` to `z`
+const MAX_WIDTH: usize = 26;
+
+// Reasons why a FEN string could not be read
+#[derive(Debug, PartialEq, Eq, Clone)]
+pub enum FenError
+{
+    // Less than the 4 required fields (placement, color, castling, en passant)
+    MissingField,
+    InvalidPiece(char),
+    // A rank doesn't have 8 blocks, or there aren't 8 ranks
+    InvalidRank,
+    InvalidColor,
+    InvalidCastling(char),
+    InvalidEnPassant,
+    InvalidNumber,
+    // Both colors need exactly one king, unless the variant has no king
+    // for the color
+    InvalidKings,
+    // Unknown piece in the pockets, or pockets in a variant without them
+    InvalidPocket,
+}
+
+// How castling rights are written.
+// `XFen` writes `KQkq` as long as the castling rook is the outermost one on
+// its side of the king and the file of the rook otherwise, which makes it
+// the same as the default FEN for default chess.
+// `Shredder` always writes the file of the rook, e.g. `HAha`
+#[derive(Debug, PartialEq, Eq, Clone, Copy)]
+pub enum CastlingNotation
+{
+    XFen,
+    Shredder,
+}
+
+impl ChessBoard
+{
+    // Initialize a chess board from a FEN string. The castling field can be
+    // written both as `KQkq`, X-FEN and Shredder-FEN. The move counters are
+    // optional
+    pub fn from_fen(fen: &str) -> Result<ChessBoard, FenError>
+    {
+        return Self::from_fen_variant(fen, Arc::new(Standard));
+    }
+
+    // Same as `ChessBoard::from_fen` for a game of the given variant. For
+    // variants that count checks the FEN can end with the checks given by
+    // white and black, like `+1+0`
+    pub fn from_fen_variant(fen: &str, variant: Arc<dyn Variant>) -> Result<ChessBoard, FenError>
+    {
+        let mut fields: Vec<&str> = fen.split_whitespace().collect();
+
+        let mut checks: [u8; 2] = [0, 0];
+        if variant.counts_checks() && fields.last().is_some_and(|f| f.starts_with('+'))
+        {
+            let field: &str = fields.pop().unwrap();
+            let counts: Vec<&str> = field[1..].split('+').collect();
+            if counts.len() != 2
+            {
+                return Err(FenError::InvalidNumber);
+            }
+
+            for i in 0..2
+            {
+                checks[i] = counts[i].parse().map_err(|_| FenError::InvalidNumber)?;
+            }
+        }
+
+        if fields.len() < 4
+        {
+            return Err(FenError::MissingField);
+        }
+
+        // The pockets follow the pieces in brackets, like `[Qn]`
+        let (placement, pockets) = match fields[0].split_once('[')
+        {
+            Some((placement, pockets)) => match pockets.strip_suffix(']')
+            {
+                Some(pockets) => (placement, Some(pockets)),
+                None => return Err(FenError::InvalidPocket),
+            },
+            None => (fields[0], None),
+        };
+
+        // FEN starts from the last rank. Every rank is read into its own row
+        // first, the board size follows from the number of rows and columns.
+        // Pieces promoted from a pawn are followed by a `~`
+        let mut rows: Vec<Vec<ChessPiece>> = Vec::new();
+        let mut promoted: Vec<(usize, usize)> = Vec::new();
+        for rank in placement.split('/')
+        {
+            let mut row: Vec<ChessPiece> = Vec::new();
+            let mut skip: usize = 0;
+
+            for c in rank.chars()
+            {
+                if let Some(d) = c.to_digit(10)
+                {
+                    // A skip past the widest board is an error before it's
+                    // ever allocated
+                    skip = skip.checked_mul(10).and_then(|skip| skip.checked_add(d as usize)).ok_or(FenError::InvalidRank)?;
+                    if row.len() + skip > MAX_WIDTH
+                    {
+                        return Err(FenError::InvalidRank);
+                    }
+                    continue;
+                }
+
+                if c == '~' && skip == 0 && !row.is_empty()
+                {
+                    promoted.push((rows.len(), row.len()-1));
+                    continue;
+                }
+
+                row.resize(row.len() + skip, ChessPiece::Empty);
+                skip = 0;
+
+                match __char_to_piece(c)
+                {
+                    Some(_el) => row.push(_el),
+                    None => return Err(FenError::InvalidPiece(c)),
+                };
+            }
+
+            row.resize(row.len() + skip, ChessPiece::Empty);
+            rows.push(row);
+        }
+
+        let width: usize = rows[0].len();
+        let height: usize = rows.len();
+        let _fits: bool = width >= 3 && height >= 4 && width <= MAX_WIDTH && width*height < 256;
+        if !_fits || rows.iter().any(|row| row.len() != width)
+        {
+            return Err(FenError::InvalidRank);
+        }
+
+        let mut chess_board: ChessBoard = ChessBoard::__empty(width as u8, height as u8);
+        for (i, row) in rows.iter().rev().enumerate()
+        {
+            chess_board.board[i*width..(i+1)*width].copy_from_slice(row);
+        }
+
+        for (i, x) in promoted
+        {
+            chess_board._promoted[(height-1-i)*width + x] = true;
+        }
+
+        if let Some(pockets) = pockets
+        {
+            if !variant.has_pockets()
+            {
+                return Err(FenError::InvalidPocket);
+            }
+
+            for c in pockets.chars()
+            {
+                let _el: ChessPiece = __char_to_piece(c).ok_or(FenError::InvalidPocket)?;
+                if _el == ChessPiece::WKing || _el == ChessPiece::BKing
+                {
+                    return Err(FenError::InvalidPocket);
+                }
+                chess_board.add_to_pocket(_el.is_white(), _el.get_type().unwrap());
+            }
+        }
+
+        // Colors without a king in the variant can have any number of them
+        let w_kings: usize = chess_board.board.iter().filter(|p| **p == ChessPiece::WKing).count();
+        let b_kings: usize = chess_board.board.iter().filter(|p| **p == ChessPiece::BKing).count();
+        if (variant.has_king(true) && w_kings != 1) || (variant.has_king(false) && b_kings != 1)
+        {
+            return Err(FenError::InvalidKings);
+        }
+
+        chess_board._variant = variant;
+        chess_board._checks = checks;
+
+        chess_board.__locate_kings();
+        chess_board.__count_pieces();
+
+        chess_board.w_lock = match fields[1]
+        {
+            "w" => false,
+            "b" => true,
+            _ => return Err(FenError::InvalidColor),
+        };
+
+        if fields[2] != "-"
+        {
+            for c in fields[2].chars()
+            {
+                chess_board.__read_castling(c)?;
+            }
+        }
+
+        if !chess_board.__read_en_passant(fields[3])
+        {
+            return Err(FenError::InvalidEnPassant);
+        }
+        chess_board.__update_en_passant();
+
+        if fields.len() > 4
+        {
+            chess_board._halfmove_clock = fields[4].parse().map_err(|_| FenError::InvalidNumber)?;
+        }
+
+        if fields.len() > 5
+        {
+            chess_board._fullmove = fields[5].parse().map_err(|_| FenError::InvalidNumber)?;
+        }
+
+        // The position might already be over
+        chess_board.__check_state();
+
+        return Ok(chess_board);
+    }
+
+    // Get the FEN string of the position, castling rights in X-FEN
+    pub fn to_fen(&self) -> String
+    {
+        return self.to_fen_with(CastlingNotation::XFen);
+    }
+
+    // Get the FEN string of the position, castling rights in Shredder-FEN
+    pub fn to_shredder_fen(&self) -> String
+    {
+        return self.to_fen_with(CastlingNotation::Shredder);
+    }
+
+    pub fn to_fen_with(&self, notation: CastlingNotation) -> String
+    {
+        let mut fen: String = String::new();
+
+        for y in (0..self._height).rev()
+        {
+            let mut skip: u8 = 0;
+            for x in 0..self._width
+            {
+                let _el: ChessPiece = self.board[self.__conv(x, y) as usize];
+                if _el.is_empty()
+                {
+                    skip += 1;
+                    continue;
+                }
+
+                if skip > 0
+                {
+                    fen.push_str(&skip.to_string());
+                    skip = 0;
+                }
+                fen.push(__piece_to_char(_el));
+                // Only matters for variants with pockets
+                if self._variant.has_pockets() && self._promoted[self.__conv(x, y) as usize]
+                {
+                    fen.push('~');
+                }
+            }
+
+            if skip > 0
+            {
+                fen.push_str(&skip.to_string());
+            }
+            if y > 0
+            {
+                fen.push('/');
+            }
+        }
+
+        if self._variant.has_pockets()
+        {
+            fen.push('[');
+            for white in [true, false]
+            {
+                for _type in self.get_pocket(white)
+                {
+                    fen.push(__piece_to_char(ChessPiece::from_type(*_type, white)));
+                }
+            }
+            fen.push(']');
+        }
+
+        fen.push_str(if self.w_lock { " b " } else { " w " });
+
+        let castling: String = (0..4).filter_map(|side| self.__write_castling(side, notation)).collect();
+        fen.push_str(if castling.is_empty() { "-" } else { &castling });
+
+        // Only written while the pawn can be taken
+        let en_passant: String = self._en_passant.map_or(String::from("-"), |target| self.write_coords(target));
+        fen.push_str(&format!(" {} ", en_passant));
+        fen.push_str(&format!("{} {}", self._halfmove_clock, self._fullmove));
+
+        if self._variant.counts_checks()
+        {
+            fen.push_str(&format!(" +{}+{}", self._checks[0], self._checks[1]));
+        }
+
+        return fen;
+    }
+
+    // Reads coordinates written like `e4`, or `j10` on bigger boards
+    pub fn read_coords(&self, coords: &str) -> Option<u8>
+    {
+        let mut chars = coords.chars();
+        let file: char = chars.next()?;
+        let digits: &str = chars.as_str();
+
+        let _is_number: bool = !digits.is_empty() && digits.bytes().all(|b| b.is_ascii_digit());
+        if !file.is_ascii_lowercase() || !_is_number || digits.starts_with('0')
+        {
+            return None;
+        }
+
+        let rank: u8 = digits.parse().ok()?;
+
+        let x: u8 = file as u8 - b'a';
+        let y: u8 = rank - 1;
+        if x >= self._width || y >= self._height
+        {
+            return None;
+        }
+
+        return Some(self.__conv(x, y));
+    }
+
+    // Writes coordinates like `e4`
+    pub fn write_coords(&self, coords: u8) -> String
+    {
+        let pos: ChessPos = self.__pos(coords);
+        return format!("{}{}", (b'a' + pos.x) as char, pos.y + 1);
+    }
+
+    // Registers the castling right written as `c`
+    fn __read_castling(&mut self, c: char) -> Result<(), FenError>
+    {
+        let white: bool = c.is_ascii_uppercase();
+        let y: u8 = if white { 0 } else { self._height-1 };
+        let _rook: ChessPiece = if white { ChessPiece::WRook } else { ChessPiece::BRook };
+        let king: ChessPos = self.__pos(if white { self._w_king } else { self._b_king });
+        let k_x: u8 = king.x;
+
+        // The king has to be on its first rank to castle at all
+        if king.y != y
+        {
+            return Err(FenError::InvalidCastling(c));
+        }
+
+        let rook_x: Option<u8> = match c.to_ascii_lowercase()
+        {
+            // The outermost rook on the side of the king
+            'k' => (k_x+1..self._width).rev().find(|x| self.board[self.__conv(*x, y) as usize] == _rook),
+            'q' => (0..k_x).find(|x| self.board[self.__conv(*x, y) as usize] == _rook),
+            'a'..='z' => Some(c.to_ascii_lowercase() as u8 - b'a')
+                .filter(|x| *x < self._width && self.board[self.__conv(*x, y) as usize] == _rook && *x != k_x),
+            _ => None,
+        };
+
+        let rook_x: u8 = match rook_x
+        {
+            Some(x) => x,
+            None => return Err(FenError::InvalidCastling(c)),
+        };
+
+        let side: usize = (if white { 0 } else { 2 }) + (if rook_x > k_x { 0 } else { 1 });
+        self._castling[side] = Some(self.__conv(rook_x, y));
+
+        return Ok(());
+    }
+
+    // Writes the castling right `side` if it's still there
+    fn __write_castling(&self, side: usize, notation: CastlingNotation) -> Option<char>
+    {
+        let rook: ChessPos = self.__pos(self._castling[side]?);
+        let white: bool = side < 2;
+        let king_side: bool = side == 0 || side == 2;
+        let _rook: ChessPiece = self.board[rook.raw() as usize];
+
+        // Is there another rook further out on the same side?
+        let outer: Vec<u8> = if king_side { (rook.x+1..self._width).collect() } else { (0..rook.x).collect() };
+        let outermost: bool = !outer.iter().any(|x| self.board[self.__conv(*x, rook.y) as usize] == _rook);
+
+        let c: char = if notation == CastlingNotation::XFen && outermost
+        {
+            if king_side { 'k' } else { 'q' }
+        }
+        else
+        {
+            (b'a' + rook.x) as char
+        };
+
+        return Some(if white { c.to_ascii_uppercase() } else { c });
+    }
+}
+
+impl ChessPiece
+{
+    // The letter of the piece in FEN, uppercase for white. An empty block
+    // is written as `.`
+    pub fn to_fen_char(self) -> char
+    {
+        return __piece_to_char(self);
+    }
+}
+
+pub(crate) fn __piece_to_char(piece: ChessPiece) -> char
+{
+    return match piece
+    {
+        ChessPiece::Empty =>    '.',
+        ChessPiece::BKing =>    'k',
+        ChessPiece::BQueen =>   'q',
+        ChessPiece::BRook =>    'r',
+        ChessPiece::BBishop =>  'b',
+        ChessPiece::BKnight =>  'n',
+        ChessPiece::BPawn =>    'p',
+        ChessPiece::WKing =>    'K',
+        ChessPiece::WQueen =>   'Q',
+        ChessPiece::WRook =>    'R',
+        ChessPiece::WBishop =>  'B',
+        ChessPiece::WKnight =>  'N',
+        ChessPiece::WPawn =>    'P',
+
+        ChessPiece::BArchbishop =>  'a',
+        ChessPiece::BChancellor =>  'c',
+        ChessPiece::BAmazon =>      'm',
+        ChessPiece::BCamel =>       'l',
+        ChessPiece::BZebra =>       'z',
+        ChessPiece::BGrasshopper => 'g',
+        ChessPiece::WArchbishop =>  'A',
+        ChessPiece::WChancellor =>  'C',
+        ChessPiece::WAmazon =>      'M',
+        ChessPiece::WCamel =>       'L',
+        ChessPiece::WZebra =>       'Z',
+        ChessPiece::WGrasshopper => 'G',
+    };
+}
+
+pub(crate) fn __char_to_piece(c: char) -> Option<ChessPiece>
+{
+    return match c
+    {
+        'k' => Some(ChessPiece::BKing),
+        'q' => Some(ChessPiece::BQueen),
+        'r' => Some(ChessPiece::BRook),
+        'b' => Some(ChessPiece::BBishop),
+        'n' => Some(ChessPiece::BKnight),
+        'p' => Some(ChessPiece::BPawn),
+        'K' => Some(ChessPiece::WKing),
+        'Q' => Some(ChessPiece::WQueen),
+        'R' => Some(ChessPiece::WRook),
+        'B' => Some(ChessPiece::WBishop),
+        'N' => Some(ChessPiece::WKnight),
+        'P' => Some(ChessPiece::WPawn),
+
+        'a' => Some(ChessPiece::BArchbishop),
+        'c' => Some(ChessPiece::BChancellor),
+        'm' => Some(ChessPiece::BAmazon),
+        'l' => Some(ChessPiece::BCamel),
+        'z' => Some(ChessPiece::BZebra),
+        'g' => Some(ChessPiece::BGrasshopper),
+        'A' => Some(ChessPiece::WArchbishop),
+        'C' => Some(ChessPiece::WChancellor),
+        'M' => Some(ChessPiece::WAmazon),
+        'L' => Some(ChessPiece::WCamel),
+        'Z' => Some(ChessPiece::WZebra),
+        'G' => Some(ChessPiece::WGrasshopper),
+        _ => None,
+    };
+}
@@ -0,0 +1,129 @@
+use super::*;
+
+const START: &str = "rnbqkbnr/pppppppp/8/8/8/8/PPPPPPPP/RNBQKBNR w KQkq - 0 1";
+
+#[test]
+fn test_fen_start_position()
+{
+    let board: ChessBoard = ChessBoard::from_fen(START).unwrap();
+    assert!(board.get_board() == ChessBoard::init_position().get_board());
+    assert!(board.castling_rights() == ChessBoard::init_position().castling_rights());
+    assert!(board.white_turn());
+
+    assert_eq!(ChessBoard::init_position().to_fen(), START);
+    assert_eq!(ChessBoard::init_position().to_shredder_fen(),
+        "rnbqkbnr/pppppppp/8/8/8/8/PPPPPPPP/RNBQKBNR w HAha - 0 1");
+}
+
+#[test]
+fn test_fen_after_moves()
+{
+    let mut board: ChessBoard = ChessBoard::init_position();
+    board.drag(0o14, 0o34);
+    assert_eq!(board.to_fen(), "rnbqkbnr/pppppppp/8/8/4P3/8/PPPP1PPP/RNBQKBNR b KQkq - 0 1");
+    board.drag(0o76, 0o55);
+    board.drag(0o04, 0o14);
+    assert_eq!(board.to_fen(), "rnbqkb1r/pppppppp/5n2/8/4P3/8/PPPPKPPP/RNBQ1BNR b kq - 2 2");
+
+    // The en passant block is only written while the pawn can be taken
+    let mut board: ChessBoard = ChessBoard::init_position();
+    for (from, to) in [(0o14, 0o34), (0o60, 0o50), (0o34, 0o44), (0o63, 0o43)]
+    {
+        board.drag(from, to);
+    }
+    assert_eq!(board.to_fen(), "rnbqkbnr/1pp1pppp/p7/3pP3/8/8/PPPP1PPP/RNBQKBNR w KQkq d6 0 3");
+    board.drag(0o10, 0o20);
+    assert_eq!(board.to_fen(), "rnbqkbnr/1pp1pppp/p7/3pP3/8/P7/1PPP1PPP/RNBQKBNR b KQkq - 0 3");
+    let board: ChessBoard = ChessBoard::from_fen("rnbqkbnr/ppp1p1pp/8/3pPp2/8/8/PPPP1PPP/RNBQKBNR w KQkq f6 0 3").unwrap();
+    assert_eq!(board.get_en_passant(), Some(0o55));
+    assert_eq!(board.to_fen(), "rnbqkbnr/ppp1p1pp/8/3pPp2/8/8/PPPP1PPP/RNBQKBNR w KQkq f6 0 3");
+
+    // Optional counters
+    let board: ChessBoard = ChessBoard::from_fen("4k3/8/8/8/8/8/8/4K3 b - -").unwrap();
+    assert_eq!(board.to_fen(), "4k3/8/8/8/8/8/8/4K3 b - - 0 1");
+}
+
+#[test]
+fn test_fen_castling_notation()
+{
+    // X-FEN uses the file only if there's another rook further out
+    let fen: &str = "4k3/8/8/8/8/8/8/RR2K1RR w GBkq - 0 1";
+    let board: ChessBoard = ChessBoard::from_fen("4k2r/8/8/8/8/8/8/RR2K1RR w GBk - 0 1")
+        .unwrap();
+    assert!(board.castling_rights() == [Some(0o06), Some(0o01), Some(0o77), None]);
+    assert_eq!(board.to_fen(), "4k2r/8/8/8/8/8/8/RR2K1RR w GBk - 0 1");
+    assert!(ChessBoard::from_fen(fen).is_err());
+
+    // Shredder-FEN and KQkq read into the same rights
+    let a: ChessBoard = ChessBoard::from_fen("r3k2r/8/8/8/8/8/8/R3K2R w HAha - 0 1").unwrap();
+    let b: ChessBoard = ChessBoard::from_fen("r3k2r/8/8/8/8/8/8/R3K2R w KQkq - 0 1").unwrap();
+    assert!(a.castling_rights() == b.castling_rights());
+    assert_eq!(a.to_fen(), "r3k2r/8/8/8/8/8/8/R3K2R w KQkq - 0 1");
+
+    // Chess960 start positions
+    let board: ChessBoard = ChessBoard::init_chess960(0);
+    assert_eq!(board.to_fen(), "bbqnnrkr/pppppppp/8/8/8/8/PPPPPPPP/BBQNNRKR w KQkq - 0 1");
+    assert_eq!(board.to_shredder_fen(), "bbqnnrkr/pppppppp/8/8/8/8/PPPPPPPP/BBQNNRKR w HFhf - 0 1");
+}
+
+#[test]
+fn test_fen_errors()
+{
+    assert!(ChessBoard::from_fen("").err() == Some(FenError::MissingField));
+    assert!(ChessBoard::from_fen("4k3/8/8/8/8/8/8/4K3 w -").err() == Some(FenError::MissingField));
+    assert!(ChessBoard::from_fen("4k3/8/8/8/8/8/7/4K3 w - -").err() == Some(FenError::InvalidRank));
+    assert!(ChessBoard::from_fen("k1/8/8/K1 w - -").err() == Some(FenError::InvalidRank));
+    assert!(ChessBoard::from_fen("4k3/8/8/8/8/8/8/4K4 w - -").err() == Some(FenError::InvalidRank));
+    assert!(ChessBoard::from_fen("4k3/8/8/8/8/8/8/4X3 w - -").err() == Some(FenError::InvalidPiece('X')));
+    assert!(ChessBoard::from_fen("8/8/8/8/8/8/8/4K3 w - -").err() == Some(FenError::InvalidKings));
+    assert!(ChessBoard::from_fen("4k3/8/8/8/8/8/8/4K3 x - -").err() == Some(FenError::InvalidColor));
+    assert!(ChessBoard::from_fen("4k3/8/8/8/8/8/8/4K3 w K -").err() == Some(FenError::InvalidCastling('K')));
+    assert!(ChessBoard::from_fen("4k3/8/8/8/8/8/8/4K3 w - z9").err() == Some(FenError::InvalidEnPassant));
+    assert!(ChessBoard::from_fen("4k3/8/8/8/8/8/8/4K3 w - e6").err() == Some(FenError::InvalidEnPassant));
+    assert!(ChessBoard::from_fen("4k3/8/8/4p3/8/8/8/4K3 w - e3").err() == Some(FenError::InvalidEnPassant));
+    assert!(ChessBoard::from_fen("4k3/8/8/8/8/8/8/4K3 w - - x 1").err() == Some(FenError::InvalidNumber));
+
+    // Skips that overflow or are wider than any board
+    assert!(ChessBoard::from_fen("99999999999999999999999/8/8/8/8/8/8/8 w - - 0 1").err() == Some(FenError::InvalidRank));
+    assert!(ChessBoard::from_fen("100000000000/8/8/8/8/8/8/4K3 w - - 0 1").err() == Some(FenError::InvalidRank));
+    assert!(ChessBoard::from_fen("k26/8/8/8/8/8/8/4K3 w - - 0 1").err() == Some(FenError::InvalidRank));
+}
+
+#[test]
+fn test_fen_game_over()
+{
+    let board: ChessBoard = ChessBoard::from_fen("7k/5Q2/6K1/8/8/8/8/8 b - - 0 1").unwrap();
+    assert!(board.get_state() == ChessState::Stalemate);
+
+    let board: ChessBoard = ChessBoard::from_fen("7k/6Q1/6K1/8/8/8/8/8 b - - 0 1").unwrap();
+    assert!(board.get_state() == ChessState::Checkmate);
+}
+
+#[test]
+fn test_fen_board_dimensions()
+{
+    // 10 x 8, numbers can take two digits
+    let fen: &str = "r3k4r/pppppppppp/10/10/10/10/PPPPPPPPPP/R3K4R w KQkq - 0 1";
+    let board: ChessBoard = ChessBoard::from_fen(fen).unwrap();
+    assert!(board.width() == 10 && board.height() == 8);
+    assert!(board.get_squares()[9] == ChessPiece::WRook);
+    assert!(board.castling_rights() == [Some(9), Some(0), Some(79), Some(70)]);
+    assert_eq!(board.to_fen(), fen);
+    assert_eq!(board.to_shredder_fen(),
+        "r3k4r/pppppppppp/10/10/10/10/PPPPPPPPPP/R3K4R w JAja - 0 1");
+
+    // 6 x 6
+    let fen: &str = "rnqknr/pppppp/6/6/PPPPPP/RNQKNR w - - 0 1";
+    let board: ChessBoard = ChessBoard::from_fen(fen).unwrap();
+    assert!(board.width() == 6 && board.height() == 6);
+    assert_eq!(board.to_fen(), fen);
+
+    // Coordinates follow the board size
+    let board: ChessBoard = ChessBoard::from_fen("k9/10/10/10/10/10/10/10/10/K9 w - -").unwrap();
+    assert!(board.read_coords("j10") == Some(99));
+    assert!(board.read_coords("a1") == Some(0));
+    assert!(board.read_coords("k1").is_none());
+    assert!(board.read_coords("a11").is_none());
+    assert!(board.read_coords("a01").is_none());
+    assert_eq!(board.write_coords(99), "j10");
+}
@@ -0,0 +1,90 @@
+// What happened to a `Game`, for the ones that want to react to it like
+// sounds, animations, loggers or a network peer, without comparing boards.
+// Observers are called in the order they subscribed, after the game was
+// changed
+use super::*;
+use alloc::boxed::Box;
+
+// Handed out by `Game::subscribe` to unsubscribe later
+pub type ObserverId = usize;
+
+pub(super) type Observer = Box<dyn FnMut(&GameEvent) + Send>;
+
+#[derive(Debug, PartialEq, Eq, Clone)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum GameEvent
+{
+    // A move was played by the color, written in SAN. Comes before the
+    // other events of the move
+    Moved { mv: ChessMove, san: String, white: bool },
+    // A piece left the board by the move, more than one in atomic chess
+    Captured { block: u8, piece: ChessPiece },
+    // The pawn turned into `piece` on the block
+    Promoted { block: u8, piece: ChessPiece },
+    // The king of the color castled, on the king side or the queen side
+    Castled { white: bool, king_side: bool },
+    // The king of the color is in check after the move
+    Check { white: bool },
+    // The game is over, either on the board (`end` is `None` and `state`
+    // says how) or by a `GameEnd`
+    Ended { winner: Option<bool>, end: Option<GameEnd>, state: ChessState },
+    // The move was taken back
+    Undone { mv: ChessMove },
+    // The game was restarted from a new position, see `Game::load`
+    Loaded,
+}
+
+// The events of the move that took `before` to `after`, apart from the end
+// of the game
+pub(super) fn move_events(before: &ChessBoard, after: &ChessBoard, mv: ChessMove, san: String) -> Vec<GameEvent>
+{
+    let white: bool = before.white_turn();
+    let mut events: Vec<GameEvent> = vec![GameEvent::Moved { mv, san, white }];
+
+    let castling: Option<usize> = match mv
+    {
+        ChessMove::Drag { from, to, .. } => before.__castling_side(from, to),
+        ChessMove::Drop { .. } => None,
+    };
+
+    // The castling rook moves without being taken
+    if castling.is_none()
+    {
+        let from: Option<u8> = match mv
+        {
+            ChessMove::Drag { from, .. } => Some(from),
+            ChessMove::Drop { .. } => None,
+        };
+
+        for (block, (&was, &is)) in before.get_squares().iter().zip(after.get_squares()).enumerate()
+        {
+            let block: u8 = block as u8;
+            if !was.is_empty() && was != is && Some(block) != from
+            {
+                events.push(GameEvent::Captured { block, piece: was });
+            }
+        }
+    }
+
+    if let ChessMove::Drag { from, to, .. } = mv
+    {
+        let moved: Option<ChessPieceType> = before.get_piece(from).get_type();
+        let landed: ChessPiece = after.get_piece(to);
+        if moved == Some(ChessPieceType::Pawn) && !landed.is_empty() && landed.get_type() != moved
+        {
+            events.push(GameEvent::Promoted { block: to, piece: landed });
+        }
+    }
+
+    if let Some(side) = castling
+    {
+        events.push(GameEvent::Castled { white, king_side: side.is_multiple_of(2) });
+    }
+
+    if after.is_check(after.white_turn())
+    {
+        events.push(GameEvent::Check { white: after.white_turn() });
+    }
+
+    return events;
+}
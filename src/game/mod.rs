@@ -0,0 +1,475 @@
+// A game on top of a `ChessBoard`: the moves played so far, taking them
+// back, resigning, draws by repetition or the fifty move rule, PGN export
+// and observers of what happens to it.
+use super::*;
+use alloc::boxed::Box;
+
+#[cfg(test)]
+mod tests;
+mod events;
+mod pgn;
+
+pub use events::{GameEvent, ObserverId};
+pub use pgn::{split_games, PgnError};
+#[cfg(feature = "std")]
+pub use pgn::PgnReader;
+
+// How the game ended, on top of `ChessState`
+#[derive(Debug, PartialEq, Eq, Clone, Copy)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum GameEnd
+{
+    // The color that resigned, true for white
+    Resigned(bool),
+    // The color that ran out of time
+    Timeout(bool),
+    Repetition,
+    FiftyMoves,
+    // Agreed by the players, or adjudicated
+    Draw,
+}
+
+pub struct Game
+{
+    board: ChessBoard,
+    // The boards before every move, to take moves back
+    history: Vec<ChessBoard>,
+    moves: Vec<ChessMove>,
+    // The moves in SAN, written when they were played
+    san: Vec<String>,
+    // PGN tags apart from the ones that follow from the game itself
+    tags: Vec<(String, String)>,
+    // The NAGs and the comment written after every move
+    annotations: Vec<(Vec<u8>, Option<String>)>,
+    end: Option<GameEnd>,
+    // Called on every event, see `subscribe`
+    observers: Vec<(ObserverId, events::Observer)>,
+    next_observer: ObserverId,
+}
+
+impl Game
+{
+    // A game of default chess from the start position
+    pub fn new() -> Game
+    {
+        return Game::from_board(ChessBoard::init_position());
+    }
+
+    // A game starting from the given position
+    pub fn from_board(board: ChessBoard) -> Game
+    {
+        let tags: Vec<(String, String)> = ["Event", "Site", "Date", "Round", "White", "Black"].iter()
+            .map(|tag| (tag.to_string(), String::from("?"))).collect();
+
+        return Game { board, history: Vec::new(), moves: Vec::new(), san: Vec::new(), tags, annotations: Vec::new(), end: None, observers: Vec::new(), next_observer: 0 };
+    }
+
+    pub fn get_board(&self) -> &ChessBoard
+    {
+        return &self.board;
+    }
+
+    // The board the game started from
+    pub fn get_start(&self) -> &ChessBoard
+    {
+        return self.history.first().unwrap_or(&self.board);
+    }
+
+    pub fn get_moves(&self) -> &[ChessMove]
+    {
+        return &self.moves;
+    }
+
+    pub fn get_san(&self) -> &[String]
+    {
+        return &self.san;
+    }
+
+    // If the game is still going on
+    pub fn is_on(&self) -> bool
+    {
+        return self.end.is_none() && self.board.get_state() == ChessState::On;
+    }
+
+    // Plays the move, returns false if it isn't legal or the game is over
+    pub fn play(&mut self, mv: ChessMove) -> bool
+    {
+        if !self.is_on() || !self.board.legal_moves().contains(&mv)
+        {
+            return false;
+        }
+
+        let san: String = self.board.to_san(mv);
+        let before: ChessBoard = self.board.clone();
+        self.board.make_move(mv);
+
+        let mut events: Vec<GameEvent> = match self.observers.is_empty()
+        {
+            true => Vec::new(),
+            false => events::move_events(&before, &self.board, mv, san.clone()),
+        };
+
+        self.history.push(before);
+        self.moves.push(mv);
+        self.san.push(san);
+        self.annotations.push((Vec::new(), None));
+
+        if self.board.halfmove_clock() >= 100
+        {
+            self.end = Some(GameEnd::FiftyMoves);
+        }
+
+        if self.__repetitions() >= 3
+        {
+            self.end = Some(GameEnd::Repetition);
+        }
+
+        if !self.is_on()
+        {
+            events.push(self.__ended());
+        }
+        self.__emit(events);
+
+        return true;
+    }
+
+    // Plays a move written in SAN
+    pub fn play_san(&mut self, san: &str) -> bool
+    {
+        return match self.board.from_san(san)
+        {
+            Some(mv) => self.play(mv),
+            None => false,
+        };
+    }
+
+    // Plays a move written in UCI notation
+    pub fn play_uci(&mut self, uci: &str) -> bool
+    {
+        return match self.board.from_uci(uci)
+        {
+            Some(mv) => self.play(mv),
+            None => false,
+        };
+    }
+
+    // Takes the last move back, also after the game ended by it. Returns
+    // false if there's no move to take back
+    pub fn undo(&mut self) -> bool
+    {
+        let board: ChessBoard = match self.history.pop()
+        {
+            Some(board) => board,
+            None => return false,
+        };
+
+        self.board = board;
+        let mv: Option<ChessMove> = self.moves.pop();
+        self.san.pop();
+        self.annotations.pop();
+        self.end = None;
+
+        if let Some(mv) = mv
+        {
+            self.__emit(vec![GameEvent::Undone { mv }]);
+        }
+
+        return true;
+    }
+
+    // Starts over from the position, keeping the tags and the observers
+    pub fn load(&mut self, board: ChessBoard)
+    {
+        self.board = board;
+        self.history.clear();
+        self.moves.clear();
+        self.san.clear();
+        self.annotations.clear();
+        self.end = None;
+
+        self.__emit(vec![GameEvent::Loaded]);
+    }
+
+    // Calls the observer on every event from now on, see `GameEvent`
+    pub fn subscribe(&mut self, observer: impl FnMut(&GameEvent) + Send + 'static) -> ObserverId
+    {
+        let id: ObserverId = self.next_observer;
+        self.next_observer += 1;
+        self.observers.push((id, Box::new(observer)));
+
+        return id;
+    }
+
+    // The events from now on, sent to a channel rather than to a callback.
+    // Events after the receiver was dropped are lost
+    #[cfg(feature = "std")]
+    pub fn events(&mut self) -> std::sync::mpsc::Receiver<GameEvent>
+    {
+        let (sender, receiver) = std::sync::mpsc::channel();
+        self.subscribe(move |event| { let _ = sender.send(event.clone()); });
+
+        return receiver;
+    }
+
+    // Returns false if the observer wasn't subscribed
+    pub fn unsubscribe(&mut self, id: ObserverId) -> bool
+    {
+        let count: usize = self.observers.len();
+        self.observers.retain(|(observer, _)| *observer != id);
+
+        return self.observers.len() != count;
+    }
+
+    // The color resigns, true for white
+    pub fn resign(&mut self, white: bool)
+    {
+        if self.is_on()
+        {
+            self.end = Some(GameEnd::Resigned(white));
+            let ended: GameEvent = self.__ended();
+            self.__emit(vec![ended]);
+        }
+    }
+
+    // The color lost on time, true for white
+    pub fn timeout(&mut self, white: bool)
+    {
+        if self.is_on()
+        {
+            self.end = Some(GameEnd::Timeout(white));
+            let ended: GameEvent = self.__ended();
+            self.__emit(vec![ended]);
+        }
+    }
+
+    // The game ends in a draw, like when the players agree to one
+    pub fn draw(&mut self)
+    {
+        if self.is_on()
+        {
+            self.end = Some(GameEnd::Draw);
+            let ended: GameEvent = self.__ended();
+            self.__emit(vec![ended]);
+        }
+    }
+
+    // How the game ended, if it didn't end on the board
+    pub fn get_end(&self) -> Option<GameEnd>
+    {
+        return self.end;
+    }
+
+    // The color that won the game, `None` while it's on or for a draw
+    pub fn get_winner(&self) -> Option<bool>
+    {
+        return match self.end
+        {
+            Some(GameEnd::Resigned(white)) | Some(GameEnd::Timeout(white)) => Some(!white),
+            Some(_) => None,
+            None => self.board.get_winner(),
+        };
+    }
+
+    // The result as written in PGN: `1-0`, `0-1`, `1/2-1/2` or `*`
+    pub fn result(&self) -> &str
+    {
+        if self.is_on()
+        {
+            return "*";
+        }
+
+        return match self.get_winner()
+        {
+            Some(true) => "1-0",
+            Some(false) => "0-1",
+            None => "1/2-1/2",
+        };
+    }
+
+    fn __ended(&self) -> GameEvent
+    {
+        return GameEvent::Ended { winner: self.get_winner(), end: self.end, state: self.board.get_state() };
+    }
+
+    fn __emit(&mut self, events: Vec<GameEvent>)
+    {
+        for event in events
+        {
+            for (_, observer) in self.observers.iter_mut()
+            {
+                observer(&event);
+            }
+        }
+    }
+
+    // Sets a PGN tag, like `White` or `Event`
+    pub fn set_tag(&mut self, name: &str, value: &str)
+    {
+        match self.tags.iter_mut().find(|(n, _)| n == name)
+        {
+            Some(tag) => tag.1 = value.to_string(),
+            None => self.tags.push((name.to_string(), value.to_string())),
+        };
+    }
+
+    // All the PGN tags that were set, in order
+    pub fn get_tags(&self) -> &[(String, String)]
+    {
+        return &self.tags;
+    }
+
+    pub fn get_tag(&self, name: &str) -> Option<&str>
+    {
+        return self.tags.iter().find(|(n, _)| n == name).map(|(_, v)| v.as_str());
+    }
+
+    // Sets the comment written after the move of the index in `get_moves`.
+    // Returns false if there's no such move
+    pub fn set_comment(&mut self, ply: usize, comment: &str) -> bool
+    {
+        return match self.annotations.get_mut(ply)
+        {
+            Some((_, old)) => {
+                *old = Some(comment.replace('}', ")"));
+                true
+            },
+            None => false,
+        };
+    }
+
+    pub fn get_comment(&self, ply: usize) -> Option<&str>
+    {
+        return self.annotations.get(ply).and_then(|(_, comment)| comment.as_deref());
+    }
+
+    // Adds a Numeric Annotation Glyph to the move of the index in
+    // `get_moves`, like 2 for `?` or 4 for `??`. Returns false if there's
+    // no such move
+    pub fn add_nag(&mut self, ply: usize, nag: u8) -> bool
+    {
+        return match self.annotations.get_mut(ply)
+        {
+            Some((nags, _)) => {
+                if !nags.contains(&nag)
+                {
+                    nags.push(nag);
+                }
+                true
+            },
+            None => false,
+        };
+    }
+
+    pub fn get_nags(&self, ply: usize) -> &[u8]
+    {
+        return self.annotations.get(ply).map_or(&[], |(nags, _)| nags.as_slice());
+    }
+
+    // Writes the game in PGN. The variant and the start position are added
+    // as tags when they aren't the default ones
+    pub fn to_pgn(&self) -> String
+    {
+        let start: &ChessBoard = self.get_start();
+        let mut pgn: String = String::new();
+
+        for (name, value) in self.tags.iter()
+        {
+            pgn.push_str(&format!("[{} \"{}\"]\n", name, value));
+        }
+        pgn.push_str(&format!("[Result \"{}\"]\n", self.result()));
+
+        let variant: &str = start.get_variant().name();
+        if variant != "Standard"
+        {
+            pgn.push_str(&format!("[Variant \"{}\"]\n", variant));
+        }
+
+        let fen: String = start.to_fen();
+        if fen != ChessBoard::init_variant(start._variant.clone()).to_fen()
+        {
+            pgn.push_str(&format!("[SetUp \"1\"]\n[FEN \"{}\"]\n", fen));
+        }
+        pgn.push('\n');
+
+        let mut tokens: Vec<String> = Vec::new();
+        let mut number: u16 = start.fullmove();
+        let mut white: bool = start.white_turn();
+        if !white
+        {
+            tokens.push(format!("{}...", number));
+        }
+
+        let mut commented: bool = false;
+        for (san, (nags, comment)) in self.san.iter().zip(self.annotations.iter())
+        {
+            if white
+            {
+                tokens.push(format!("{}.", number));
+            }
+            else
+            {
+                // The number is written again after a comment
+                if commented
+                {
+                    tokens.push(format!("{}...", number));
+                }
+                number += 1;
+            }
+            tokens.push(san.clone());
+            tokens.extend(nags.iter().map(|nag| format!("${}", nag)));
+
+            // Words on their own, for the lines to break between them
+            commented = comment.is_some();
+            if let Some(comment) = comment
+            {
+                let comment: String = format!("{{{}}}", comment);
+                tokens.extend(comment.split_whitespace().map(|word| word.to_string()));
+            }
+            white = !white;
+        }
+        tokens.push(self.result().to_string());
+
+        // Lines are kept below 80 characters
+        let mut line: String = String::new();
+        for token in tokens
+        {
+            if !line.is_empty() && line.len() + token.len() + 1 > 79
+            {
+                pgn.push_str(&line);
+                pgn.push('\n');
+                line.clear();
+            }
+
+            if !line.is_empty()
+            {
+                line.push(' ');
+            }
+            line.push_str(&token);
+        }
+        pgn.push_str(&line);
+        pgn.push('\n');
+
+        return pgn;
+    }
+
+    // How many times the current position came up, counting the pieces,
+    // the color to move and the castling rights
+    fn __repetitions(&self) -> usize
+    {
+        let key = |board: &ChessBoard| -> String
+        {
+            return board.to_fen().split(' ').take(4).collect::<Vec<&str>>().join(" ");
+        };
+
+        let current: String = key(&self.board);
+        return 1 + self.history.iter().filter(|board| key(board) == current).count();
+    }
+}
+
+impl Default for Game
+{
+    fn default() -> Self
+    {
+        return Self::new();
+    }
+}
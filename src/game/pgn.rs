@@ -0,0 +1,252 @@
+use super::*;
+use fen::FenError;
+
+#[derive(Debug, PartialEq, Eq, Clone)]
+pub enum PgnError
+{
+    // A tag that isn't written like `[Name "value"]`
+    InvalidTag(String),
+    UnknownVariant(String),
+    InvalidFen(FenError),
+    // The move isn't legal or can't be read, written as in the PGN
+    IllegalMove(String),
+    // A comment or a variation that isn't closed
+    Unterminated,
+}
+
+impl Game
+{
+    // Reads a single game from PGN. Comments, NAGs and variations are
+    // skipped. A game that isn't over on the board ends the way the result
+    // says: a draw, or a loss on time if the `Termination` tag says so and
+    // by resignation otherwise
+    pub fn from_pgn(pgn: &str) -> Result<Game, PgnError>
+    {
+        let mut tags: Vec<(String, String)> = Vec::new();
+        let mut movetext: String = String::new();
+
+        for line in pgn.lines()
+        {
+            let line: &str = line.trim();
+            if line.starts_with('[') && movetext.trim().is_empty()
+            {
+                tags.push(__read_tag(line).ok_or(PgnError::InvalidTag(line.to_string()))?);
+            }
+            // Escaped lines
+            else if !line.starts_with('%')
+            {
+                movetext.push_str(line);
+                movetext.push('\n');
+            }
+        }
+
+        let tag = |name: &str| -> Option<&str>
+        {
+            return tags.iter().find(|(n, _)| n == name).map(|(_, v)| v.as_str());
+        };
+
+        let variant: Arc<dyn variant::Variant> = match tag("Variant")
+        {
+            Some(name) => variant::from_name(name).ok_or(PgnError::UnknownVariant(name.to_string()))?,
+            None => Arc::new(variant::Standard),
+        };
+        let board: ChessBoard = match tag("FEN")
+        {
+            Some(fen) => ChessBoard::from_fen_variant(fen, variant).map_err(PgnError::InvalidFen)?,
+            None => ChessBoard::init_variant(variant),
+        };
+
+        let mut game: Game = Game::from_board(board);
+        // The rest follows from the game itself, see `Game::to_pgn`
+        for (name, value) in tags.iter()
+        {
+            if !["Result", "Variant", "SetUp", "FEN"].contains(&name.as_str())
+            {
+                game.set_tag(name, value);
+            }
+        }
+
+        let mut result: Option<&str> = None;
+        for token in __movetext_tokens(&movetext)?
+        {
+            if ["1-0", "0-1", "1/2-1/2", "*"].contains(&token)
+            {
+                result = Some(token);
+                break;
+            }
+
+            // Move numbers, also when they're written right before the move
+            let san: &str = token.rsplit('.').next().unwrap();
+            let san: &str = san.trim_end_matches(['!', '?']);
+            if san.is_empty()
+            {
+                continue;
+            }
+
+            if !game.play_san(san)
+            {
+                return Err(PgnError::IllegalMove(token.to_string()));
+            }
+        }
+
+        let on_time: bool = tag("Termination").is_some_and(|t| t.to_lowercase().contains("time"));
+        match result
+        {
+            Some("1-0") | Some("0-1") if on_time => game.timeout(result == Some("0-1")),
+            Some("1-0") | Some("0-1") => game.resign(result == Some("0-1")),
+            Some("1/2-1/2") => game.draw(),
+            _ => {},
+        };
+
+        return Ok(game);
+    }
+}
+
+// Reads `[Name "value"]` with `\"` and `\\` escaped in the value
+fn __read_tag(line: &str) -> Option<(String, String)>
+{
+    let inner: &str = line.strip_prefix('[')?.strip_suffix(']')?.trim();
+    let (name, value) = inner.split_once(char::is_whitespace)?;
+    let value: &str = value.trim().strip_prefix('"')?.strip_suffix('"')?;
+
+    let mut unescaped: String = String::new();
+    let mut chars = value.chars();
+    while let Some(c) = chars.next()
+    {
+        unescaped.push(if c == '\\' { chars.next()? } else { c });
+    }
+
+    return Some((name.to_string(), unescaped));
+}
+
+// Splits the moves of the mainline into tokens, without the comments, the
+// NAGs and the variations
+fn __movetext_tokens(movetext: &str) -> Result<Vec<&str>, PgnError>
+{
+    let mut tokens: Vec<&str> = Vec::new();
+    let mut depth: usize = 0;
+    let mut start: Option<usize> = None;
+    let mut chars = movetext.char_indices();
+
+    while let Some((i, c)) = chars.next()
+    {
+        if !c.is_whitespace() && !"{;()".contains(c)
+        {
+            start = start.or(Some(i));
+            continue;
+        }
+
+        if let Some(s) = start.take()
+        {
+            if depth == 0 { tokens.push(&movetext[s..i]); }
+        }
+
+        match c
+        {
+            // Skips the comment up to its end
+            '{' if !chars.any(|(_, c)| c == '}') => return Err(PgnError::Unterminated),
+            ';' => { chars.any(|(_, c)| c == '\n'); },
+            '(' => depth += 1,
+            ')' => depth = depth.checked_sub(1).ok_or(PgnError::Unterminated)?,
+            _ => {},
+        };
+    }
+
+    if let Some(s) = start
+    {
+        if depth == 0 { tokens.push(&movetext[s..]); }
+    }
+
+    if depth > 0
+    {
+        return Err(PgnError::Unterminated);
+    }
+
+    tokens.retain(|token| !token.starts_with('$'));
+    return Ok(tokens);
+}
+
+// Splits a PGN file into its games, each one starting with its tags
+pub fn split_games(pgn: &str) -> Vec<&str>
+{
+    let mut games: Vec<&str> = Vec::new();
+    let mut start: usize = 0;
+    let mut in_movetext: bool = false;
+    let mut offset: usize = 0;
+
+    for line in pgn.split_inclusive('\n')
+    {
+        let trimmed: &str = line.trim();
+        if trimmed.starts_with('[') && in_movetext
+        {
+            games.push(&pgn[start..offset]);
+            start = offset;
+            in_movetext = false;
+        }
+        else if !trimmed.is_empty() && !trimmed.starts_with('[')
+        {
+            in_movetext = true;
+        }
+        offset += line.len();
+    }
+
+    games.push(&pgn[start..]);
+    games.retain(|game| !game.trim().is_empty());
+    return games;
+}
+
+// Reads the games of a PGN file one at a time, split as in `split_games`,
+// so a large file is never read whole
+#[cfg(feature = "std")]
+pub struct PgnReader<R: std::io::BufRead>
+{
+    lines: std::io::Lines<R>,
+    // The first line of the next game, read with the end of the last one
+    next: Option<String>,
+}
+
+#[cfg(feature = "std")]
+impl<R: std::io::BufRead> PgnReader<R>
+{
+    pub fn new(reader: R) -> PgnReader<R>
+    {
+        return PgnReader { lines: reader.lines(), next: None };
+    }
+}
+
+#[cfg(feature = "std")]
+impl<R: std::io::BufRead> Iterator for PgnReader<R>
+{
+    type Item = std::io::Result<String>;
+
+    fn next(&mut self) -> Option<std::io::Result<String>>
+    {
+        let mut game: String = self.next.take().unwrap_or_default();
+        let mut in_movetext: bool = false;
+
+        for line in self.lines.by_ref()
+        {
+            let line: String = match line
+            {
+                Ok(line) => line,
+                Err(error) => return Some(Err(error)),
+            };
+
+            let trimmed: &str = line.trim();
+            if trimmed.starts_with('[') && in_movetext
+            {
+                self.next = Some(format!("{}\n", line));
+                return Some(Ok(game));
+            }
+            else if !trimmed.is_empty() && !trimmed.starts_with('[')
+            {
+                in_movetext = true;
+            }
+
+            game.push_str(&line);
+            game.push('\n');
+        }
+
+        return (!game.trim().is_empty()).then_some(Ok(game));
+    }
+}
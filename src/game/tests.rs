@@ -0,0 +1,158 @@
+use super::*;
+
+#[test]
+fn test_game_moves_and_undo()
+{
+    let mut game: Game = Game::new();
+    assert!(game.play_san("e4"));
+    assert!(game.play_uci("e7e5"));
+    assert!(!game.play_san("e4"));
+    assert!(game.get_san() == ["e4", "e5"]);
+
+    assert!(game.undo());
+    assert!(game.get_san() == ["e4"]);
+    assert!(!game.get_board().white_turn());
+    assert!(game.undo());
+    assert!(!game.undo());
+    assert_eq!(game.get_board(), &ChessBoard::init_position());
+}
+
+#[test]
+fn test_game_end()
+{
+    let mut game: Game = Game::new();
+    for san in ["f3", "e5", "g4", "Qh4#"]
+    {
+        assert!(game.play_san(san));
+    }
+    assert!(!game.is_on());
+    assert!(game.result() == "0-1");
+
+    let mut game: Game = Game::new();
+    game.resign(true);
+    assert!(game.get_end() == Some(GameEnd::Resigned(true)));
+    assert!(game.result() == "0-1");
+    assert!(!game.play_san("e4"));
+
+    // Knights going back and forth
+    let mut game: Game = Game::new();
+    for san in ["Nf3", "Nf6", "Ng1", "Ng8", "Nf3", "Nf6", "Ng1", "Ng8"]
+    {
+        assert!(game.play_san(san));
+    }
+    assert!(game.get_end() == Some(GameEnd::Repetition));
+    assert!(game.result() == "1/2-1/2");
+}
+
+#[test]
+fn test_game_pgn()
+{
+    let mut game: Game = Game::new();
+    game.set_tag("White", "Fischer");
+    for san in ["e4", "e5", "Nf3"]
+    {
+        game.play_san(san);
+    }
+
+    let pgn: String = game.to_pgn();
+    assert!(pgn.starts_with("[Event \"?\"]\n"));
+    assert!(pgn.contains("[White \"Fischer\"]\n"));
+    assert!(pgn.contains("[Result \"*\"]\n"));
+    assert!(!pgn.contains("[FEN"));
+    assert!(pgn.ends_with("\n\n1. e4 e5 2. Nf3 *\n"));
+
+    // Games starting with black to move, in a variant
+    let board: ChessBoard = ChessBoard::from_fen_variant("4k3/8/8/8/8/8/8/4K3 b - - 0 7", Arc::new(variant::KingOfTheHill)).unwrap();
+    let mut game: Game = Game::from_board(board);
+    game.play_san("Kd7");
+    game.play_san("Kd2");
+    let pgn: String = game.to_pgn();
+    assert!(pgn.contains("[Variant \"King of the Hill\"]\n"));
+    assert!(pgn.contains("[SetUp \"1\"]\n[FEN \"4k3/8/8/8/8/8/8/4K3 b - - 0 7\"]\n"));
+    assert!(pgn.ends_with("\n\n7... Kd7 8. Kd2 *\n"));
+
+    // NAGs and comments after the moves
+    assert!(game.add_nag(0, 2) && game.add_nag(0, 2) && game.set_comment(0, "The {king} walks"));
+    assert!(!game.set_comment(2, "none") && game.get_nags(0) == [2]);
+    assert!(game.to_pgn().ends_with("\n\n7... Kd7 $2 {The {king) walks} 8. Kd2 *\n"));
+    game.set_comment(1, "back");
+    game.play_san("Ke7");
+    assert!(game.to_pgn().ends_with("8. Kd2 {back} 8... Ke7 *\n"));
+    game.undo();
+    game.undo();
+    assert!(game.get_comment(1).is_none() && game.get_comment(0) == Some("The {king) walks"));
+}
+
+#[test]
+fn test_game_from_pgn()
+{
+    let pgn: &str = "[Event \"Club \\\"open\\\"\"]\n[White \"Fischer\"]\n[Result \"0-1\"]\n\n\
+        1. e4 {best by test} e5 2.Nf3 $1 (2. f4 exf4 (2... d5)) Nc6?! ; a comment\n\
+        3. Bb5 a6 4.0-0 0-1\n";
+    let game: Game = Game::from_pgn(pgn).unwrap();
+    assert_eq!(game.get_san(), ["e4", "e5", "Nf3", "Nc6", "Bb5", "a6", "O-O"]);
+    assert_eq!(game.get_tag("Event"), Some("Club \"open\""));
+    assert_eq!(game.get_end(), Some(GameEnd::Resigned(true)));
+    assert_eq!(game.result(), "0-1");
+
+    // The PGN of a game reads back as the same game
+    let mut game: Game = Game::from_board(ChessBoard::init_variant(variant::from_name("atomic").unwrap()));
+    for san in ["e4", "d5", "exd5"]
+    {
+        assert!(game.play_san(san));
+    }
+    game.draw();
+    let copy: Game = Game::from_pgn(&game.to_pgn()).unwrap();
+    assert_eq!(copy.to_pgn(), game.to_pgn());
+    assert_eq!(copy.get_board(), game.get_board());
+
+    // Taking en passant
+    let game: Game = Game::from_pgn("1. e4 a6 2. e5 d5 3. exd6 cxd6 *").unwrap();
+    assert_eq!(game.get_san(), ["e4", "a6", "e5", "d5", "exd6", "cxd6"]);
+    assert_eq!(game.get_board().to_fen(), "rnbqkbnr/1p2pppp/p2p4/8/8/8/PPPP1PPP/RNBQKBNR w KQkq - 0 4");
+
+    assert_eq!(Game::from_pgn("1. e4 e5 2. Ke3").err(), Some(PgnError::IllegalMove(String::from("Ke3"))));
+    assert_eq!(Game::from_pgn("1. e4 { open").err(), Some(PgnError::Unterminated));
+    assert_eq!(Game::from_pgn("1. e4 (1. d4").err(), Some(PgnError::Unterminated));
+    assert!(matches!(Game::from_pgn("[FEN \"8/8 w\"]\n\n*"), Err(PgnError::InvalidFen(_))));
+    assert!(matches!(Game::from_pgn("[Variant \"shogi\"]\n\n*"), Err(PgnError::UnknownVariant(_))));
+}
+
+#[test]
+#[cfg(feature = "std")]
+fn test_game_events()
+{
+    let mut game: Game = Game::new();
+    let events: std::sync::mpsc::Receiver<GameEvent> = game.events();
+    let moves: Arc<std::sync::Mutex<usize>> = Arc::new(std::sync::Mutex::new(0));
+    let counter: Arc<std::sync::Mutex<usize>> = moves.clone();
+    let observer: ObserverId = game.subscribe(move |event| if let GameEvent::Moved { .. } = event { *counter.lock().unwrap() += 1; });
+
+    for san in ["e4", "d5", "exd5"]
+    {
+        assert!(game.play_san(san));
+    }
+    assert!(!game.play_san("exd5"));
+    assert!(game.undo());
+    let got: Vec<GameEvent> = events.try_iter().collect();
+    assert!(got.len() == 5);
+    assert!(got[2] == GameEvent::Moved { mv: ChessMove::drag(0o34, 0o43), san: "exd5".to_string(), white: true });
+    assert!(got[3] == GameEvent::Captured { block: 0o43, piece: ChessPiece::BPawn });
+    assert!(got[4] == GameEvent::Undone { mv: ChessMove::drag(0o34, 0o43) });
+
+    assert!(game.unsubscribe(observer));
+    assert!(!game.unsubscribe(observer));
+    game.load(ChessBoard::from_fen("4k3/1P6/8/8/8/8/8/4K2R w K - 0 1").unwrap());
+    for san in ["O-O", "Kd8", "b8=Q+"]
+    {
+        assert!(game.play_san(san));
+    }
+    game.resign(false);
+    let got: Vec<GameEvent> = events.try_iter().collect();
+    assert!(got[0] == GameEvent::Loaded);
+    assert!(got[2] == GameEvent::Castled { white: true, king_side: true });
+    assert!(got[5] == GameEvent::Promoted { block: 0o71, piece: ChessPiece::WQueen });
+    assert!(got[6] == GameEvent::Check { white: false });
+    assert!(got[7] == GameEvent::Ended { winner: Some(true), end: Some(GameEnd::Resigned(false)), state: ChessState::On });
+    assert!(got.len() == 8 && *moves.lock().unwrap() == 3);
+}
@@ -1,6 +1,19 @@
-use std::collections::HashMap;
+// Explicit `return` statements are the style of this crate
+#![allow(clippy::needless_return)]
+// The rules only need an allocator, the `std` feature adds the engine, the
+// C bindings and the terminal binary
+#![cfg_attr(not(feature = "std"), no_std)]
 
-#[derive(Clone, Copy)]
+extern crate alloc;
+
+use alloc::collections::BTreeMap;
+use alloc::string::{String, ToString};
+use alloc::sync::Arc;
+use alloc::vec::Vec;
+use alloc::{format, vec};
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub enum ChessPieceType
 {
     King,
@@ -8,7 +21,27 @@ pub enum ChessPieceType
     Rook,
     Bishop,
     Knight,
-    Pawn
+    Pawn,
+
+    // Fairy pieces, their movement is described in Betza notation,
+    // see `betza`. This is the whole set, a board can't add piece types
+    Archbishop,
+    Chancellor,
+    Amazon,
+    Camel,
+    Zebra,
+    Grasshopper,
+}
+
+impl ChessPieceType
+{
+    // Fairy piece types don't have any built in rules
+    pub fn is_fairy(self) -> bool
+    {
+        return !matches!(self, ChessPieceType::King | ChessPieceType::Queen |
+            ChessPieceType::Rook | ChessPieceType::Bishop | ChessPieceType::Knight |
+            ChessPieceType::Pawn);
+    }
 }
 
 // Map every piece as an u8 for a more
@@ -31,18 +64,42 @@ pub enum ChessPiece
     WBishop =   10,
     WKnight =   11,
     WPawn   =   12,
+
+    // Fairy pieces are mapped after the default ones
+    BArchbishop     =   13,
+    BChancellor     =   14,
+    BAmazon         =   15,
+    BCamel          =   16,
+    BZebra          =   17,
+    BGrasshopper    =   18,
+
+    WArchbishop     =   19,
+    WChancellor     =   20,
+    WAmazon         =   21,
+    WCamel          =   22,
+    WZebra          =   23,
+    WGrasshopper    =   24,
 }
 
+// Number of `ChessPiece` values, `Empty` included
+const PIECE_KINDS: usize = 25;
+
 impl ChessPiece
 {
     pub fn is_white(self) -> bool
     {
-        return ((self as u8) > 6u8) && ((self as u8) < 13u8);
+        let _default: bool = ((self as u8) > 6u8) && ((self as u8) < 13u8);
+        let _fairy: bool = ((self as u8) > 18u8) && ((self as u8) < 25u8);
+
+        return _default || _fairy;
     }
 
     pub fn is_black(self) -> bool
     {
-        return ((self as u8) > 0u8) && ((self as u8) < 7u8);
+        let _default: bool = ((self as u8) > 0u8) && ((self as u8) < 7u8);
+        let _fairy: bool = ((self as u8) > 12u8) && ((self as u8) < 19u8);
+
+        return _default || _fairy;
     }
 
     pub fn is_empty(self) -> bool
@@ -58,6 +115,61 @@ impl ChessPiece
 
         return case1 || case2 || case3;
     }
+
+    // Get the piece of the given type and color
+    pub fn from_type(_type: ChessPieceType, white: bool) -> ChessPiece
+    {
+        return match (_type, white)
+        {
+            (ChessPieceType::King, false) =>    ChessPiece::BKing,
+            (ChessPieceType::Queen, false) =>   ChessPiece::BQueen,
+            (ChessPieceType::Rook, false) =>    ChessPiece::BRook,
+            (ChessPieceType::Bishop, false) =>  ChessPiece::BBishop,
+            (ChessPieceType::Knight, false) =>  ChessPiece::BKnight,
+            (ChessPieceType::Pawn, false) =>    ChessPiece::BPawn,
+            (ChessPieceType::King, true) =>     ChessPiece::WKing,
+            (ChessPieceType::Queen, true) =>    ChessPiece::WQueen,
+            (ChessPieceType::Rook, true) =>     ChessPiece::WRook,
+            (ChessPieceType::Bishop, true) =>   ChessPiece::WBishop,
+            (ChessPieceType::Knight, true) =>   ChessPiece::WKnight,
+            (ChessPieceType::Pawn, true) =>     ChessPiece::WPawn,
+
+            (ChessPieceType::Archbishop, false) =>  ChessPiece::BArchbishop,
+            (ChessPieceType::Chancellor, false) =>  ChessPiece::BChancellor,
+            (ChessPieceType::Amazon, false) =>      ChessPiece::BAmazon,
+            (ChessPieceType::Camel, false) =>       ChessPiece::BCamel,
+            (ChessPieceType::Zebra, false) =>       ChessPiece::BZebra,
+            (ChessPieceType::Grasshopper, false) => ChessPiece::BGrasshopper,
+            (ChessPieceType::Archbishop, true) =>   ChessPiece::WArchbishop,
+            (ChessPieceType::Chancellor, true) =>   ChessPiece::WChancellor,
+            (ChessPieceType::Amazon, true) =>       ChessPiece::WAmazon,
+            (ChessPieceType::Camel, true) =>        ChessPiece::WCamel,
+            (ChessPieceType::Zebra, true) =>        ChessPiece::WZebra,
+            (ChessPieceType::Grasshopper, true) =>  ChessPiece::WGrasshopper,
+        };
+    }
+
+    // Get the type of the piece, `None` for an empty block
+    pub fn get_type(self) -> Option<ChessPieceType>
+    {
+        return match self
+        {
+            ChessPiece::Empty => None,
+            ChessPiece::BKing | ChessPiece::WKing =>        Some(ChessPieceType::King),
+            ChessPiece::BQueen | ChessPiece::WQueen =>      Some(ChessPieceType::Queen),
+            ChessPiece::BRook | ChessPiece::WRook =>        Some(ChessPieceType::Rook),
+            ChessPiece::BBishop | ChessPiece::WBishop =>    Some(ChessPieceType::Bishop),
+            ChessPiece::BKnight | ChessPiece::WKnight =>    Some(ChessPieceType::Knight),
+            ChessPiece::BPawn | ChessPiece::WPawn =>        Some(ChessPieceType::Pawn),
+
+            ChessPiece::BArchbishop | ChessPiece::WArchbishop =>    Some(ChessPieceType::Archbishop),
+            ChessPiece::BChancellor | ChessPiece::WChancellor =>    Some(ChessPieceType::Chancellor),
+            ChessPiece::BAmazon | ChessPiece::WAmazon =>            Some(ChessPieceType::Amazon),
+            ChessPiece::BCamel | ChessPiece::WCamel =>              Some(ChessPieceType::Camel),
+            ChessPiece::BZebra | ChessPiece::WZebra =>              Some(ChessPieceType::Zebra),
+            ChessPiece::BGrasshopper | ChessPiece::WGrasshopper =>  Some(ChessPieceType::Grasshopper),
+        };
+    }
 }
 
 #[repr(u8)]
@@ -69,43 +181,178 @@ pub enum ChessPathway
 }
 
 #[derive(Debug, PartialEq, Eq, Clone, Copy)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 #[repr(u8)]
 pub enum ChessState
 {
     On = 0,
     Stalemate = 1,
     Checkmate = 2,
-    Aborted = 3
+    Aborted = 3,
+    // The game was won or drawn by a rule of the variant, see `variant`
+    VariantWin = 4,
+    VariantDraw = 5,
+}
+
+// A single move. Castling is a drag of the king, either onto its
+// destination or onto the rook it castles with, see `ChessBoard::drag`
+#[derive(Debug, PartialEq, Eq, Clone, Copy, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum ChessMove
+{
+    // Moving the piece on `from` to `to`. `promotion` is the type a pawn
+    // turns into on the last rank, the default promotion if `None`
+    Drag { from: u8, to: u8, promotion: Option<ChessPieceType> },
+    // Dropping a piece from the pocket, see `variant::Crazyhouse`
+    Drop { piece: ChessPieceType, to: u8 },
+}
+
+impl ChessMove
+{
+    pub fn drag(from: u8, to: u8) -> ChessMove
+    {
+        return ChessMove::Drag { from, to, promotion: None };
+    }
+
+    // The block the piece ends up on
+    pub fn get_to(self) -> u8
+    {
+        return match self
+        {
+            ChessMove::Drag { to, .. } => to,
+            ChessMove::Drop { to, .. } => to,
+        };
+    }
+}
+
+// A list of at most `N` moves that lives on the stack, filled by
+// `ChessBoard::generate_moves` where allocating isn't an option
+#[derive(Debug, Clone, Copy)]
+pub struct MoveList<const N: usize = 256>
+{
+    moves: [ChessMove; N],
+    len: usize,
+}
+
+impl<const N: usize> MoveList<N>
+{
+    pub fn new() -> MoveList<N>
+    {
+        return MoveList { moves: [ChessMove::drag(0, 0); N], len: 0 };
+    }
+
+    // Adds the move, returns false if the list is full
+    pub fn push(&mut self, mv: ChessMove) -> bool
+    {
+        if self.len == N
+        {
+            return false;
+        }
+
+        self.moves[self.len] = mv;
+        self.len += 1;
+        return true;
+    }
+
+    pub fn clear(&mut self)
+    {
+        self.len = 0;
+    }
+
+    pub fn len(&self) -> usize
+    {
+        return self.len;
+    }
+
+    pub fn is_empty(&self) -> bool
+    {
+        return self.len == 0;
+    }
+
+    pub fn as_slice(&self) -> &[ChessMove]
+    {
+        return &self.moves[..self.len];
+    }
+}
+
+impl<const N: usize> Default for MoveList<N>
+{
+    fn default() -> Self
+    {
+        return Self::new();
+    }
 }
 
 #[derive(PartialEq, Clone, Copy)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "serde", serde(try_from = "serialize::ChessPosRecord"))]
 pub struct ChessPos
 {
     x: u8,
     y: u8,
+    // Dimensions of the board the position belongs to
+    w: u8,
+    h: u8,
 }
 
 impl ChessPos
 {
+    // The block in column `x` and row `y` of a board of `w` x `h` blocks,
+    // `None` if it's not on the board or the board has more blocks than a
+    // `u8` can number
+    pub fn checked(x: u8, y: u8, w: u8, h: u8) -> Option<ChessPos>
+    {
+        if x >= w || y >= h || (w as u16)*(h as u16) > 256
+        {
+            return None;
+        }
+
+        return Some(ChessPos{x, y, w, h});
+    }
+
     pub fn from(pos: u8, ignore_bounds: bool) -> ChessPos
+    {
+        return ChessPos::from_dim(pos, 8, 8, ignore_bounds);
+    }
+
+    // Same as `ChessPos::from` on a board of `w` x `h` blocks
+    pub fn from_dim(pos: u8, w: u8, h: u8, ignore_bounds: bool) -> ChessPos
     {
         if !ignore_bounds
         {
-            assert!(pos<64, "Position outside of bounds");
+            assert!((pos as u16) < (w as u16)*(h as u16), "Position outside of bounds");
         }
         
-        ChessPos{x: pos % 8, y: pos/8}
+        ChessPos{x: pos % w, y: pos/w, w, h}
     }
 
     pub fn raw(self) -> u8
     {
-        return self.y*8+self.x;
+        return self.y*self.w+self.x;
     }
 
     pub fn conv(x: u8, y: u8) -> u8
     {
-        assert!(x<8 && y<8, "Position outside of bounds");
-        return y*8+x;
+        return ChessPos::conv_dim(x, y, 8, 8);
+    }
+
+    // Same as `ChessPos::conv` on a board of `w` x `h` blocks
+    pub fn conv_dim(x: u8, y: u8, w: u8, h: u8) -> u8
+    {
+        assert!(x<w && y<h, "Position outside of bounds");
+        return y*w+x;
+    }
+
+    // Column of the position, 0 is the a-file
+    pub fn get_x(self) -> u8
+    {
+        return self.x;
+    }
+
+    // Row of the position, 0 is the first rank
+    pub fn get_y(self) -> u8
+    {
+        return self.y;
     }
 
     // This function is equivalent to `self ∈ (a,b)`
@@ -150,24 +397,96 @@ impl ChessPos
     }
 }
 
+#[derive(Clone)]
 pub struct ChessBoard
 {
     w_lock: bool,
     // Real men do 1d array and interpretate it as a 2d array
-    board: [ChessPiece; 64],
+    board: Vec<ChessPiece>,
+    // Number of files and ranks, 8 x 8 for default chess
+    _width: u8,
+    _height: u8,
 
     _w_king: u8,
     _b_king: u8,
 
     _default_promotion: ChessPieceType,
 
+    // How the fairy pieces move, see `betza`
+    _movements: BTreeMap<ChessPieceType, betza::PieceMovement>,
+
+    // Coordinates of the rooks that still have castling rights, in the
+    // order white king side, white queen side, black king side, black
+    // queen side. Storing the rook itself rather than a flag is what
+    // allows Chess960 setups where the rooks can start on any file
+    _castling: [Option<u8>; 4],
+    // The block a pawn passed over with the two step move just made, while
+    // it can be taken en passant
+    _en_passant: Option<u8>,
+    // Castling is entered as "king takes own rook" instead of moving the
+    // king two squares
+    _chess960: bool,
+
+    // Half moves since the last capture or pawn move and the number of
+    // the current full move, both kept for FEN
+    _halfmove_clock: u16,
+    _fullmove: u16,
+
+    // Rules on top of the default chess rules
+    _variant: Arc<dyn variant::Variant>,
+    // Number of checks given by white and black
+    _checks: [u8; 2],
+    // Captured pieces white and black can drop back on the board, for
+    // variants with pockets like Crazyhouse. Kept sorted by type
+    _pockets: [Vec<ChessPieceType>; 2],
+    // Blocks taken by pieces that were promoted from a pawn, they turn
+    // back into pawns when captured into a pocket
+    _promoted: Vec<bool>,
+
     // Current game state
     _state: ChessState,    
+    // The color that won the game, true for white
+    _winner: Option<bool>,
     // Having a count on the chess pieces without having to count
-    // 64 elements every time
-    _piece_count: HashMap<ChessPiece, u8>,
+    // 64 elements every time, indexed by the `ChessPiece` value
+    _piece_count: [u8; PIECE_KINDS],
+    // If the color to move can capture anything, with the hash of the
+    // position it was found for. Kept by `Antichess`, where it's asked for
+    // every move that doesn't capture
+    _can_capture: Option<(u64, bool)>,
 }
 
 mod chess_logic;
+pub mod betza;
+pub mod fen;
+pub mod variant;
+pub mod notation;
+pub mod game;
+#[cfg(feature = "std")]
+pub mod engine;
+#[cfg(feature = "std")]
+pub mod match_runner;
+pub mod tree;
+pub mod tournament;
+pub mod puzzle;
+pub mod epd;
+pub mod eco;
+#[cfg(feature = "std")]
+pub mod ratings;
+#[cfg(feature = "std")]
+pub mod explorer;
+#[cfg(feature = "std")]
+pub mod database;
+#[cfg(feature = "std")]
+pub mod review;
+#[cfg(feature = "std")]
+pub mod syzygy;
+mod display;
+#[cfg(feature = "serde")]
+mod serialize;
+#[cfg(feature = "wasm")]
+pub mod wasm;
+#[cfg(feature = "server")]
+pub mod server;
 #[cfg(test)]
 mod pub_tests;
\ No newline at end of file
@@ -0,0 +1,425 @@
+// Plays games between two players, engines over UCI or the built in one,
+// to find out which one is stronger. The colors alternate and every
+// opening of the book is played twice, once with each color, so neither
+// player gets the better side of an opening. Games can be cut short when
+// both players agree on the score, or when the tablebase knows the result.
+use super::*;
+use engine::{Engine, SearchLimit, SearchResult};
+use fen::FenError;
+use game::{Game, PgnError};
+use std::time::{Duration, Instant};
+
+mod stats;
+mod uci;
+#[cfg(test)]
+mod tests;
+
+pub use stats::{elo_difference, MatchScore, Sprt, SprtStatus};
+pub use uci::UciPlayer;
+
+// Anything that can choose moves in a match
+pub trait Player
+{
+    fn name(&self) -> &str;
+
+    // Called before every game
+    fn new_game(&mut self) {}
+
+    // The move for the color to move in the game, and the score in
+    // centipawns from its side if the player knows one. `clocks` is the
+    // time left for white and black. `None` if the player has no move,
+    // which loses the game
+    fn go(&mut self, game: &Game, clocks: [Duration; 2], increment: Duration) -> Option<(ChessMove, Option<i32>)>;
+}
+
+// The built in engine
+pub struct EnginePlayer
+{
+    name: String,
+    engine: Engine,
+    // `None` to use the clock
+    limit: Option<SearchLimit>,
+}
+
+impl EnginePlayer
+{
+    pub fn new(name: &str, limit: Option<SearchLimit>) -> EnginePlayer
+    {
+        return EnginePlayer { name: name.to_string(), engine: Engine::new(), limit };
+    }
+}
+
+impl Player for EnginePlayer
+{
+    fn name(&self) -> &str
+    {
+        return &self.name;
+    }
+
+    fn go(&mut self, game: &Game, clocks: [Duration; 2], increment: Duration) -> Option<(ChessMove, Option<i32>)>
+    {
+        let left: Duration = clocks[if game.get_board().white_turn() { 0 } else { 1 }];
+        let limit: SearchLimit = self.limit.unwrap_or(SearchLimit::from_clock(left, increment));
+
+        let result: SearchResult = self.engine.search(game.get_board(), limit);
+        return result.get_move().map(|mv| (mv, Some(result.get_score())));
+    }
+}
+
+// The time for the whole game and the time added after every move
+#[derive(Debug, PartialEq, Eq, Clone, Copy)]
+pub struct TimeControl
+{
+    base: Duration,
+    increment: Duration,
+}
+
+impl TimeControl
+{
+    pub fn new(base: Duration, increment: Duration) -> TimeControl
+    {
+        return TimeControl { base, increment };
+    }
+
+    // Seconds for the game and seconds of increment, like `10+0.1` or `60`
+    pub fn parse(value: &str) -> Option<TimeControl>
+    {
+        let (base, increment) = value.split_once('+').unwrap_or((value, "0"));
+        let base: f64 = base.parse().ok()?;
+        let increment: f64 = increment.parse().ok()?;
+        if !(base > 0.0 && increment >= 0.0 && base.is_finite() && increment.is_finite())
+        {
+            return None;
+        }
+
+        return Some(TimeControl::new(Duration::from_secs_f64(base), Duration::from_secs_f64(increment)));
+    }
+
+    pub fn get_base(&self) -> Duration
+    {
+        return self.base;
+    }
+
+    pub fn get_increment(&self) -> Duration
+    {
+        return self.increment;
+    }
+}
+
+// Win, draw or loss for the color to move
+#[derive(Debug, PartialEq, Eq, Clone, Copy)]
+pub enum Wdl
+{
+    Win,
+    Draw,
+    Loss,
+}
+
+// Knows the result of some positions with perfect play
+pub trait Tablebase
+{
+    // `None` if the position isn't in the tablebase
+    fn probe(&self, board: &ChessBoard) -> Option<Wdl>;
+}
+
+// The endings of standard chess with a king against a king and at most one
+// other piece: the minor pieces can't mate, and the queen and the rook win
+// unless the king can take them right away. A pawn isn't always a win, so
+// those are left out
+pub struct MaterialTablebase;
+
+impl Tablebase for MaterialTablebase
+{
+    fn probe(&self, board: &ChessBoard) -> Option<Wdl>
+    {
+        if board.get_variant().name() != "Standard" || board.get_state() != ChessState::On
+        {
+            return None;
+        }
+
+        let mut extra: Option<(u8, ChessPiece)> = None;
+        for (coords, piece) in board.get_squares().iter().enumerate()
+        {
+            match piece.get_type()
+            {
+                None | Some(ChessPieceType::King) => {},
+                Some(_) if extra.is_some() => return None,
+                Some(_) => extra = Some((coords as u8, *piece)),
+            };
+        }
+
+        let (coords, piece) = match extra
+        {
+            Some(extra) => extra,
+            None => return Some(Wdl::Draw),
+        };
+
+        return match piece.get_type().unwrap()
+        {
+            ChessPieceType::Knight | ChessPieceType::Bishop => Some(Wdl::Draw),
+            ChessPieceType::Queen | ChessPieceType::Rook => {
+                let stronger_to_move: bool = piece.is_white() == board.white_turn();
+                if stronger_to_move
+                {
+                    Some(Wdl::Win)
+                }
+                // Unless the king takes it, the lone king loses
+                else if !board.is_attacked(coords, board.white_turn())
+                {
+                    Some(Wdl::Loss)
+                }
+                else
+                {
+                    None
+                }
+            },
+            _ => None,
+        };
+    }
+}
+
+// When the runner ends a game before it's over on the board
+pub struct Adjudication
+{
+    // A player loses once its score was this many centipawns down for this
+    // many of its moves in a row
+    resign: Option<(i32, u16)>,
+    // A draw once both scores were within this many centipawns for this
+    // many moves of each player in a row, not before the given move number
+    draw: Option<(i32, u16, u16)>,
+    tablebase: Option<Box<dyn Tablebase>>,
+}
+
+impl Adjudication
+{
+    // Games are played to their end
+    pub fn new() -> Adjudication
+    {
+        return Adjudication { resign: None, draw: None, tablebase: None };
+    }
+
+    pub fn set_resign(&mut self, score: i32, moves: u16)
+    {
+        self.resign = Some((score, moves));
+    }
+
+    pub fn set_draw(&mut self, score: i32, moves: u16, after_move: u16)
+    {
+        self.draw = Some((score, moves, after_move));
+    }
+
+    pub fn set_tablebase(&mut self, tablebase: Box<dyn Tablebase>)
+    {
+        self.tablebase = Some(tablebase);
+    }
+}
+
+impl Default for Adjudication
+{
+    fn default() -> Self
+    {
+        return Self::new();
+    }
+}
+
+// Reads an opening book in EPD, one position per line. Only the first four
+// fields are read, the operations after them are ignored
+pub fn read_epd_book(epd: &str) -> Result<Vec<ChessBoard>, FenError>
+{
+    let mut openings: Vec<ChessBoard> = Vec::new();
+    for line in epd.lines()
+    {
+        let fields: Vec<&str> = line.split_whitespace().take(4).collect();
+        if fields.is_empty() || fields[0].starts_with('#')
+        {
+            continue;
+        }
+
+        openings.push(ChessBoard::from_fen(&format!("{} 0 1", fields.join(" ")))?);
+    }
+
+    return Ok(openings);
+}
+
+// Reads an opening book in PGN, the openings are the positions at the end
+// of the games
+pub fn read_pgn_book(pgn: &str) -> Result<Vec<ChessBoard>, PgnError>
+{
+    return game::split_games(pgn).into_iter().map(|text| Ok(Game::from_pgn(text)?.get_board().clone())).collect();
+}
+
+pub struct Match
+{
+    players: [Box<dyn Player>; 2],
+    time: TimeControl,
+    adjudication: Adjudication,
+    // The start position alone if there's no book
+    openings: Vec<ChessBoard>,
+    games: Vec<Game>,
+    // From the side of the first player
+    score: MatchScore,
+}
+
+impl Match
+{
+    pub fn new(first: Box<dyn Player>, second: Box<dyn Player>, time: TimeControl) -> Match
+    {
+        return Match {
+            players: [first, second],
+            time,
+            adjudication: Adjudication::new(),
+            openings: vec![ChessBoard::init_position()],
+            games: Vec::new(),
+            score: MatchScore::new(),
+        };
+    }
+
+    pub fn set_adjudication(&mut self, adjudication: Adjudication)
+    {
+        self.adjudication = adjudication;
+    }
+
+    // The openings are played in order, each one twice
+    pub fn set_openings(&mut self, openings: Vec<ChessBoard>)
+    {
+        if !openings.is_empty()
+        {
+            self.openings = openings;
+        }
+    }
+
+    pub fn get_games(&self) -> &[Game]
+    {
+        return &self.games;
+    }
+
+    // Wins, draws and losses of the first player
+    pub fn get_score(&self) -> MatchScore
+    {
+        return self.score;
+    }
+
+    pub fn get_names(&self) -> [&str; 2]
+    {
+        return [self.players[0].name(), self.players[1].name()];
+    }
+
+    // Plays the next game and returns it. The first player has white in the
+    // first game of every opening
+    pub fn play_game(&mut self) -> &Game
+    {
+        let round: usize = self.games.len();
+        let first_white: bool = round.is_multiple_of(2);
+        let white: usize = if first_white { 0 } else { 1 };
+        let opening: ChessBoard = self.openings[(round/2) % self.openings.len()].clone();
+
+        let mut game: Game = Game::from_board(opening);
+        game.set_tag("Event", "Engine match");
+        game.set_tag("Round", &(round + 1).to_string());
+        game.set_tag("White", self.players[white].name());
+        game.set_tag("Black", self.players[1 - white].name());
+
+        for player in self.players.iter_mut()
+        {
+            player.new_game();
+        }
+
+        let mut clocks: [Duration; 2] = [self.time.base; 2];
+        // Moves in a row under the resign score for each color, and half
+        // moves in a row within the draw score
+        let mut resign_counts: [u16; 2] = [0; 2];
+        let mut draw_count: u16 = 0;
+
+        while game.is_on()
+        {
+            let white_turn: bool = game.get_board().white_turn();
+            let turn: usize = if white_turn { 0 } else { 1 };
+
+            if let Some(wdl) = self.adjudication.tablebase.as_ref().and_then(|tb| tb.probe(game.get_board()))
+            {
+                match wdl
+                {
+                    Wdl::Win => game.resign(!white_turn),
+                    Wdl::Loss => game.resign(white_turn),
+                    Wdl::Draw => game.draw(),
+                };
+                game.set_tag("Termination", "adjudication");
+                break;
+            }
+
+            let player: &mut Box<dyn Player> = &mut self.players[if turn == 0 { white } else { 1 - white }];
+            let started: Instant = Instant::now();
+            let answer: Option<(ChessMove, Option<i32>)> = player.go(&game, clocks, self.time.increment);
+            let used: Duration = started.elapsed();
+
+            if used >= clocks[turn]
+            {
+                game.timeout(white_turn);
+                game.set_tag("Termination", "time forfeit");
+                break;
+            }
+            clocks[turn] = clocks[turn] - used + self.time.increment;
+
+            let score: Option<i32> = match answer
+            {
+                Some((mv, score)) if game.play(mv) => score,
+                _ => {
+                    game.resign(white_turn);
+                    game.set_tag("Termination", "rules infraction");
+                    break;
+                },
+            };
+
+            if self.__adjudicate(&mut game, score, turn, &mut resign_counts, &mut draw_count)
+            {
+                game.set_tag("Termination", "adjudication");
+            }
+        }
+
+        self.score.add(game.get_winner().map(|w| w == first_white));
+        self.games.push(game);
+        return self.games.last().unwrap();
+    }
+
+    // Ends the game if the scores allow it, after the player of `turn`
+    // moved with the given score. Returns true if it did
+    fn __adjudicate(&self, game: &mut Game, score: Option<i32>, turn: usize, resign_counts: &mut [u16; 2], draw_count: &mut u16) -> bool
+    {
+        if !game.is_on()
+        {
+            return false;
+        }
+
+        if let Some((resign_score, moves)) = self.adjudication.resign
+        {
+            resign_counts[turn] = match score
+            {
+                Some(score) if score <= -resign_score => resign_counts[turn] + 1,
+                _ => 0,
+            };
+
+            if resign_counts[turn] >= moves
+            {
+                game.resign(turn == 0);
+                return true;
+            }
+        }
+
+        if let Some((draw_score, moves, after_move)) = self.adjudication.draw
+        {
+            *draw_count = match score
+            {
+                Some(score) if score.abs() <= draw_score && game.get_board().fullmove() > after_move => *draw_count + 1,
+                _ => 0,
+            };
+
+            if *draw_count >= 2*moves
+            {
+                game.draw();
+                return true;
+            }
+        }
+
+        return false;
+    }
+}
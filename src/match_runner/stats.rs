@@ -0,0 +1,175 @@
+// The Elo difference a match result gives and the sequential probability
+// ratio test, which stops a match as soon as it's clear enough whether a
+// change gains at least `elo1` or at most `elo0`
+
+#[derive(Debug, PartialEq, Eq, Clone, Copy, Default)]
+pub struct MatchScore
+{
+    wins: u32,
+    draws: u32,
+    losses: u32,
+}
+
+impl MatchScore
+{
+    pub fn new() -> MatchScore
+    {
+        return MatchScore { wins: 0, draws: 0, losses: 0 };
+    }
+
+    pub fn from_counts(wins: u32, draws: u32, losses: u32) -> MatchScore
+    {
+        return MatchScore { wins, draws, losses };
+    }
+
+    // Adds a game, `Some(true)` if it was won and `None` for a draw
+    pub fn add(&mut self, won: Option<bool>)
+    {
+        match won
+        {
+            Some(true) => self.wins += 1,
+            Some(false) => self.losses += 1,
+            None => self.draws += 1,
+        };
+    }
+
+    pub fn get_wins(&self) -> u32
+    {
+        return self.wins;
+    }
+
+    pub fn get_draws(&self) -> u32
+    {
+        return self.draws;
+    }
+
+    pub fn get_losses(&self) -> u32
+    {
+        return self.losses;
+    }
+
+    pub fn games(&self) -> u32
+    {
+        return self.wins + self.draws + self.losses;
+    }
+
+    // The points per game, from 0 to 1
+    pub fn ratio(&self) -> f64
+    {
+        if self.games() == 0
+        {
+            return 0.5;
+        }
+
+        return (self.wins as f64 + self.draws as f64 / 2.0) / self.games() as f64;
+    }
+
+    // The variance of the points of a game
+    fn __variance(&self) -> f64
+    {
+        let n: f64 = self.games() as f64;
+        let s: f64 = self.ratio();
+        return (self.wins as f64 * (1.0 - s).powi(2) + self.draws as f64 * (0.5 - s).powi(2) + self.losses as f64 * s.powi(2)) / n;
+    }
+}
+
+// The Elo difference of the expected points per game
+fn __elo(score: f64) -> f64
+{
+    return 400.0 * (score / (1.0 - score)).log10();
+}
+
+// The expected points per game for the Elo difference
+fn __score(elo: f64) -> f64
+{
+    return 1.0 / (1.0 + 10f64.powf(-elo/400.0));
+}
+
+// The Elo difference of the first player and the margin of its 95%
+// confidence interval. `None` before a game was played or when all of
+// them were won or lost, as the difference has no bound then
+pub fn elo_difference(score: &MatchScore) -> Option<(f64, f64)>
+{
+    let s: f64 = score.ratio();
+    if score.games() == 0 || s <= 0.0 || s >= 1.0
+    {
+        return None;
+    }
+
+    let deviation: f64 = (score.__variance() / score.games() as f64).sqrt();
+    let low: f64 = s - 1.96*deviation;
+    let high: f64 = s + 1.96*deviation;
+    let margin: f64 = if low <= 0.0 || high >= 1.0 { f64::INFINITY } else { (__elo(high) - __elo(low)) / 2.0 };
+
+    return Some((__elo(s), margin));
+}
+
+#[derive(Debug, PartialEq, Eq, Clone, Copy)]
+pub enum SprtStatus
+{
+    Continue,
+    // The change gains at most `elo0`
+    AcceptH0,
+    // The change gains at least `elo1`
+    AcceptH1,
+}
+
+#[derive(Debug, PartialEq, Clone, Copy)]
+pub struct Sprt
+{
+    elo0: f64,
+    elo1: f64,
+    // The chances of accepting H1 when H0 holds and the other way around
+    alpha: f64,
+    beta: f64,
+}
+
+impl Sprt
+{
+    pub fn new(elo0: f64, elo1: f64, alpha: f64, beta: f64) -> Sprt
+    {
+        return Sprt { elo0, elo1, alpha, beta };
+    }
+
+    // The log likelihood ratio of the result, from the normal
+    // approximation of the points per game
+    pub fn llr(&self, score: &MatchScore) -> f64
+    {
+        if score.games() == 0
+        {
+            return 0.0;
+        }
+
+        let variance: f64 = score.__variance();
+        if variance <= 0.0
+        {
+            return 0.0;
+        }
+
+        let s0: f64 = __score(self.elo0);
+        let s1: f64 = __score(self.elo1);
+        return score.games() as f64 * (s1 - s0) * (2.0*score.ratio() - s0 - s1) / (2.0*variance);
+    }
+
+    // The LLR under which H0 is accepted and over which H1 is
+    pub fn bounds(&self) -> (f64, f64)
+    {
+        return ((self.beta / (1.0 - self.alpha)).ln(), ((1.0 - self.beta) / self.alpha).ln());
+    }
+
+    pub fn status(&self, score: &MatchScore) -> SprtStatus
+    {
+        let llr: f64 = self.llr(score);
+        let (lower, upper) = self.bounds();
+
+        if llr <= lower
+        {
+            return SprtStatus::AcceptH0;
+        }
+        if llr >= upper
+        {
+            return SprtStatus::AcceptH1;
+        }
+        return SprtStatus::Continue;
+    }
+}
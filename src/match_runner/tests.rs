@@ -0,0 +1,138 @@
+use super::*;
+
+// Plays the first legal move and always gives the same score
+struct FixedPlayer(i32);
+
+impl Player for FixedPlayer
+{
+    fn name(&self) -> &str
+    {
+        return "fixed";
+    }
+
+    fn go(&mut self, game: &Game, _clocks: [Duration; 2], _increment: Duration) -> Option<(ChessMove, Option<i32>)>
+    {
+        return game.get_board().clone().legal_moves().first().map(|mv| (*mv, Some(self.0)));
+    }
+}
+
+#[test]
+fn test_match_stats()
+{
+    let even: MatchScore = MatchScore::from_counts(10, 20, 10);
+    let (elo, margin) = elo_difference(&even).unwrap();
+    assert!(elo.abs() < 1e-9 && margin > 0.0);
+
+    // 75% of the points is about 191 Elo
+    let (elo, _) = elo_difference(&MatchScore::from_counts(50, 50, 0)).unwrap();
+    assert!((elo - 190.85).abs() < 0.01);
+    assert!(elo_difference(&MatchScore::from_counts(3, 0, 0)).is_none());
+
+    let sprt: Sprt = Sprt::new(0.0, 5.0, 0.05, 0.05);
+    let (lower, upper) = sprt.bounds();
+    assert!((lower + 2.944).abs() < 0.001 && (upper - 2.944).abs() < 0.001);
+    assert!(sprt.status(&even) == SprtStatus::Continue);
+    assert!(sprt.status(&MatchScore::from_counts(600, 800, 400)) == SprtStatus::AcceptH1);
+    assert!(sprt.status(&MatchScore::from_counts(400, 800, 600)) == SprtStatus::AcceptH0);
+}
+
+#[test]
+fn test_match_books()
+{
+    let epd: &str = "# two openings\n\
+        rnbqkbnr/pppppppp/8/8/4P3/8/PPPP1PPP/RNBQKBNR b KQkq - id \"e4\";\n\
+        \n\
+        rnbqkbnr/pppppppp/8/8/3P4/8/PPP1PPPP/RNBQKBNR b KQkq -\n";
+    let openings: Vec<ChessBoard> = read_epd_book(epd).unwrap();
+    assert!(openings.len() == 2 && !openings[0].white_turn());
+    assert!(read_epd_book("not a fen").is_err());
+
+    let pgn: &str = "[Event \"a\"]\n\n1. e4 e5 *\n\n[Event \"b\"]\n\n1. d4 d5 2. c4 *\n";
+    let openings: Vec<ChessBoard> = read_pgn_book(pgn).unwrap();
+    assert!(openings.len() == 2);
+    assert!(openings[1].to_fen() == "rnbqkbnr/ppp1pppp/8/3p4/2PP4/8/PP2PPPP/RNBQKBNR b KQkq c3 0 2"
+        || openings[1].to_fen() == "rnbqkbnr/ppp1pppp/8/3p4/2PP4/8/PP2PPPP/RNBQKBNR b KQkq - 0 2");
+}
+
+#[test]
+fn test_match_games()
+{
+    let time: TimeControl = TimeControl::parse("10+0.1").unwrap();
+    assert!(time.get_base() == Duration::from_secs(10) && time.get_increment() == Duration::from_millis(100));
+    assert!(TimeControl::parse("0").is_none());
+
+    let first: Box<dyn Player> = Box::new(EnginePlayer::new("depth 2", Some(SearchLimit::Depth(2))));
+    let second: Box<dyn Player> = Box::new(EnginePlayer::new("depth 1", Some(SearchLimit::Depth(1))));
+    let mut runner: Match = Match::new(first, second, time);
+
+    let mut adjudication: Adjudication = Adjudication::new();
+    adjudication.set_resign(500, 2);
+    adjudication.set_draw(0, 1000, 0);
+    adjudication.set_tablebase(Box::new(MaterialTablebase));
+    runner.set_adjudication(adjudication);
+    // Black has only its king, so it resigns after its second move
+    runner.set_openings(read_epd_book("4k3/8/8/8/8/8/PPPPPPPP/RNBQKBNR w KQ -").unwrap());
+
+    // Colors swap with every game
+    assert!(runner.play_game().get_tag("White") == Some("depth 2"));
+    assert!(runner.play_game().get_tag("White") == Some("depth 1"));
+    assert!(runner.get_games().iter().all(|game| game.result() == "1-0" && game.get_moves().len() == 4));
+    assert!(runner.get_score() == MatchScore::from_counts(1, 0, 1));
+
+    // Both players think the game is even, so it's a draw after 3 moves each
+    let mut runner: Match = Match::new(Box::new(FixedPlayer(0)), Box::new(FixedPlayer(0)), time);
+    let mut adjudication: Adjudication = Adjudication::new();
+    adjudication.set_draw(10, 3, 0);
+    runner.set_adjudication(adjudication);
+    let game: &Game = runner.play_game();
+    assert!(game.get_moves().len() == 6 && game.result() == "1/2-1/2");
+    assert!(game.get_tag("Termination") == Some("adjudication"));
+}
+
+#[test]
+fn test_match_tablebase()
+{
+    let probe = |fen: &str| MaterialTablebase.probe(&ChessBoard::from_fen(fen).unwrap());
+    assert!(probe("8/8/4k3/8/8/3K4/8/8 w - - 0 1") == Some(Wdl::Draw));
+    assert!(probe("8/8/4k3/8/8/3KN3/8/8 b - - 0 1") == Some(Wdl::Draw));
+    assert!(probe("8/8/4k3/8/8/3K4/8/7Q w - - 0 1") == Some(Wdl::Win));
+    assert!(probe("8/8/4k3/8/8/3K4/8/7Q b - - 0 1") == Some(Wdl::Loss));
+    // The king takes the rook
+    assert!(probe("8/8/4k3/4R3/8/8/8/K7 b - - 0 1").is_none());
+    assert!(probe("8/8/4k3/8/8/3K4/4P3/8 w - - 0 1").is_none());
+    assert!(probe("rnbqkbnr/pppppppp/8/8/8/8/PPPPPPPP/RNBQKBNR w KQkq - 0 1").is_none());
+}
+
+#[test]
+fn test_match_uci_lines()
+{
+    assert!(uci::parse_score("info depth 8 seldepth 12 score cp -35 nodes 1000 pv e2e4") == Some(-35));
+    assert!(uci::parse_score("info depth 3 score mate 1 pv a1a8") == Some(engine::MATE - 1));
+    assert!(uci::parse_score("info depth 3 score mate -1") == Some(-engine::MATE + 2));
+    assert!(uci::parse_score("info string score cp 10").is_some());
+    assert!(uci::parse_score("bestmove e2e4").is_none());
+    assert!(uci::parse_bestmove("bestmove e2e4 ponder e7e5") == Some("e2e4"));
+    assert!(uci::parse_bestmove("info depth 1").is_none());
+}
+
+// A pretend engine in the shell that always plays e2e4
+#[cfg(unix)]
+#[test]
+fn test_match_uci_player()
+{
+    let script: &str = "while read line; do case \"$line\" in \
+        uci) echo 'id name Fake'; echo uciok;; \
+        isready) echo readyok;; \
+        go*) echo 'info depth 1 score cp 12'; echo 'bestmove e2e4';; \
+        quit) exit;; \
+        esac; done";
+
+    let mut player: UciPlayer = UciPlayer::start("sh", &["-c", script]).unwrap();
+    assert!(player.name() == "Fake");
+    player.new_game();
+
+    let game: Game = Game::new();
+    let clocks: [Duration; 2] = [Duration::from_secs(5); 2];
+    let answer: Option<(ChessMove, Option<i32>)> = player.go(&game, clocks, Duration::ZERO);
+    assert!(answer == Some((ChessMove::drag(0o14, 0o34), Some(12))));
+}
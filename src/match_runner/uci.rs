@@ -0,0 +1,226 @@
+// An engine in another process, talking UCI over its standard input and
+// output. The output is read on its own thread, so an engine that hangs
+// only costs the game and not the match
+use super::*;
+use engine::MATE;
+use std::io::{self, BufRead, BufReader, Write};
+use std::process::{Child, ChildStdin, Command, Stdio};
+use std::sync::mpsc::{self, Receiver, RecvTimeoutError};
+use std::thread;
+
+// How long the engine has to answer `uci` and `isready`
+const READY_TIMEOUT: Duration = Duration::from_secs(10);
+// Time the engine gets past its clock before the runner stops waiting
+const MOVE_MARGIN: Duration = Duration::from_secs(1);
+
+pub struct UciPlayer
+{
+    name: String,
+    child: Child,
+    stdin: ChildStdin,
+    lines: Receiver<String>,
+}
+
+impl UciPlayer
+{
+    // Starts the engine and waits for `uciok`. The name is the one the
+    // engine gives, or the command
+    pub fn start(command: &str, args: &[&str]) -> io::Result<UciPlayer>
+    {
+        let mut child: Child = Command::new(command).args(args).stdin(Stdio::piped()).stdout(Stdio::piped()).stderr(Stdio::null()).spawn()?;
+        let stdin: ChildStdin = child.stdin.take().unwrap();
+        let stdout = BufReader::new(child.stdout.take().unwrap());
+
+        let (sender, lines) = mpsc::channel::<String>();
+        thread::spawn(move ||
+        {
+            for line in stdout.lines().map_while(Result::ok)
+            {
+                if sender.send(line).is_err() { break; }
+            }
+        });
+
+        let mut player: UciPlayer = UciPlayer { name: command.to_string(), child, stdin, lines };
+        player.__send("uci")?;
+        loop
+        {
+            let line: String = player.__next_line(Instant::now() + READY_TIMEOUT)?;
+            if let Some(name) = line.strip_prefix("id name ")
+            {
+                player.name = name.trim().to_string();
+            }
+            if line.trim() == "uciok"
+            {
+                break;
+            }
+        }
+
+        player.__ready()?;
+        return Ok(player);
+    }
+
+    // Sends `setoption`, like for `Hash` or `Threads`
+    pub fn set_option(&mut self, name: &str, value: &str) -> io::Result<()>
+    {
+        self.__send(&format!("setoption name {} value {}", name, value))?;
+        return self.__ready();
+    }
+
+    fn __send(&mut self, line: &str) -> io::Result<()>
+    {
+        writeln!(self.stdin, "{}", line)?;
+        return self.stdin.flush();
+    }
+
+    fn __next_line(&mut self, deadline: Instant) -> io::Result<String>
+    {
+        let timeout: Duration = deadline.saturating_duration_since(Instant::now());
+        return match self.lines.recv_timeout(timeout)
+        {
+            Ok(line) => Ok(line),
+            Err(RecvTimeoutError::Timeout) => Err(io::Error::new(io::ErrorKind::TimedOut, "the engine doesn't answer")),
+            Err(RecvTimeoutError::Disconnected) => Err(io::Error::new(io::ErrorKind::UnexpectedEof, "the engine quit")),
+        };
+    }
+
+    // Waits for `readyok`, which also skips what's left of an earlier
+    // search
+    fn __ready(&mut self) -> io::Result<()>
+    {
+        self.__send("isready")?;
+        while self.__next_line(Instant::now() + READY_TIMEOUT)?.trim() != "readyok" {}
+        return Ok(());
+    }
+
+    // The `position` command for the game
+    fn __position(game: &Game) -> String
+    {
+        let mut command: String = match game.get_start().to_fen() == ChessBoard::init_position().to_fen()
+        {
+            true => "position startpos".to_string(),
+            false => format!("position fen {}", game.get_start().to_fen()),
+        };
+
+        if !game.get_moves().is_empty()
+        {
+            let mut board: ChessBoard = game.get_start().clone();
+            command.push_str(" moves");
+            for mv in game.get_moves()
+            {
+                command.push(' ');
+                command.push_str(&board.to_uci(*mv));
+                board.make_move(*mv);
+            }
+        }
+
+        return command;
+    }
+}
+
+impl Player for UciPlayer
+{
+    fn name(&self) -> &str
+    {
+        return &self.name;
+    }
+
+    fn new_game(&mut self)
+    {
+        let _ = self.__send("ucinewgame").and_then(|_| self.__ready());
+    }
+
+    fn go(&mut self, game: &Game, clocks: [Duration; 2], increment: Duration) -> Option<(ChessMove, Option<i32>)>
+    {
+        let turn: usize = if game.get_board().white_turn() { 0 } else { 1 };
+        self.__send(&Self::__position(game)).ok()?;
+        self.__send(&format!(
+            "go wtime {} btime {} winc {} binc {}",
+            clocks[0].as_millis(), clocks[1].as_millis(), increment.as_millis(), increment.as_millis(),
+        )).ok()?;
+
+        let deadline: Instant = Instant::now() + clocks[turn] + MOVE_MARGIN;
+        let mut score: Option<i32> = None;
+        loop
+        {
+            let line: String = match self.__next_line(deadline)
+            {
+                Ok(line) => line,
+                Err(_) => {
+                    let _ = self.__send("stop");
+                    return None;
+                },
+            };
+
+            if let Some(uci) = parse_bestmove(&line)
+            {
+                let mv: ChessMove = game.get_board().clone().from_uci(uci)?;
+                return Some((mv, score));
+            }
+            score = parse_score(&line).or(score);
+        }
+    }
+}
+
+impl Drop for UciPlayer
+{
+    fn drop(&mut self)
+    {
+        let _ = self.__send("quit");
+        let deadline: Instant = Instant::now() + Duration::from_secs(1);
+        while Instant::now() < deadline
+        {
+            if let Ok(Some(_)) = self.child.try_wait()
+            {
+                return;
+            }
+            thread::sleep(Duration::from_millis(10));
+        }
+
+        let _ = self.child.kill();
+        let _ = self.child.wait();
+    }
+}
+
+// The score of an `info` line in centipawns, mates counted like the built
+// in engine does
+pub fn parse_score(line: &str) -> Option<i32>
+{
+    let mut tokens = line.split_whitespace();
+    if tokens.next() != Some("info")
+    {
+        return None;
+    }
+
+    while let Some(token) = tokens.next()
+    {
+        if token != "score"
+        {
+            continue;
+        }
+
+        let kind: &str = tokens.next()?;
+        let value: i32 = tokens.next()?.parse().ok()?;
+        return match kind
+        {
+            "cp" => Some(value),
+            // Mate in `value` moves, negative if the engine gets mated
+            "mate" if value > 0 => Some(MATE - (2*value - 1)),
+            "mate" => Some(-MATE - 2*value),
+            _ => None,
+        };
+    }
+
+    return None;
+}
+
+// The move of a `bestmove` line
+pub fn parse_bestmove(line: &str) -> Option<&str>
+{
+    let mut tokens = line.split_whitespace();
+    if tokens.next() != Some("bestmove")
+    {
+        return None;
+    }
+
+    return tokens.next();
+}
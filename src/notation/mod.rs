@@ -0,0 +1,194 @@
+// Reading and writing moves in SAN (`Nf3`, `exd5`, `O-O`, `e8=Q+`) and in
+// the coordinates used by UCI (`g1f3`, `e7e8q`). Drops are written as
+// `N@f3` in both.
+//
+// In UCI castling is the king's drag: `e1g1`, or `e1h1` when the board is
+// in chess960 mode, see `ChessBoard::drag`
+use super::*;
+
+#[cfg(test)]
+mod tests;
+
+impl ChessBoard
+{
+    // Writes the move in UCI notation
+    pub fn to_uci(&self, mv: ChessMove) -> String
+    {
+        return match mv
+        {
+            ChessMove::Drag { from, to, promotion } => {
+                let mut uci: String = format!("{}{}", self.write_coords(from), self.write_coords(to));
+                if let Some(_type) = promotion
+                {
+                    uci.push(fen::__piece_to_char(ChessPiece::from_type(_type, false)));
+                }
+                uci
+            },
+            ChessMove::Drop { piece, to } => self.write_drop(piece, to),
+        };
+    }
+
+    // Reads a legal move written in UCI notation
+    pub fn from_uci(&mut self, uci: &str) -> Option<ChessMove>
+    {
+        let mv: ChessMove = if uci.contains('@')
+        {
+            let (piece, to) = self.read_drop(uci)?;
+            ChessMove::Drop { piece, to }
+        }
+        else
+        {
+            // Both blocks are a letter and a number, the promotion comes last
+            let second: usize = uci.char_indices().skip(1).find(|(_, c)| c.is_ascii_lowercase())?.0;
+            let third: usize = uci.char_indices().skip(second+1).find(|(_, c)| !c.is_ascii_digit()).map_or(uci.len(), |(i, _)| i);
+
+            let from: u8 = self.read_coords(&uci[..second])?;
+            let to: u8 = self.read_coords(&uci[second..third])?;
+            let promotion: Option<ChessPieceType> = match &uci[third..]
+            {
+                "" => None,
+                p if p.chars().count() == 1 => Some(fen::__char_to_piece(p.chars().next()?)?.get_type()?),
+                _ => return None,
+            };
+
+            ChessMove::Drag { from, to, promotion }
+        };
+
+        let moves: Vec<ChessMove> = self.legal_moves();
+        if moves.contains(&mv)
+        {
+            return Some(mv);
+        }
+
+        // Without a promotion the default one is meant
+        if let ChessMove::Drag { from, to, promotion: None } = mv
+        {
+            let default: ChessMove = ChessMove::Drag { from, to, promotion: Some(self._default_promotion) };
+            if moves.contains(&default)
+            {
+                return Some(default);
+            }
+        }
+
+        return None;
+    }
+
+    // Writes the move in SAN, with `+` for a check and `#` for a checkmate.
+    // The move has to be legal
+    pub fn to_san(&mut self, mv: ChessMove) -> String
+    {
+        let moves: Vec<ChessMove> = self.legal_moves();
+        let mut san: String = self.__san_without_check(mv, &moves);
+
+        let mut after: ChessBoard = self.clone();
+        after.make_move(mv);
+        if after.get_state() == ChessState::Checkmate
+        {
+            san.push('#');
+        }
+        else if after.is_check(after.white_turn())
+        {
+            san.push('+');
+        }
+
+        return san;
+    }
+
+    // Reads a legal move written in SAN. Check and annotation symbols at
+    // the end are ignored, `0-0` is read as `O-O` and the `=` of a
+    // promotion can be left out
+    pub fn from_san(&mut self, san: &str) -> Option<ChessMove>
+    {
+        let wanted: String = __normalize_san(san);
+        let moves: Vec<ChessMove> = self.legal_moves();
+
+        for mv in moves.iter()
+        {
+            if __normalize_san(&self.__san_without_check(*mv, &moves)) == wanted
+            {
+                return Some(*mv);
+            }
+        }
+
+        return None;
+    }
+
+    fn __san_without_check(&self, mv: ChessMove, moves: &[ChessMove]) -> String
+    {
+        let (from, to, promotion) = match mv
+        {
+            ChessMove::Drag { from, to, promotion } => (from, to, promotion),
+            ChessMove::Drop { piece, to } => return self.write_drop(piece, to),
+        };
+
+        if let Some(side) = self.__castling_side(from, to)
+        {
+            return String::from(if side == 0 || side == 2 { "O-O" } else { "O-O-O" });
+        }
+
+        let from_el: ChessPiece = self.board[from as usize];
+        let capture: bool = self.is_capture(from, to);
+        let f: ChessPos = self.__pos(from);
+        let mut san: String = String::new();
+
+        if from_el == ChessPiece::WPawn || from_el == ChessPiece::BPawn
+        {
+            if capture
+            {
+                san.push((b'a' + f.x) as char);
+            }
+        }
+        else
+        {
+            san.push(fen::__piece_to_char(from_el).to_ascii_uppercase());
+
+            // Other pieces of the same kind that can get to the same block
+            let others: Vec<ChessPos> = moves.iter().filter_map(|other| match *other
+            {
+                ChessMove::Drag { from: o, to: t, .. } if t == to && o != from && self.board[o as usize] == from_el => Some(self.__pos(o)),
+                _ => None,
+            }).collect();
+
+            if !others.is_empty()
+            {
+                if others.iter().all(|o| o.x != f.x)
+                {
+                    san.push((b'a' + f.x) as char);
+                }
+                else if others.iter().all(|o| o.y != f.y)
+                {
+                    san.push_str(&(f.y+1).to_string());
+                }
+                else
+                {
+                    san.push_str(&self.write_coords(from));
+                }
+            }
+        }
+
+        if capture
+        {
+            san.push('x');
+        }
+        san.push_str(&self.write_coords(to));
+
+        if let Some(_type) = promotion
+        {
+            san.push('=');
+            san.push(fen::__piece_to_char(ChessPiece::from_type(_type, true)));
+        }
+
+        return san;
+    }
+}
+
+fn __normalize_san(san: &str) -> String
+{
+    let san: &str = san.trim_end_matches(['+', '#', '!', '?']);
+    if san.starts_with("0-0")
+    {
+        return san.replace('0', "O");
+    }
+
+    return san.replace('=', "");
+}
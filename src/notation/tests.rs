@@ -0,0 +1,69 @@
+use super::*;
+
+#[test]
+fn test_notation_san()
+{
+    let mut board: ChessBoard = ChessBoard::init_position();
+    assert!(board.legal_moves().len() == 20);
+
+    let mv: ChessMove = board.from_san("e4").unwrap();
+    assert!(mv == ChessMove::drag(0o14, 0o34));
+    assert_eq!(board.to_san(mv), "e4");
+    assert_eq!(board.to_uci(mv), "e2e4");
+    assert!(board.from_san("e5").is_none());
+    assert!(board.from_san("Ke2").is_none());
+
+    // Disambiguation by file and by rank, captures and checks
+    let mut board: ChessBoard = ChessBoard::from_fen("4k3/8/8/8/8/8/4K3/R6R w - - 0 1").unwrap();
+    let mv: ChessMove = board.from_san("Rad1").unwrap();
+    assert!(mv == ChessMove::drag(0o00, 0o03));
+    assert_eq!(board.to_san(ChessMove::drag(0o07, 0o77)), "Rh8+");
+
+    let mut board: ChessBoard = ChessBoard::from_fen("4k3/8/8/8/R7/8/8/R3K3 w - - 0 1").unwrap();
+    assert_eq!(board.to_san(ChessMove::drag(0o00, 0o20)), "R1a3");
+
+    let mut board: ChessBoard = ChessBoard::from_fen("4k3/8/8/3p4/4P3/8/8/4K3 w - - 0 1").unwrap();
+    let mv: ChessMove = board.from_san("exd5").unwrap();
+    assert_eq!(board.to_san(mv), "exd5");
+
+    // Checkmate
+    let mut board: ChessBoard = ChessBoard::from_fen("6k1/5ppp/8/8/8/8/8/R5K1 w - - 0 1").unwrap();
+    assert_eq!(board.to_san(ChessMove::drag(0o00, 0o70)), "Ra8#");
+}
+
+#[test]
+fn test_notation_castling_and_promotion()
+{
+    let mut board: ChessBoard = ChessBoard::from_fen("r3k2r/8/8/8/8/8/8/R3K2R w KQkq - 0 1").unwrap();
+    let mv: ChessMove = board.from_san("O-O").unwrap();
+    assert!(mv == ChessMove::drag(0o04, 0o06));
+    assert!(board.from_san("0-0-0") == Some(ChessMove::drag(0o04, 0o02)));
+    assert_eq!(board.to_uci(mv), "e1g1");
+
+    // Chess960 castling is the king taking its rook
+    board.set_chess960(true);
+    assert!(board.from_uci("e1h1") == Some(ChessMove::drag(0o04, 0o07)));
+    assert_eq!(board.to_san(ChessMove::drag(0o04, 0o07)), "O-O");
+
+    let mut board: ChessBoard = ChessBoard::from_fen("1r2k3/P7/8/8/8/8/8/4K3 w - - 0 1").unwrap();
+    let mv: ChessMove = board.from_san("axb8=N").unwrap();
+    assert!(mv == ChessMove::Drag { from: 0o60, to: 0o71, promotion: Some(ChessPieceType::Knight) });
+    assert_eq!(board.to_uci(mv), "a7b8n");
+    assert!(board.from_uci("a7a8") == Some(ChessMove::Drag { from: 0o60, to: 0o70, promotion: Some(ChessPieceType::Queen) }));
+    assert!(board.from_san("a8Q+") == board.from_uci("a7a8q"));
+
+    // Every promotion type is a move of its own
+    assert!(board.legal_moves().len() == 8 + 5);
+    board.make_move(mv);
+    assert!(board.get_piece(0o71) == ChessPiece::WKnight);
+    assert!(board.get_promotion() == ChessPieceType::Queen);
+}
+
+#[test]
+fn test_notation_bigger_board()
+{
+    let mut board: ChessBoard = ChessBoard::from_fen("k9/10/10/10/10/10/10/10/10/K8R w - -").unwrap();
+    let mv: ChessMove = board.from_uci("j1j10").unwrap();
+    assert_eq!(board.to_san(mv), "Rj10+");
+    assert!(board.from_san("Rj10") == Some(mv));
+}
@@ -70,6 +70,23 @@ fn test_enum_chesspiece()
     assert!(ChessPiece::WBishop.is_enemy_to(ChessPiece::BBishop));
     assert!(ChessPiece::WBishop.is_enemy_to(ChessPiece::Empty));
     assert!(!ChessPiece::WBishop.is_enemy_to(ChessPiece::WBishop));
+
+
+    // Testing fairy pieces
+    assert!(ChessPiece::BArchbishop.is_black() && !ChessPiece::BArchbishop.is_white());
+    assert!(ChessPiece::BGrasshopper.is_black() && !ChessPiece::BGrasshopper.is_white());
+    assert!(ChessPiece::WArchbishop.is_white() && !ChessPiece::WArchbishop.is_black());
+    assert!(ChessPiece::WGrasshopper.is_white() && !ChessPiece::WGrasshopper.is_black());
+    assert!(ChessPiece::WCamel.is_enemy_to(ChessPiece::BZebra));
+    assert!(!ChessPiece::WCamel.is_enemy_to(ChessPiece::WAmazon));
+
+    // Testing `ChessPiece::from_type` and `ChessPiece::get_type`
+    assert_eq!(ChessPiece::from_type(ChessPieceType::Chancellor, true), ChessPiece::WChancellor);
+    assert_eq!(ChessPiece::from_type(ChessPieceType::Knight, false), ChessPiece::BKnight);
+    assert_eq!(ChessPiece::BAmazon.get_type(), Some(ChessPieceType::Amazon));
+    assert_eq!(ChessPiece::WPawn.get_type(), Some(ChessPieceType::Pawn));
+    assert!(ChessPiece::Empty.get_type().is_none());
+    assert!(ChessPieceType::Camel.is_fairy() && !ChessPieceType::Rook.is_fairy());
 }
 
 #[test]
@@ -289,6 +306,37 @@ fn test_struct_chesspos_no_panic()
 
 }
 
+#[test]
+fn test_struct_chesspos_dimensions()
+{
+    // 10 x 8 board
+    let a1: ChessPos = ChessPos::from_dim(0, 10, 8, false);
+    let a2: ChessPos = ChessPos::from_dim(79, 10, 8, false);
+    let a3: ChessPos = ChessPos::from_dim(23, 10, 8, false);
+
+    assert!(a1.get_x()==0&&a1.get_y()==0);
+    assert!(a2.get_x()==9&&a2.get_y()==7);
+    assert!(a3.get_x()==3&&a3.get_y()==2);
+    assert!(a3.raw() == 23);
+
+    assert!(ChessPos::conv_dim(9, 7, 10, 8) == 79);
+    assert!(ChessPos::conv_dim(5, 5, 6, 6) == 35);
+}
+
+#[test]
+#[should_panic]
+fn test_struct_chesspos_from_dim_panic()
+{
+    ChessPos::from_dim(80, 10, 8, false);
+}
+
+#[test]
+#[should_panic]
+fn test_struct_chesspos_conv_dim_panic()
+{
+    ChessPos::conv_dim(6, 0, 6, 6);
+}
+
 #[test]
 #[should_panic]
 fn test_struct_chesspos_from_no_ignore_panic()
@@ -312,4 +360,15 @@ fn test_struct_chesspos_between_panic()
     let p2: ChessPos = ChessPos::from(0o76 , false);
 
     ChessPos::from(0o00, false).between(p1,p2,false);
-}
\ No newline at end of file
+}
+
+#[test]
+fn test_struct_chessboard_eq()
+{
+    // `assert_eq!` on boards prints the FEN of both when it fails
+    let mut board: ChessBoard = ChessBoard::init_position();
+    board.drag(0o14, 0o34);
+    assert_eq!(board, ChessBoard::from_fen("rnbqkbnr/pppppppp/8/8/4P3/8/PPPP1PPP/RNBQKBNR b KQkq e3 0 1").unwrap());
+    assert_eq!(board.get_piece(0o34), "P".parse().unwrap());
+    assert_ne!(board, ChessBoard::init_position());
+}
@@ -0,0 +1,341 @@
+// Tactics puzzles in the format of the lichess puzzle database, a CSV with
+// the fields
+//
+//      PuzzleId,FEN,Moves,Rating,RatingDeviation,Popularity,NbPlays,Themes,GameUrl,OpeningTags
+//
+// The FEN is the position before the move of the opponent, which is the
+// first of the moves (in UCI, separated by spaces). The player then finds
+// every other move while the opponent's replies are played for them, see
+// `Solving`. Only the id, the FEN, the moves, the rating and the themes
+// are read
+use super::*;
+use core::ops::RangeInclusive;
+use game::Game;
+
+#[cfg(test)]
+mod tests;
+
+#[derive(Debug, PartialEq, Eq, Clone)]
+pub enum PuzzleError
+{
+    // The field of the given name is missing
+    MissingField(&'static str),
+    InvalidRating(String),
+    InvalidFen(fen::FenError),
+    // The move isn't legal in the line of the puzzle, or there are too few
+    // moves for a puzzle
+    IllegalMove(String),
+}
+
+#[derive(Debug, Clone)]
+pub struct Puzzle
+{
+    id: String,
+    fen: String,
+    // In UCI, the first one is the move of the opponent
+    moves: Vec<String>,
+    rating: u16,
+    themes: Vec<String>,
+}
+
+impl Puzzle
+{
+    // Checks that the moves are legal one after the other, and that there
+    // is at least a move of the opponent and one of the player
+    pub fn new(id: &str, fen: &str, moves: &[&str], rating: u16, themes: &[&str]) -> Result<Puzzle, PuzzleError>
+    {
+        if moves.len() < 2
+        {
+            return Err(PuzzleError::IllegalMove(moves.join(" ")));
+        }
+
+        let mut board: ChessBoard = ChessBoard::from_fen(fen).map_err(PuzzleError::InvalidFen)?;
+        for uci in moves
+        {
+            match board.from_uci(uci)
+            {
+                Some(mv) => board.make_move(mv),
+                None => return Err(PuzzleError::IllegalMove(uci.to_string())),
+            };
+        }
+
+        return Ok(Puzzle
+        {
+            id: id.to_string(),
+            fen: fen.to_string(),
+            moves: moves.iter().map(|uci| uci.to_string()).collect(),
+            rating,
+            themes: themes.iter().map(|theme| theme.to_string()).collect(),
+        });
+    }
+
+    // Reads a line of the lichess CSV
+    pub fn from_csv(line: &str) -> Result<Puzzle, PuzzleError>
+    {
+        let fields: Vec<&str> = line.trim().split(',').collect();
+        let field = |index: usize, name: &'static str| fields.get(index).copied().ok_or(PuzzleError::MissingField(name));
+
+        let id: &str = field(0, "PuzzleId")?;
+        let fen: &str = field(1, "FEN")?;
+        let moves: Vec<&str> = field(2, "Moves")?.split_whitespace().collect();
+        let rating: &str = field(3, "Rating")?;
+        let rating: u16 = rating.parse().map_err(|_| PuzzleError::InvalidRating(rating.to_string()))?;
+        // Not every export has the themes
+        let themes: Vec<&str> = fields.get(7).map_or(Vec::new(), |themes| themes.split_whitespace().collect());
+
+        return Puzzle::new(id, fen, &moves, rating, &themes);
+    }
+
+    pub fn get_id(&self) -> &str
+    {
+        return &self.id;
+    }
+
+    pub fn get_fen(&self) -> &str
+    {
+        return &self.fen;
+    }
+
+    // The whole line in UCI, starting with the move of the opponent
+    pub fn get_moves(&self) -> &[String]
+    {
+        return &self.moves;
+    }
+
+    pub fn get_rating(&self) -> u16
+    {
+        return self.rating;
+    }
+
+    pub fn get_themes(&self) -> &[String]
+    {
+        return &self.themes;
+    }
+
+    pub fn has_theme(&self, theme: &str) -> bool
+    {
+        return self.themes.iter().any(|t| t.eq_ignore_ascii_case(theme));
+    }
+}
+
+// Reads the puzzles of a lichess CSV, the header is skipped if there's
+// one. Fails on the first puzzle that can't be read, with its line number
+// counting from 1
+pub fn read_csv(csv: &str) -> Result<Vec<Puzzle>, (usize, PuzzleError)>
+{
+    let mut puzzles: Vec<Puzzle> = Vec::new();
+    for (index, line) in csv.lines().enumerate()
+    {
+        if line.trim().is_empty() || (index == 0 && line.starts_with("PuzzleId"))
+        {
+            continue;
+        }
+
+        puzzles.push(Puzzle::from_csv(line).map_err(|error| (index + 1, error))?);
+    }
+
+    return Ok(puzzles);
+}
+
+// The puzzles that have all the themes and a rating in the range
+pub fn filter<'a>(puzzles: &'a [Puzzle], themes: &'a [&str], ratings: RangeInclusive<u16>) -> impl Iterator<Item = &'a Puzzle>
+{
+    return puzzles.iter()
+        .filter(move |puzzle| ratings.contains(&puzzle.rating))
+        .filter(move |puzzle| themes.iter().all(|theme| puzzle.has_theme(theme)));
+}
+
+#[derive(Debug, PartialEq, Eq, Clone, Copy)]
+pub enum PuzzleState
+{
+    Solving,
+    Solved,
+    // A wrong move was tried. The puzzle can still be finished, but it
+    // doesn't count as solved
+    Failed,
+}
+
+// What came of a move of the player
+#[derive(Debug, PartialEq, Eq, Clone, Copy)]
+pub enum Attempt
+{
+    // The move of the solution, followed by the reply of the opponent
+    Correct(ChessMove),
+    // The last move of the solution, or another checkmate
+    Solved,
+    // A legal move that isn't the solution, it isn't played
+    Wrong,
+    // Not a legal move, or the puzzle is over
+    Illegal,
+}
+
+// A puzzle being solved. Starts after the move of the opponent
+pub struct Solving
+{
+    game: Game,
+    // The line of the puzzle, read on the board
+    solution: Vec<ChessMove>,
+    // The index of the next move in `solution`
+    next: usize,
+    state: PuzzleState,
+    mistakes: usize,
+    hints: usize,
+}
+
+impl Solving
+{
+    pub fn start(puzzle: &Puzzle) -> Solving
+    {
+        // The puzzle was checked when it was made
+        let mut board: ChessBoard = ChessBoard::from_fen(&puzzle.fen).unwrap();
+        let mut solution: Vec<ChessMove> = Vec::new();
+        for uci in puzzle.moves.iter()
+        {
+            let mv: ChessMove = board.from_uci(uci).unwrap();
+            board.make_move(mv);
+            solution.push(mv);
+        }
+
+        let mut game: Game = Game::from_board(ChessBoard::from_fen(&puzzle.fen).unwrap());
+        game.play(solution[0]);
+
+        return Solving { game, solution, next: 1, state: PuzzleState::Solving, mistakes: 0, hints: 0 };
+    }
+
+    // The game so far, from the position of the puzzle
+    pub fn get_game(&self) -> &Game
+    {
+        return &self.game;
+    }
+
+    pub fn get_state(&self) -> PuzzleState
+    {
+        return self.state;
+    }
+
+    // If the puzzle is over, solved or not
+    pub fn is_over(&self) -> bool
+    {
+        return self.next >= self.solution.len();
+    }
+
+    pub fn get_mistakes(&self) -> usize
+    {
+        return self.mistakes;
+    }
+
+    pub fn get_hints(&self) -> usize
+    {
+        return self.hints;
+    }
+
+    // Plays the move of the player if it's the one of the solution, and the
+    // reply of the opponent after it. A move that checkmates solves the
+    // puzzle even if the solution has another mate
+    pub fn try_move(&mut self, mv: ChessMove) -> Attempt
+    {
+        if self.is_over()
+        {
+            return Attempt::Illegal;
+        }
+
+        // The same move as the board would read it, with the promotion
+        let mut board: ChessBoard = self.game.get_board().clone();
+        let mv: ChessMove = match board.from_uci(&board.to_uci(mv))
+        {
+            Some(mv) => mv,
+            None => return Attempt::Illegal,
+        };
+
+        board.make_move(mv);
+        let mates: bool = board.get_state() == ChessState::Checkmate;
+        if mv != self.solution[self.next] && !mates
+        {
+            self.mistakes += 1;
+            self.state = PuzzleState::Failed;
+            return Attempt::Wrong;
+        }
+
+        self.game.play(mv);
+        self.next += 1;
+        if mates || self.is_over()
+        {
+            self.next = self.solution.len();
+            self.__finish();
+            return Attempt::Solved;
+        }
+
+        let reply: ChessMove = self.solution[self.next];
+        self.game.play(reply);
+        self.next += 1;
+        if self.is_over()
+        {
+            self.__finish();
+            return Attempt::Solved;
+        }
+
+        return Attempt::Correct(reply);
+    }
+
+    pub fn try_uci(&mut self, uci: &str) -> Attempt
+    {
+        return match self.game.get_board().clone().from_uci(uci)
+        {
+            Some(mv) => self.try_move(mv),
+            None => Attempt::Illegal,
+        };
+    }
+
+    pub fn try_san(&mut self, san: &str) -> Attempt
+    {
+        return match self.game.get_board().clone().from_san(san)
+        {
+            Some(mv) => self.try_move(mv),
+            None => Attempt::Illegal,
+        };
+    }
+
+    // The block of the piece to move next, `None` once the puzzle is over
+    pub fn hint(&mut self) -> Option<u8>
+    {
+        return match self.solution.get(self.next)
+        {
+            Some(ChessMove::Drag { from, .. }) => {
+                self.hints += 1;
+                Some(*from)
+            },
+            Some(ChessMove::Drop { to, .. }) => {
+                self.hints += 1;
+                Some(*to)
+            },
+            None => None,
+        };
+    }
+
+    // The moves of the solution that are left, the player's and the
+    // opponent's. Giving up fails the puzzle
+    pub fn give_up(&mut self) -> Vec<ChessMove>
+    {
+        if !self.is_over()
+        {
+            self.state = PuzzleState::Failed;
+        }
+
+        let rest: Vec<ChessMove> = self.solution[self.next..].to_vec();
+        for mv in rest.iter()
+        {
+            self.game.play(*mv);
+        }
+        self.next = self.solution.len();
+
+        return rest;
+    }
+
+    fn __finish(&mut self)
+    {
+        if self.state == PuzzleState::Solving
+        {
+            self.state = PuzzleState::Solved;
+        }
+    }
+}
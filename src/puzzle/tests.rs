@@ -0,0 +1,54 @@
+use super::*;
+
+const CSV: &str = "PuzzleId,FEN,Moves,Rating,RatingDeviation,Popularity,NbPlays,Themes,GameUrl,OpeningTags
+00008,r6k/pp2r2p/4Rp1Q/3p4/8/1N1P2R1/PqP2bPP/7K b - - 0 24,f2g3 e6e7 b2b1 b3c1 b1c1 h6c1,1913,75,94,6230,crushing hangingPiece long middlegame,https://lichess.org/787zsVup/black#48,
+back,7k/5ppp/8/n7/8/8/8/RR4K1 b - - 0 1,a5c4 a1a8,900,80,90,100,mate mateIn1 backRankMate oneMove,,
+";
+
+#[test]
+fn test_puzzle_csv()
+{
+    let puzzles: Vec<Puzzle> = read_csv(CSV).unwrap();
+    assert!(puzzles.len() == 2);
+    assert!(puzzles[0].get_id() == "00008" && puzzles[0].get_rating() == 1913);
+    assert!(puzzles[0].get_moves().len() == 6 && puzzles[0].has_theme("hangingpiece"));
+
+    let ids = |themes: &[&str], ratings: RangeInclusive<u16>| filter(&puzzles, themes, ratings).map(|p| p.get_id().to_string()).collect::<Vec<String>>();
+    assert!(ids(&[], 0..=3000) == ["00008", "back"]);
+    assert!(ids(&["mate"], 0..=3000) == ["back"]);
+    assert!(ids(&["middlegame", "long"], 0..=3000) == ["00008"]);
+    assert!(ids(&[], 1000..=1500).is_empty());
+
+    assert!(read_csv("a,8/8/8/8/8/8/8/8 w - - 0 1,e2e4,1500").unwrap_err().0 == 1);
+    assert!(Puzzle::from_csv("a,7k/8/8/8/8/8/8/K7 w - - 0 1,a1a2 h8h7,high").unwrap_err() == PuzzleError::InvalidRating("high".to_string()));
+    assert!(Puzzle::from_csv("a,7k/8/8/8/8/8/8/K7 w - - 0 1,a1a2 h8h1,1500").unwrap_err() == PuzzleError::IllegalMove("h8h1".to_string()));
+    assert!(Puzzle::from_csv("a,7k/8/8/8/8/8/8/K7 w - - 0 1").unwrap_err() == PuzzleError::MissingField("Moves"));
+}
+
+#[test]
+fn test_puzzle_solving()
+{
+    let puzzles: Vec<Puzzle> = read_csv(CSV).unwrap();
+
+    let mut solving: Solving = Solving::start(&puzzles[0]);
+    assert!(solving.get_game().get_moves() == [ChessMove::drag(0o15, 0o26)]);
+    assert!(solving.try_uci("e6e8") == Attempt::Illegal);
+    assert!(solving.try_san("Rxf6") == Attempt::Wrong);
+    assert!(solving.get_state() == PuzzleState::Failed && solving.get_mistakes() == 1);
+    assert!(solving.hint() == Some(0o54) && solving.get_hints() == 1);
+
+    assert!(solving.try_san("Rxe7") == Attempt::Correct(ChessMove::drag(0o11, 0o01)));
+    assert!(solving.try_uci("b3c1") == Attempt::Correct(ChessMove::drag(0o01, 0o02)));
+    assert!(solving.try_uci("h6c1") == Attempt::Solved);
+    assert!(solving.is_over() && solving.get_state() == PuzzleState::Failed);
+    assert!(solving.try_uci("a7a6") == Attempt::Illegal && solving.hint().is_none());
+
+    // Another mate than the one of the solution
+    let mut solving: Solving = Solving::start(&puzzles[1]);
+    assert!(solving.try_san("Rb8#") == Attempt::Solved);
+    assert!(solving.get_state() == PuzzleState::Solved && solving.get_hints() == 0);
+
+    let mut solving: Solving = Solving::start(&puzzles[0]);
+    assert!(solving.give_up().len() == 5);
+    assert!(solving.get_state() == PuzzleState::Failed && solving.get_game().get_moves().len() == 6);
+}
@@ -0,0 +1,152 @@
+// The Glicko-2 update of a rating period, as described by Mark Glickman
+// in "Example of the Glicko-2 system"
+use std::f64::consts::PI;
+
+// Converts between the Glicko scale and the Glicko-2 one
+const SCALE: f64 = 173.7178;
+// When the volatility iteration stops
+const EPSILON: f64 = 0.000001;
+
+#[derive(Debug, PartialEq, Clone, Copy)]
+pub struct Glicko
+{
+    rating: f64,
+    deviation: f64,
+    volatility: f64,
+}
+
+impl Glicko
+{
+    pub fn new(rating: f64, deviation: f64, volatility: f64) -> Glicko
+    {
+        return Glicko { rating, deviation, volatility };
+    }
+
+    pub fn get_rating(&self) -> f64
+    {
+        return self.rating;
+    }
+
+    // How uncertain the rating is, about 95% of the time the strength is
+    // within two deviations of it
+    pub fn get_deviation(&self) -> f64
+    {
+        return self.deviation;
+    }
+
+    // How much the strength of the player changes over time
+    pub fn get_volatility(&self) -> f64
+    {
+        return self.volatility;
+    }
+
+    fn __mu(&self) -> f64
+    {
+        return (self.rating - 1500.0) / SCALE;
+    }
+
+    fn __phi(&self) -> f64
+    {
+        return self.deviation / SCALE;
+    }
+
+    // The expected points against the opponent, counting the uncertainty
+    // of both ratings
+    pub fn expected_score(&self, opponent: &Glicko) -> f64
+    {
+        let phi: f64 = (self.__phi().powi(2) + opponent.__phi().powi(2)).sqrt();
+        return __expected(self.__mu(), opponent.__mu(), phi);
+    }
+
+    // The rating after a period with the games against the opponents, as
+    // their ratings at the start of the period and the points. Without
+    // games only the deviation grows
+    pub fn update(&self, games: &[(Glicko, f64)], tau: f64) -> Glicko
+    {
+        let mu: f64 = self.__mu();
+        let phi: f64 = self.__phi();
+
+        if games.is_empty()
+        {
+            let phi: f64 = (phi.powi(2) + self.volatility.powi(2)).sqrt();
+            return Glicko::new(self.rating, phi * SCALE, self.volatility);
+        }
+
+        let mut variance: f64 = 0.0;
+        let mut improvement: f64 = 0.0;
+        for (opponent, score) in games.iter()
+        {
+            let g: f64 = __g(opponent.__phi());
+            let expected: f64 = __expected(mu, opponent.__mu(), opponent.__phi());
+            variance += g.powi(2) * expected * (1.0 - expected);
+            improvement += g * (score - expected);
+        }
+        let v: f64 = 1.0 / variance;
+        let delta: f64 = v * improvement;
+
+        let volatility: f64 = self.__new_volatility(delta, v, tau);
+        let phi_star: f64 = (phi.powi(2) + volatility.powi(2)).sqrt();
+        let phi: f64 = 1.0 / (1.0 / phi_star.powi(2) + 1.0 / v).sqrt();
+        let mu: f64 = mu + phi.powi(2) * improvement;
+
+        return Glicko::new(mu * SCALE + 1500.0, phi * SCALE, volatility);
+    }
+
+    // The Illinois algorithm of step 5
+    fn __new_volatility(&self, delta: f64, v: f64, tau: f64) -> f64
+    {
+        let phi: f64 = self.__phi();
+        let a: f64 = self.volatility.powi(2).ln();
+        let f = |x: f64| -> f64
+        {
+            let ex: f64 = x.exp();
+            return ex * (delta.powi(2) - phi.powi(2) - v - ex) / (2.0 * (phi.powi(2) + v + ex).powi(2)) - (x - a) / tau.powi(2);
+        };
+
+        let mut low: f64 = a;
+        let mut high: f64 = if delta.powi(2) > phi.powi(2) + v
+        {
+            (delta.powi(2) - phi.powi(2) - v).ln()
+        }
+        else
+        {
+            let mut k: f64 = 1.0;
+            while f(a - k*tau) < 0.0
+            {
+                k += 1.0;
+            }
+            a - k*tau
+        };
+
+        let mut f_low: f64 = f(low);
+        let mut f_high: f64 = f(high);
+        while (high - low).abs() > EPSILON
+        {
+            let c: f64 = low + (low - high) * f_low / (f_high - f_low);
+            let f_c: f64 = f(c);
+            if f_c * f_high <= 0.0
+            {
+                low = high;
+                f_low = f_high;
+            }
+            else
+            {
+                f_low /= 2.0;
+            }
+            high = c;
+            f_high = f_c;
+        }
+
+        return (low / 2.0).exp();
+    }
+}
+
+fn __g(phi: f64) -> f64
+{
+    return 1.0 / (1.0 + 3.0 * phi.powi(2) / PI.powi(2)).sqrt();
+}
+
+fn __expected(mu: f64, opponent_mu: f64, phi: f64) -> f64
+{
+    return 1.0 / (1.0 + (-__g(phi) * (mu - opponent_mu)).exp());
+}
@@ -0,0 +1,369 @@
+// Ratings of players and bots, kept by name. Every game updates the Elo
+// rating right away. The Glicko-2 ratings change once per rating period,
+// with all the games of the period, when `Ratings::end_period` is called.
+//
+// Players start at 1500 and stay provisional for their first games, their
+// Elo rating moves twice as fast while they are
+use super::*;
+use game::Game;
+use std::io;
+use std::path::Path;
+use tournament::GameResult;
+
+mod glicko;
+#[cfg(test)]
+mod tests;
+
+pub use glicko::Glicko;
+
+const START_RATING: f64 = 1500.0;
+const START_DEVIATION: f64 = 350.0;
+const START_VOLATILITY: f64 = 0.06;
+
+#[derive(Debug, PartialEq, Clone, Copy)]
+pub struct PlayerRating
+{
+    elo: f64,
+    glicko: Glicko,
+    games: u32,
+}
+
+impl PlayerRating
+{
+    pub fn get_elo(&self) -> f64
+    {
+        return self.elo;
+    }
+
+    pub fn get_glicko(&self) -> Glicko
+    {
+        return self.glicko;
+    }
+
+    // The number of games rated so far, for Glicko-2 only those of the
+    // periods that ended
+    pub fn get_games(&self) -> u32
+    {
+        return self.games;
+    }
+}
+
+impl Default for PlayerRating
+{
+    fn default() -> Self
+    {
+        return PlayerRating { elo: START_RATING, glicko: Glicko::new(START_RATING, START_DEVIATION, START_VOLATILITY), games: 0 };
+    }
+}
+
+#[derive(Debug, PartialEq, Eq, Clone)]
+pub enum RatingsError
+{
+    // The line of the file, from 1, that can't be read
+    InvalidLine(usize),
+    Io(io::ErrorKind),
+}
+
+#[derive(Debug, Clone)]
+pub struct Ratings
+{
+    k_factor: f64,
+    // The Glicko-2 system constant, how much the volatility can change
+    tau: f64,
+    // Players are provisional for this many games
+    provisional_games: u32,
+    players: BTreeMap<String, PlayerRating>,
+    // The games of the current period, with the points of white
+    period: Vec<(String, String, f64)>,
+}
+
+impl Ratings
+{
+    pub fn new() -> Ratings
+    {
+        return Ratings { k_factor: 20.0, tau: 0.5, provisional_games: 10, players: BTreeMap::new(), period: Vec::new() };
+    }
+
+    pub fn set_k_factor(&mut self, k_factor: f64)
+    {
+        self.k_factor = k_factor;
+    }
+
+    pub fn get_k_factor(&self) -> f64
+    {
+        return self.k_factor;
+    }
+
+    pub fn set_tau(&mut self, tau: f64)
+    {
+        self.tau = tau;
+    }
+
+    pub fn set_provisional_games(&mut self, games: u32)
+    {
+        self.provisional_games = games;
+    }
+
+    // Adds a player with an Elo rating, or changes the rating of one. The
+    // Glicko-2 rating starts at the same value. Returns false for a name
+    // with a tab or a line break, or a rating that isn't a number
+    pub fn set_player(&mut self, name: &str, elo: f64) -> bool
+    {
+        if !__valid_name(name) || !elo.is_finite()
+        {
+            return false;
+        }
+
+        let player: &mut PlayerRating = self.players.entry(__clean_name(name)).or_default();
+        player.elo = elo;
+        player.glicko = Glicko::new(elo, player.glicko.get_deviation(), player.glicko.get_volatility());
+        return true;
+    }
+
+    pub fn get(&self, name: &str) -> Option<&PlayerRating>
+    {
+        return self.players.get(&__clean_name(name));
+    }
+
+    // The players by name
+    pub fn players(&self) -> impl Iterator<Item = (&str, &PlayerRating)>
+    {
+        return self.players.iter().map(|(name, rating)| (name.as_str(), rating));
+    }
+
+    // The players with the highest Elo first
+    pub fn ranking(&self) -> Vec<(&str, &PlayerRating)>
+    {
+        let mut ranking: Vec<(&str, &PlayerRating)> = self.players().collect();
+        ranking.sort_by(|a, b| b.1.elo.total_cmp(&a.1.elo));
+        return ranking;
+    }
+
+    // A player is provisional for the first games, unknown players too
+    pub fn is_provisional(&self, name: &str) -> bool
+    {
+        return self.get(name).is_none_or(|player| player.games < self.provisional_games);
+    }
+
+    // The points the first player is expected to get against the second
+    // from their Elo ratings, unknown players counted at 1500
+    pub fn expected_score(&self, player: &str, opponent: &str) -> f64
+    {
+        return __expected_elo(self.__elo(player), self.__elo(opponent));
+    }
+
+    // Same as `Ratings::expected_score` from the Glicko-2 ratings
+    pub fn glicko_expected_score(&self, player: &str, opponent: &str) -> f64
+    {
+        let glicko = |name: &str| self.get(name).copied().unwrap_or_default().glicko;
+        return glicko(player).expected_score(&glicko(opponent));
+    }
+
+    // Rates a game. Players that aren't known yet are added. Returns false
+    // if a name has a tab or a line break, see `Ratings::set_player`
+    pub fn record(&mut self, white: &str, black: &str, result: GameResult) -> bool
+    {
+        if !__valid_name(white) || !__valid_name(black)
+        {
+            return false;
+        }
+
+        let (white, black) = (__clean_name(white), __clean_name(black));
+        let points: f64 = result.points(true);
+        let expected: f64 = self.expected_score(&white, &black);
+
+        let k = |name: &str| if self.is_provisional(name) { 2.0 * self.k_factor } else { self.k_factor };
+        let (k_white, k_black) = (k(&white), k(&black));
+
+        let white_rating: &mut PlayerRating = self.players.entry(white.clone()).or_default();
+        white_rating.elo += k_white * (points - expected);
+        white_rating.games += 1;
+
+        let black_rating: &mut PlayerRating = self.players.entry(black.clone()).or_default();
+        black_rating.elo += k_black * (expected - points);
+        black_rating.games += 1;
+
+        self.period.push((white, black, points));
+        return true;
+    }
+
+    // Rates a finished board, returns false if it's still on or was aborted
+    // or a name can't be used
+    pub fn record_board(&mut self, white: &str, black: &str, board: &ChessBoard) -> bool
+    {
+        if matches!(board.get_state(), ChessState::On | ChessState::Aborted)
+        {
+            return false;
+        }
+
+        return self.record(white, black, GameResult::from_winner(board.get_winner()));
+    }
+
+    // Rates a finished game, the players are its `White` and `Black` tags.
+    // Returns false if it's still on, a tag is missing or a name can't be
+    // used
+    pub fn record_game(&mut self, game: &Game) -> bool
+    {
+        let (white, black) = match (game.get_tag("White"), game.get_tag("Black"))
+        {
+            (Some(white), Some(black)) if !game.is_on() && game.get_board().get_state() != ChessState::Aborted => (white, black),
+            _ => return false,
+        };
+
+        return self.record(white, black, GameResult::from_winner(game.get_winner()));
+    }
+
+    // The games recorded since the last period ended
+    pub fn period_games(&self) -> usize
+    {
+        return self.period.len();
+    }
+
+    // Updates the Glicko-2 ratings with the games of the period and starts
+    // a new one. The deviation of the players who didn't play grows
+    pub fn end_period(&mut self)
+    {
+        let start: BTreeMap<String, PlayerRating> = self.players.clone();
+        for (name, player) in self.players.iter_mut()
+        {
+            let mut games: Vec<(Glicko, f64)> = Vec::new();
+            for (white, black, points) in self.period.iter()
+            {
+                if white == name
+                {
+                    games.push((start[black].glicko, *points));
+                }
+                else if black == name
+                {
+                    games.push((start[white].glicko, 1.0 - points));
+                }
+            }
+
+            player.glicko = player.glicko.update(&games, self.tau);
+        }
+
+        self.period.clear();
+    }
+
+    fn __elo(&self, name: &str) -> f64
+    {
+        return self.get(name).map_or(START_RATING, |player| player.elo);
+    }
+
+    // Writes the ratings as text, one line per setting, player and game
+    // of the current period, with the fields separated by tabs:
+    //
+    //      k	20
+    //      player	<name>	<elo>	<glicko rating>	<deviation>	<volatility>	<games>
+    //      game	<white>	<black>	<points of white>
+    pub fn to_text(&self) -> String
+    {
+        let mut text: String = String::from("# dynchess ratings\n");
+        text.push_str(&format!("k\t{}\ntau\t{}\nprovisional\t{}\n", self.k_factor, self.tau, self.provisional_games));
+
+        for (name, player) in self.players.iter()
+        {
+            let glicko: Glicko = player.glicko;
+            text.push_str(&format!(
+                "player\t{}\t{}\t{}\t{}\t{}\t{}\n",
+                name, player.elo, glicko.get_rating(), glicko.get_deviation(), glicko.get_volatility(), player.games,
+            ));
+        }
+        for (white, black, points) in self.period.iter()
+        {
+            text.push_str(&format!("game\t{}\t{}\t{}\n", white, black, points));
+        }
+
+        return text;
+    }
+
+    // Reads the text of `Ratings::to_text`. Empty lines and lines starting
+    // with `#` are skipped
+    pub fn from_text(text: &str) -> Result<Ratings, RatingsError>
+    {
+        let mut ratings: Ratings = Ratings::new();
+        for (i, line) in text.lines().enumerate()
+        {
+            if line.trim().is_empty() || line.starts_with('#')
+            {
+                continue;
+            }
+
+            if ratings.__read_line(line).is_none()
+            {
+                return Err(RatingsError::InvalidLine(i + 1));
+            }
+        }
+
+        return Ok(ratings);
+    }
+
+    fn __read_line(&mut self, line: &str) -> Option<()>
+    {
+        let fields: Vec<&str> = line.split('\t').collect();
+        match fields.as_slice()
+        {
+            ["k", k] => self.k_factor = __parse_finite(k)?,
+            ["tau", tau] => self.tau = __parse_finite(tau)?,
+            ["provisional", games] => self.provisional_games = games.parse().ok()?,
+            ["player", name, elo, rating, deviation, volatility, games] => {
+                let glicko: Glicko = Glicko::new(__parse_finite(rating)?, __parse_finite(deviation)?, __parse_finite(volatility)?);
+                let player: PlayerRating = PlayerRating { elo: __parse_finite(elo)?, glicko, games: games.parse().ok()? };
+                self.players.insert(name.to_string(), player);
+            },
+            ["game", white, black, points] => {
+                let points: f64 = __parse_finite(points)?;
+                let known: bool = self.players.contains_key(*white) && self.players.contains_key(*black);
+                if !known || !(0.0..=1.0).contains(&points)
+                {
+                    return None;
+                }
+                self.period.push((white.to_string(), black.to_string(), points));
+            },
+            _ => return None,
+        };
+
+        return Some(());
+    }
+
+    pub fn save(&self, path: impl AsRef<Path>) -> Result<(), RatingsError>
+    {
+        return std::fs::write(path, self.to_text()).map_err(|e| RatingsError::Io(e.kind()));
+    }
+
+    pub fn load(path: impl AsRef<Path>) -> Result<Ratings, RatingsError>
+    {
+        let text: String = std::fs::read_to_string(path).map_err(|e| RatingsError::Io(e.kind()))?;
+        return Ratings::from_text(&text);
+    }
+}
+
+impl Default for Ratings
+{
+    fn default() -> Self
+    {
+        return Self::new();
+    }
+}
+
+fn __expected_elo(rating: f64, opponent: f64) -> f64
+{
+    return 1.0 / (1.0 + 10f64.powf((opponent - rating) / 400.0));
+}
+
+fn __clean_name(name: &str) -> String
+{
+    return name.trim().to_string();
+}
+
+// Names are kept on one line without tabs, so they fit in the file
+fn __valid_name(name: &str) -> bool
+{
+    return !name.contains(['\t', '\n', '\r']);
+}
+
+// `NaN` and infinities are numbers to `parse` but not ratings
+fn __parse_finite(text: &str) -> Option<f64>
+{
+    return text.parse::<f64>().ok().filter(|value| value.is_finite());
+}
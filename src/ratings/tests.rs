@@ -0,0 +1,104 @@
+use super::*;
+
+#[test]
+fn test_ratings_glicko_example()
+{
+    // The example from Glickman's paper
+    let player: Glicko = Glicko::new(1500.0, 200.0, 0.06);
+    let games: [(Glicko, f64); 3] = [
+        (Glicko::new(1400.0, 30.0, 0.06), 1.0),
+        (Glicko::new(1550.0, 100.0, 0.06), 0.0),
+        (Glicko::new(1700.0, 300.0, 0.06), 0.0),
+    ];
+
+    let updated: Glicko = player.update(&games, 0.5);
+    assert!((updated.get_rating() - 1464.06).abs() < 0.01);
+    assert!((updated.get_deviation() - 151.52).abs() < 0.01);
+    assert!((updated.get_volatility() - 0.05999).abs() < 0.00001);
+
+    // Without games only the deviation grows
+    let idle: Glicko = player.update(&[], 0.5);
+    assert!(idle.get_rating() == 1500.0 && idle.get_deviation() > 200.0);
+}
+
+#[test]
+fn test_ratings_elo()
+{
+    let mut ratings: Ratings = Ratings::new();
+    ratings.set_k_factor(10.0);
+    ratings.set_provisional_games(1);
+    assert!(ratings.set_player("Alice", 1600.0));
+    assert!(ratings.set_player("Bob", 1400.0));
+    assert!((ratings.expected_score("Alice", "Bob") - 0.7597).abs() < 0.0001);
+    assert!(ratings.is_provisional("Alice") && ratings.is_provisional("Carol"));
+
+    // Provisional players move twice as fast
+    assert!(ratings.record("Alice", "Bob", GameResult::Draw));
+    let change: f64 = 20.0 * (0.5 - 0.7597469);
+    assert!((ratings.get("Alice").unwrap().get_elo() - (1600.0 + change)).abs() < 0.0001);
+    assert!((ratings.get("Bob").unwrap().get_elo() - (1400.0 - change)).abs() < 0.0001);
+    assert!(!ratings.is_provisional("Alice"));
+
+    // Fool's mate
+    let mut board: ChessBoard = ChessBoard::init_position();
+    assert!(!ratings.record_board("Carol", "Bob", &board));
+    for (from, to) in [(0o15, 0o25), (0o64, 0o44), (0o16, 0o36), (0o73, 0o37)]
+    {
+        board.drag(from, to);
+    }
+    assert!(ratings.record_board("Carol", "Bob", &board));
+    assert!(ratings.get("Carol").unwrap().get_elo() < 1500.0);
+
+    let mut game: Game = Game::new();
+    game.set_tag("White", "Alice");
+    game.set_tag("Black", "Carol");
+    assert!(!ratings.record_game(&game));
+    game.resign(false);
+    assert!(ratings.record_game(&game));
+    assert!(ratings.ranking()[0].0 == "Alice");
+
+    // Names that would break the file, and ratings that aren't numbers
+    assert!(!ratings.set_player("Al\tice", 1500.0) && !ratings.set_player("Al\nice", 1500.0));
+    assert!(!ratings.set_player("Dave", f64::NAN) && !ratings.set_player("Dave", f64::INFINITY));
+    assert!(!ratings.record("Alice", "B\tob", GameResult::Draw));
+    assert!(ratings.get("Dave").is_none() && ratings.players().count() == 3);
+}
+
+#[test]
+fn test_ratings_periods_and_file()
+{
+    let mut ratings: Ratings = Ratings::new();
+    ratings.record("Alice", "Bob", GameResult::WhiteWins);
+    ratings.record("Bob", "Alice", GameResult::BlackWins);
+    assert!(ratings.get("Alice").unwrap().get_glicko().get_rating() == 1500.0);
+    assert!(ratings.period_games() == 2);
+
+    // Saved in the middle of the period
+    let text: String = ratings.to_text();
+    let mut loaded: Ratings = Ratings::from_text(&text).unwrap();
+    assert!(loaded.to_text() == text);
+
+    for r in [&mut ratings, &mut loaded]
+    {
+        r.end_period();
+    }
+    let alice: Glicko = ratings.get("Alice").unwrap().get_glicko();
+    assert!(alice.get_rating() > 1500.0 && alice.get_deviation() < 350.0);
+    assert!(loaded.get("Alice") == ratings.get("Alice"));
+    assert!(ratings.glicko_expected_score("Alice", "Bob") > 0.5);
+
+    assert!(matches!(Ratings::from_text("k\t20\nplayer\tAlice\t1500"), Err(RatingsError::InvalidLine(2))));
+    for number in ["NaN", "inf", "-inf"]
+    {
+        let line: String = format!("player\ta\t{}\t1500\t350\t0.06\t0", number);
+        assert!(matches!(Ratings::from_text(&line), Err(RatingsError::InvalidLine(1))));
+        let line: String = format!("player\ta\t1500\t1500\t{}\t0.06\t0", number);
+        assert!(matches!(Ratings::from_text(&line), Err(RatingsError::InvalidLine(1))));
+    }
+
+    let path = std::env::temp_dir().join(format!("dynchess-ratings-{}.txt", std::process::id()));
+    ratings.save(&path).unwrap();
+    assert!(Ratings::load(&path).unwrap().to_text() == ratings.to_text());
+    std::fs::remove_file(&path).unwrap();
+    assert!(Ratings::load(&path).is_err());
+}
@@ -0,0 +1,337 @@
+// Reviewing a game with the built in engine: every position is searched,
+// and every move is judged by how much it lost against the best move.
+//
+// Scores are in centipawns from the side of white, mates as in `engine`.
+// For the losses they're capped at 1000 centipawns, a mate counting as
+// that much. The win probability and the accuracy of a move follow the
+// formulas of lichess:
+//
+//      win%     = 50 + 50 * (2 / (1 + exp(-0.00368208 * cp)) - 1)
+//      accuracy = 103.1668 * exp(-0.04354 * (win% before - win% after)) - 3.1669
+//
+// A move losing 5, 10 or 15 points of win probability is an inaccuracy, a
+// mistake or a blunder. A player's accuracy is the average of their moves
+use super::*;
+use engine::{Engine, SearchLimit, SearchResult, MATE};
+use game::Game;
+
+#[cfg(test)]
+mod tests;
+
+// Scores at least this close to `MATE` are forced mates
+const MATE_SCORES: i32 = 1000;
+// The cap on the scores for the losses
+const MAX_CENTIPAWNS: i32 = 1000;
+// Forced moves looked through before searching, see `__evaluate`
+const MAX_FORCED: u8 = 8;
+
+#[derive(Debug, PartialEq, Eq, PartialOrd, Ord, Clone, Copy)]
+pub enum Judgement
+{
+    Best,
+    Good,
+    Inaccuracy,
+    Mistake,
+    Blunder,
+}
+
+impl Judgement
+{
+    // The NAG of PGN: `?!`, `?` and `??`
+    pub fn nag(self) -> Option<u8>
+    {
+        return match self
+        {
+            Judgement::Best | Judgement::Good => None,
+            Judgement::Inaccuracy => Some(6),
+            Judgement::Mistake => Some(2),
+            Judgement::Blunder => Some(4),
+        };
+    }
+
+    pub fn name(self) -> &'static str
+    {
+        return match self
+        {
+            Judgement::Best => "Best move",
+            Judgement::Good => "Good move",
+            Judgement::Inaccuracy => "Inaccuracy",
+            Judgement::Mistake => "Mistake",
+            Judgement::Blunder => "Blunder",
+        };
+    }
+}
+
+#[derive(Debug, Clone)]
+pub struct MoveReview
+{
+    played: ChessMove,
+    best: Option<ChessMove>,
+    // In SAN, `None` if the position had a single legal move
+    best_san: Option<String>,
+    white: bool,
+    // From the side of white, before and after the move
+    score_before: i32,
+    score_after: i32,
+    centipawn_loss: i32,
+    // Points of win probability lost by the move, from 0 to 100
+    win_loss: f64,
+    accuracy: f64,
+    judgement: Judgement,
+    // The player had a forced mate and the move lets it go
+    missed_mate: bool,
+}
+
+impl MoveReview
+{
+    pub fn get_played(&self) -> ChessMove
+    {
+        return self.played;
+    }
+
+    pub fn get_best(&self) -> Option<ChessMove>
+    {
+        return self.best;
+    }
+
+    // If the move was played by white
+    pub fn is_white(&self) -> bool
+    {
+        return self.white;
+    }
+
+    pub fn get_score_before(&self) -> i32
+    {
+        return self.score_before;
+    }
+
+    pub fn get_score_after(&self) -> i32
+    {
+        return self.score_after;
+    }
+
+    pub fn get_centipawn_loss(&self) -> i32
+    {
+        return self.centipawn_loss;
+    }
+
+    pub fn get_win_loss(&self) -> f64
+    {
+        return self.win_loss;
+    }
+
+    pub fn get_accuracy(&self) -> f64
+    {
+        return self.accuracy;
+    }
+
+    pub fn get_judgement(&self) -> Judgement
+    {
+        return self.judgement;
+    }
+
+    pub fn is_missed_mate(&self) -> bool
+    {
+        return self.missed_mate;
+    }
+
+    // The comment of the move in PGN, like `Mistake (0.35 -> -1.20). Nf3
+    // was best.`, `None` for a good move
+    pub fn comment(&self) -> Option<String>
+    {
+        if self.judgement <= Judgement::Good
+        {
+            return None;
+        }
+
+        let mut comment: String = match self.missed_mate
+        {
+            true => format!("{}, the mate was missed", self.judgement.name()),
+            false => self.judgement.name().to_string(),
+        };
+        comment.push_str(&format!(" ({} -> {}).", format_score(self.score_before), format_score(self.score_after)));
+        if let Some(best) = self.best_san.as_ref()
+        {
+            comment.push_str(&format!(" {} was best.", best));
+        }
+
+        return Some(comment);
+    }
+}
+
+#[derive(Debug, Clone)]
+pub struct GameReview
+{
+    moves: Vec<MoveReview>,
+}
+
+impl GameReview
+{
+    // The reviews in the order of `Game::get_moves`
+    pub fn get_moves(&self) -> &[MoveReview]
+    {
+        return &self.moves;
+    }
+
+    // The average accuracy of the moves of the color, from 0 to 100
+    pub fn accuracy(&self, white: bool) -> f64
+    {
+        return __average(self.moves.iter().filter(|m| m.white == white).map(|m| m.accuracy));
+    }
+
+    pub fn average_centipawn_loss(&self, white: bool) -> f64
+    {
+        return __average(self.moves.iter().filter(|m| m.white == white).map(|m| m.centipawn_loss as f64));
+    }
+
+    // How many moves of the color got the judgement
+    pub fn count(&self, white: bool, judgement: Judgement) -> usize
+    {
+        return self.moves.iter().filter(|m| m.white == white && m.judgement == judgement).count();
+    }
+
+    // Writes the NAGs and the comments of the moves into the game, and the
+    // accuracies in the `WhiteAccuracy` and `BlackAccuracy` tags. The game
+    // has to be the reviewed one
+    pub fn annotate(&self, game: &mut Game)
+    {
+        for (ply, review) in self.moves.iter().enumerate()
+        {
+            if let Some(nag) = review.judgement.nag()
+            {
+                game.add_nag(ply, nag);
+            }
+            if let Some(comment) = review.comment()
+            {
+                game.set_comment(ply, &comment);
+            }
+        }
+
+        game.set_tag("WhiteAccuracy", &format!("{:.1}", self.accuracy(true)));
+        game.set_tag("BlackAccuracy", &format!("{:.1}", self.accuracy(false)));
+    }
+}
+
+// Searches every position of the game and judges every move
+pub fn review_game(game: &Game, engine: &mut Engine, limit: SearchLimit) -> GameReview
+{
+    let mut board: ChessBoard = game.get_start().clone();
+    let (mut score, mut best) = __evaluate(engine, &board, limit, 0);
+    let mut moves: Vec<MoveReview> = Vec::new();
+
+    for mv in game.get_moves()
+    {
+        let white: bool = board.white_turn();
+        let single: bool = board.legal_moves().len() == 1;
+        let best_san: Option<String> = best.filter(|_| !single).map(|best| board.to_san(best));
+
+        let mut after: ChessBoard = board.clone();
+        after.make_move(*mv);
+        let (next_score, next_best) = __evaluate(engine, &after, limit, 0);
+
+        // From the side of white
+        let before: i32 = if white { score } else { -score };
+        let after_score: i32 = if white { -next_score } else { next_score };
+
+        // Losses from the side of the player
+        let sign: i32 = if white { 1 } else { -1 };
+        let centipawn_loss: i32 = i32::max(0, __capped(sign * before) - __capped(sign * after_score));
+        let win_loss: f64 = f64::max(0.0, win_probability(sign * before) - win_probability(sign * after_score));
+        let accuracy: f64 = (103.1668 * f64::exp(-0.04354 * win_loss) - 3.1669).clamp(0.0, 100.0);
+
+        let mut judgement: Judgement = match win_loss
+        {
+            _ if best == Some(*mv) || single => Judgement::Best,
+            loss if loss >= 15.0 => Judgement::Blunder,
+            loss if loss >= 10.0 => Judgement::Mistake,
+            loss if loss >= 5.0 => Judgement::Inaccuracy,
+            _ => Judgement::Good,
+        };
+        let missed_mate: bool = is_mate(sign * before) && sign * before > 0 && !(is_mate(sign * after_score) && sign * after_score > 0);
+        if missed_mate
+        {
+            judgement = judgement.max(Judgement::Mistake);
+        }
+
+        moves.push(MoveReview
+        {
+            played: *mv, best, best_san, white,
+            score_before: before, score_after: after_score,
+            centipawn_loss, win_loss, accuracy, judgement, missed_mate,
+        });
+
+        board = after;
+        score = next_score;
+        best = next_best;
+    }
+
+    return GameReview { moves };
+}
+
+// The chance to win of the side the score is from, from 0 to 100
+pub fn win_probability(score: i32) -> f64
+{
+    let score: f64 = __capped(score) as f64;
+    return 50.0 + 50.0 * (2.0 / (1.0 + f64::exp(-0.00368208 * score)) - 1.0);
+}
+
+pub fn is_mate(score: i32) -> bool
+{
+    return score.abs() >= MATE - MATE_SCORES;
+}
+
+// Like `0.35`, `-1.20` or `#3` for a mate in three moves, `#-3` for being
+// mated
+pub fn format_score(score: i32) -> String
+{
+    if is_mate(score)
+    {
+        let moves: i32 = (MATE - score.abs() + 1) / 2;
+        return format!("#{}{}", if score < 0 { "-" } else { "" }, moves);
+    }
+    return format!("{:.2}", score as f64 / 100.0);
+}
+
+// The score and the best move for the color to move. The engine gives no
+// score when there's a single legal move, so forced moves are played
+// until there's a choice
+fn __evaluate(engine: &mut Engine, board: &ChessBoard, limit: SearchLimit, forced: u8) -> (i32, Option<ChessMove>)
+{
+    let mut board: ChessBoard = board.clone();
+    let moves: Vec<ChessMove> = board.legal_moves();
+
+    if board.get_state() != ChessState::On || moves.is_empty()
+    {
+        let score: i32 = match board.get_winner()
+        {
+            Some(white) if white == board.white_turn() => MATE,
+            Some(_) => -MATE,
+            None => 0,
+        };
+        return (score, None);
+    }
+
+    if moves.len() == 1 && forced < MAX_FORCED
+    {
+        let mut child: ChessBoard = board.clone();
+        child.make_move(moves[0]);
+        let (score, _) = __evaluate(engine, &child, limit, forced + 1);
+        // A mate further away is worth a bit less
+        let score: i32 = if is_mate(score) { -score + score.signum() } else { -score };
+        return (score, Some(moves[0]));
+    }
+
+    let result: SearchResult = engine.search(&board, limit);
+    return (result.get_score(), result.get_move());
+}
+
+fn __capped(score: i32) -> i32
+{
+    return score.clamp(-MAX_CENTIPAWNS, MAX_CENTIPAWNS);
+}
+
+fn __average(values: impl Iterator<Item = f64>) -> f64
+{
+    let (sum, count) = values.fold((0.0, 0), |(sum, count), value| (sum + value, count + 1));
+    return if count == 0 { 0.0 } else { sum / count as f64 };
+}
@@ -0,0 +1,73 @@
+use super::*;
+
+fn play(moves: &str) -> Game
+{
+    let mut game: Game = Game::new();
+    for san in moves.split_whitespace()
+    {
+        assert!(game.play_san(san), "{}", san);
+    }
+    return game;
+}
+
+#[test]
+fn test_review_blunder()
+{
+    let mut engine: Engine = Engine::new();
+    let mut game: Game = play("e4 e5 Qh5 Nc6 Bc4 Nf6 Qxf7#");
+    let review: GameReview = review_game(&game, &mut engine, SearchLimit::Depth(2));
+    let moves: &[MoveReview] = review.get_moves();
+    assert!(moves.len() == 7);
+
+    // Nf6 lets the queen mate
+    assert!(moves[5].get_judgement() == Judgement::Blunder);
+    assert!(!moves[5].is_white());
+    assert!(is_mate(moves[5].get_score_after()) && moves[5].get_score_after() > 0);
+    assert!(moves[5].get_centipawn_loss() > 0);
+    assert!(!moves[5].is_missed_mate());
+    assert!(moves[6].get_judgement() == Judgement::Best);
+    assert!(moves[6].get_score_after() == MATE);
+    assert!(review.count(false, Judgement::Blunder) == 1);
+    assert!(review.accuracy(true) > review.accuracy(false));
+    assert!(review.average_centipawn_loss(false) > review.average_centipawn_loss(true));
+
+    review.annotate(&mut game);
+    assert!(game.get_nags(5) == [4]);
+    assert!(game.get_comment(5).unwrap().starts_with("Blunder ("));
+    assert!(game.get_comment(5).unwrap().ends_with("was best."));
+    assert!(game.get_tag("WhiteAccuracy").is_some());
+    assert!(game.to_pgn().contains("Nf6 $4 {Blunder ("));
+}
+
+#[test]
+fn test_review_missed_mate()
+{
+    let mut engine: Engine = Engine::new();
+    let game: Game = play("e4 e5 Qh5 Nc6 Bc4 Nf6 d3");
+    let review: GameReview = review_game(&game, &mut engine, SearchLimit::Depth(2));
+    let last: &MoveReview = &review.get_moves()[6];
+
+    assert!(last.is_missed_mate());
+    assert!(last.get_judgement() >= Judgement::Mistake);
+    let mut board: ChessBoard = play("e4 e5 Qh5 Nc6 Bc4 Nf6").get_board().clone();
+    assert!(last.get_best() == board.from_san("Qxf7#"));
+    assert!(last.comment().unwrap().contains("Qxf7# was best."));
+}
+
+#[test]
+fn test_review_scores()
+{
+    assert!(win_probability(0) == 50.0);
+    assert!(win_probability(MATE) > 97.0 && win_probability(-MATE) < 3.0);
+    assert!((win_probability(300) + win_probability(-300) - 100.0).abs() < 1e-9);
+
+    assert!(format_score(35) == "0.35");
+    assert!(format_score(-120) == "-1.20");
+    assert!(format_score(MATE - 1) == "#1");
+    assert!(format_score(MATE - 5) == "#3");
+    assert!(format_score(-(MATE - 2)) == "#-1");
+    assert!(is_mate(MATE - 10) && !is_mate(900));
+
+    assert!(Judgement::Blunder.nag() == Some(4));
+    assert!(Judgement::Good.nag().is_none());
+}
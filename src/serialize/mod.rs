@@ -0,0 +1,163 @@
+// `Serialize` and `Deserialize` for the types that don't derive them, with
+// the `serde` feature.
+//
+// `ChessPiece` is written as its FEN letter and `ChessBoard` as its FEN. A
+// `Game` is written as the variant, the start position, the moves in UCI
+// notation, the tags and how it ended, and is read back by playing the
+// moves again, so a stored game can't end up in an illegal position
+use super::*;
+use game::{Game, GameEnd};
+use serde::de::Error;
+use serde::{Deserialize, Deserializer, Serialize, Serializer};
+
+#[cfg(test)]
+mod tests;
+
+impl Serialize for ChessPiece
+{
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error>
+    {
+        return serializer.serialize_char(self.to_fen_char());
+    }
+}
+
+impl<'de> Deserialize<'de> for ChessPiece
+{
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<ChessPiece, D::Error>
+    {
+        let letter: char = char::deserialize(deserializer)?;
+        return fen::__char_to_piece(letter).ok_or_else(|| D::Error::custom(format!("invalid piece {:?}", letter)));
+    }
+}
+
+// The variant isn't part of the FEN, a board is always read back as a
+// board of default chess. Boards of other variants are kept with `Game`
+impl Serialize for ChessBoard
+{
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error>
+    {
+        return serializer.serialize_str(&self.to_fen());
+    }
+}
+
+impl<'de> Deserialize<'de> for ChessBoard
+{
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<ChessBoard, D::Error>
+    {
+        let fen: String = String::deserialize(deserializer)?;
+        return ChessBoard::from_fen(&fen).map_err(|e| D::Error::custom(format!("invalid FEN {:?}: {:?}", fen, e)));
+    }
+}
+
+// How a `ChessPos` is read, it's checked before it becomes one
+#[derive(Deserialize)]
+pub struct ChessPosRecord
+{
+    x: u8,
+    y: u8,
+    w: u8,
+    h: u8,
+}
+
+impl TryFrom<ChessPosRecord> for ChessPos
+{
+    type Error = String;
+
+    fn try_from(record: ChessPosRecord) -> Result<ChessPos, String>
+    {
+        return ChessPos::checked(record.x, record.y, record.w, record.h).ok_or_else(|| {
+            format!("invalid position ({}, {}) on a {}x{} board", record.x, record.y, record.w, record.h)
+        });
+    }
+}
+
+// How a game is stored
+#[derive(Serialize, Deserialize)]
+struct GameRecord
+{
+    variant: String,
+    // `None` when the game started from the start position of the variant
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    fen: Option<String>,
+    // Castling moves are written as the king taking its rook in Chess960
+    #[serde(default)]
+    chess960: bool,
+    moves: Vec<String>,
+    #[serde(default)]
+    tags: Vec<(String, String)>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    end: Option<GameEnd>,
+}
+
+impl Serialize for Game
+{
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error>
+    {
+        let start: &ChessBoard = self.get_start();
+        let fen: String = start.to_fen();
+        let default: bool = fen == ChessBoard::init_variant(start._variant.clone()).to_fen();
+
+        let mut board: ChessBoard = start.clone();
+        let mut moves: Vec<String> = Vec::new();
+        for mv in self.get_moves()
+        {
+            moves.push(board.to_uci(*mv));
+            board.make_move(*mv);
+        }
+
+        let record: GameRecord = GameRecord
+        {
+            variant: start.get_variant().name().to_string(),
+            fen: if default { None } else { Some(fen) },
+            chess960: start.is_chess960(),
+            moves,
+            tags: self.get_tags().to_vec(),
+            end: self.get_end(),
+        };
+
+        return record.serialize(serializer);
+    }
+}
+
+impl<'de> Deserialize<'de> for Game
+{
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Game, D::Error>
+    {
+        let record: GameRecord = GameRecord::deserialize(deserializer)?;
+
+        let variant: Arc<dyn variant::Variant> = variant::from_name(&record.variant)
+            .ok_or_else(|| D::Error::custom(format!("unknown variant {:?}", record.variant)))?;
+        let mut board: ChessBoard = match record.fen
+        {
+            Some(fen) => ChessBoard::from_fen_variant(&fen, variant)
+                .map_err(|e| D::Error::custom(format!("invalid FEN {:?}: {:?}", fen, e)))?,
+            None => ChessBoard::init_variant(variant),
+        };
+        board.set_chess960(record.chess960);
+
+        let mut game: Game = Game::from_board(board);
+        for (name, value) in record.tags.iter()
+        {
+            game.set_tag(name, value);
+        }
+
+        for uci in record.moves.iter()
+        {
+            if !game.play_uci(uci)
+            {
+                return Err(D::Error::custom(format!("illegal move {:?}", uci)));
+            }
+        }
+
+        // Draws by the rules follow from the moves
+        match record.end
+        {
+            Some(GameEnd::Resigned(white)) => game.resign(white),
+            Some(GameEnd::Timeout(white)) => game.timeout(white),
+            Some(GameEnd::Draw) => game.draw(),
+            _ => {},
+        };
+
+        return Ok(game);
+    }
+}
@@ -0,0 +1,90 @@
+use super::*;
+
+#[test]
+fn test_serde_pieces_and_moves()
+{
+    assert_eq!(serde_json::to_string(&ChessPiece::WKnight).unwrap(), "\"N\"");
+    assert_eq!(serde_json::from_str::<ChessPiece>("\"z\"").unwrap(), ChessPiece::BZebra);
+    assert!(serde_json::from_str::<ChessPiece>("\"x\"").is_err());
+
+    // A format that isn't self-describing reads back what it wrote
+    for piece in [ChessPiece::WKnight, ChessPiece::BZebra, ChessPiece::WKing]
+    {
+        let bytes: Vec<u8> = bincode::serialize(&piece).unwrap();
+        assert_eq!(bincode::deserialize::<ChessPiece>(&bytes).unwrap(), piece);
+    }
+    let bytes: Vec<u8> = bincode::serialize(&[ChessPiece::BQueen, ChessPiece::WPawn]).unwrap();
+    assert_eq!(bincode::deserialize::<[ChessPiece; 2]>(&bytes).unwrap(), [ChessPiece::BQueen, ChessPiece::WPawn]);
+    assert!(bincode::deserialize::<ChessPiece>(&bincode::serialize(&'x').unwrap()).is_err());
+
+    let mv: ChessMove = ChessMove::Drag { from: 0o14, to: 0o34, promotion: None };
+    let json: String = serde_json::to_string(&mv).unwrap();
+    assert_eq!(serde_json::from_str::<ChessMove>(&json).unwrap(), mv);
+
+    let pos: ChessPos = ChessPos::from(0o63, false);
+    let json: String = serde_json::to_string(&pos).unwrap();
+    assert!(serde_json::from_str::<ChessPos>(&json).unwrap() == pos);
+    let pos: ChessPos = ChessPos::from_dim(0o377, 16, 16, false);
+    assert!(serde_json::from_str::<ChessPos>(&serde_json::to_string(&pos).unwrap()).unwrap() == pos);
+
+    // Positions off their board, or on a board too big to number
+    assert!(serde_json::from_str::<ChessPos>(r#"{"x":8,"y":0,"w":8,"h":8}"#).is_err());
+    assert!(serde_json::from_str::<ChessPos>(r#"{"x":0,"y":8,"w":8,"h":8}"#).is_err());
+    assert!(serde_json::from_str::<ChessPos>(r#"{"x":0,"y":0,"w":0,"h":8}"#).is_err());
+    assert!(serde_json::from_str::<ChessPos>(r#"{"x":1,"y":1,"w":17,"h":16}"#).is_err());
+    assert!(ChessPos::checked(7, 7, 8, 8).unwrap() == ChessPos::from(0o77, false));
+}
+
+#[test]
+fn test_serde_board()
+{
+    let mut board: ChessBoard = ChessBoard::init_position();
+    board.drag(0o14, 0o34);
+
+    let json: String = serde_json::to_string(&board).unwrap();
+    assert_eq!(json, format!("\"{}\"", board.to_fen()));
+    assert_eq!(serde_json::from_str::<ChessBoard>(&json).unwrap(), board);
+    assert!(serde_json::from_str::<ChessBoard>("\"8/8 w\"").is_err());
+}
+
+#[test]
+fn test_serde_game()
+{
+    let mut game: Game = Game::from_board(ChessBoard::init_variant(variant::from_name("crazyhouse").unwrap()));
+    game.set_tag("White", "Alice");
+    for san in ["e4", "d5", "exd5", "Qxd5", "Nc3"]
+    {
+        assert!(game.play_san(san));
+    }
+    game.resign(false);
+
+    let json: String = serde_json::to_string(&game).unwrap();
+    assert!(json.contains("\"variant\":\"Crazyhouse\""));
+    assert!(json.contains("\"moves\":[\"e2e4\",\"d7d5\",\"e4d5\",\"d8d5\",\"b1c3\"]"));
+    assert!(!json.contains("\"fen\""));
+
+    let copy: Game = serde_json::from_str(&json).unwrap();
+    assert_eq!(copy.get_board(), game.get_board());
+    assert_eq!(copy.get_tag("White"), Some("Alice"));
+    assert_eq!(copy.get_end(), Some(GameEnd::Resigned(false)));
+    assert_eq!(copy.to_pgn(), game.to_pgn());
+
+    let illegal: String = json.replace("b1c3", "b1b3");
+    assert!(serde_json::from_str::<Game>(&illegal).is_err());
+
+    // Chess960 castling is the king taking its rook, which needs the flag
+    let mut board: ChessBoard = ChessBoard::from_fen("4k3/8/8/8/8/8/8/R5KR w HA - 0 1").unwrap();
+    board.set_chess960(true);
+    let mut game: Game = Game::from_board(board);
+    assert!(game.play_uci("g1h1"));
+
+    let json: String = serde_json::to_string(&game).unwrap();
+    assert!(json.contains("\"chess960\":true") && json.contains("\"moves\":[\"g1h1\"]"));
+    let copy: Game = serde_json::from_str(&json).unwrap();
+    assert!(copy.get_start().is_chess960());
+    assert_eq!(copy.get_board(), game.get_board());
+
+    // Records written without the flag are standard chess
+    let copy: Game = serde_json::from_str("{\"variant\":\"Standard\",\"moves\":[\"e2e4\"]}").unwrap();
+    assert!(!copy.get_start().is_chess960());
+}
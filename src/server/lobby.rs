@@ -0,0 +1,374 @@
+// The games on the server and the clients in them, without any sockets:
+// every client gets a channel its messages are sent to, so the lobby can
+// be driven by local stand-ins as well as by connections
+use super::*;
+use std::sync::mpsc::{self, Receiver, Sender};
+
+pub type ClientId = u64;
+
+struct HostedGame
+{
+    game: Game,
+    // The client and the name in each seat, white first
+    seats: [Option<(ClientId, String)>; 2],
+    spectators: Vec<ClientId>,
+    // The time for the game and the increment, `None` without a clock
+    time: Option<(Duration, Duration)>,
+    clocks: [Duration; 2],
+    // When the color to move started thinking, once both seats are taken
+    turn_started: Option<Instant>,
+}
+
+impl HostedGame
+{
+    fn __name(&self, white: bool) -> Option<String>
+    {
+        return self.seats[if white { 0 } else { 1 }].as_ref().map(|(_, name)| name.clone());
+    }
+
+    // Everyone who gets the events of the game
+    fn __audience(&self) -> Vec<ClientId>
+    {
+        let mut audience: Vec<ClientId> = self.seats.iter().flatten().map(|(client, _)| *client).collect();
+        audience.extend(self.spectators.iter());
+        audience.sort();
+        audience.dedup();
+        return audience;
+    }
+
+    fn __clock_message(&self, id: GameId) -> ServerMessage
+    {
+        return ServerMessage::Clock { game: id, white_ms: self.clocks[0].as_millis() as u64, black_ms: self.clocks[1].as_millis() as u64 };
+    }
+}
+
+pub struct Lobby
+{
+    clients: BTreeMap<ClientId, Sender<ServerMessage>>,
+    games: BTreeMap<GameId, HostedGame>,
+    next_client: ClientId,
+    next_game: GameId,
+}
+
+impl Lobby
+{
+    pub fn new() -> Lobby
+    {
+        return Lobby { clients: BTreeMap::new(), games: BTreeMap::new(), next_client: 1, next_game: 1 };
+    }
+
+    // A new client and the messages sent to it
+    pub fn connect(&mut self) -> (ClientId, Receiver<ServerMessage>)
+    {
+        let (sender, receiver) = mpsc::channel::<ServerMessage>();
+        let client: ClientId = self.next_client;
+        self.next_client += 1;
+        self.clients.insert(client, sender);
+        return (client, receiver);
+    }
+
+    // The client left. A player leaving a game that started resigns it, a
+    // game that didn't start yet frees the seat
+    pub fn disconnect(&mut self, client: ClientId)
+    {
+        self.clients.remove(&client);
+
+        let ids: Vec<GameId> = self.games.keys().copied().collect();
+        for id in ids
+        {
+            let hosted: &mut HostedGame = self.games.get_mut(&id).unwrap();
+            hosted.spectators.retain(|c| *c != client);
+
+            let seat: Option<usize> = hosted.seats.iter().position(|s| s.as_ref().is_some_and(|(c, _)| *c == client));
+            match seat
+            {
+                Some(seat) if hosted.turn_started.is_some() && hosted.game.is_on() => {
+                    hosted.game.resign(seat == 0);
+                    self.__broadcast_end(id);
+                },
+                Some(seat) if hosted.turn_started.is_none() => {
+                    hosted.seats[seat] = None;
+                    if hosted.seats.iter().all(|s| s.is_none())
+                    {
+                        self.games.remove(&id);
+                    }
+                },
+                _ => {},
+            };
+        }
+    }
+
+    pub fn handle(&mut self, client: ClientId, message: ClientMessage, now: Instant)
+    {
+        let result: Result<(), String> = match message
+        {
+            ClientMessage::List => {
+                self.send(client, ServerMessage::Games { games: self.list() });
+                Ok(())
+            },
+            ClientMessage::Create { name, side, variant, fen, base_ms, increment_ms } => {
+                let time: Option<(Duration, Duration)> = base_ms.map(|base| (Duration::from_millis(base), Duration::from_millis(increment_ms.unwrap_or(0))));
+                self.__create(client, name, side.unwrap_or(Side::White), variant, fen, time)
+            },
+            ClientMessage::Join { game, name } => self.__join(client, game, name, now),
+            ClientMessage::Spectate { game } => self.__spectate(client, game),
+            ClientMessage::Move { game, mv } => self.__move(client, game, &mv, now),
+            ClientMessage::Resign { game } => self.__resign(client, game),
+        };
+
+        if let Err(message) = result
+        {
+            self.send(client, ServerMessage::Error { message });
+        }
+    }
+
+    // Ends the games where the color to move ran out of time
+    pub fn tick(&mut self, now: Instant)
+    {
+        let mut flagged: Vec<GameId> = Vec::new();
+        for (id, hosted) in self.games.iter_mut()
+        {
+            let started: Instant = match hosted.turn_started
+            {
+                Some(started) if hosted.time.is_some() && hosted.game.is_on() => started,
+                _ => continue,
+            };
+
+            let white: bool = hosted.game.get_board().white_turn();
+            let turn: usize = if white { 0 } else { 1 };
+            if now.saturating_duration_since(started) >= hosted.clocks[turn]
+            {
+                hosted.clocks[turn] = Duration::ZERO;
+                hosted.game.timeout(white);
+                flagged.push(*id);
+            }
+        }
+
+        for id in flagged
+        {
+            let clock: ServerMessage = self.games[&id].__clock_message(id);
+            self.__broadcast(id, clock);
+            self.__broadcast_end(id);
+        }
+    }
+
+    pub fn list(&self) -> Vec<GameInfo>
+    {
+        return self.games.iter().map(|(id, hosted)| GameInfo::new(
+            *id,
+            hosted.__name(true),
+            hosted.__name(false),
+            hosted.game.get_board().get_variant().name().to_string(),
+            hosted.game.result().to_string(),
+        )).collect();
+    }
+
+    fn __create(&mut self, client: ClientId, name: String, side: Side, variant: Option<String>, fen: Option<String>, time: Option<(Duration, Duration)>) -> Result<(), String>
+    {
+        let variant: Arc<dyn variant::Variant> = match variant
+        {
+            Some(name) => variant::from_name(&name).ok_or(format!("unknown variant `{}`", name))?,
+            None => Arc::new(variant::Standard),
+        };
+        let board: ChessBoard = match fen
+        {
+            Some(fen) => ChessBoard::from_fen_variant(&fen, variant).map_err(|e| format!("invalid FEN: {:?}", e))?,
+            None => ChessBoard::init_variant(variant),
+        };
+        if time.is_some_and(|(base, _)| base.is_zero())
+        {
+            return Err("the clock needs some time".to_string());
+        }
+
+        let mut seats: [Option<(ClientId, String)>; 2] = [None, None];
+        seats[if side.is_white() { 0 } else { 1 }] = Some((client, name));
+
+        let id: GameId = self.next_game;
+        self.next_game += 1;
+        self.games.insert(id, HostedGame {
+            game: Game::from_board(board),
+            seats,
+            spectators: Vec::new(),
+            time,
+            clocks: [time.map_or(Duration::ZERO, |(base, _)| base); 2],
+            turn_started: None,
+        });
+
+        self.send(client, ServerMessage::Created { game: id, side });
+        return Ok(());
+    }
+
+    fn __join(&mut self, client: ClientId, id: GameId, name: String, now: Instant) -> Result<(), String>
+    {
+        let hosted: &mut HostedGame = self.games.get_mut(&id).ok_or(format!("no game {}", id))?;
+        let seat: usize = hosted.seats.iter().position(|s| s.is_none()).ok_or(format!("game {} is full", id))?;
+        hosted.seats[seat] = Some((client, name));
+        hosted.turn_started = Some(now);
+        hosted.game.set_tag("White", &hosted.__name(true).unwrap());
+        hosted.game.set_tag("Black", &hosted.__name(false).unwrap());
+
+        let started: ServerMessage = ServerMessage::Started {
+            game: id,
+            white: hosted.__name(true).unwrap(),
+            black: hosted.__name(false).unwrap(),
+            fen: hosted.game.get_board().to_fen(),
+        };
+        let clock: Option<ServerMessage> = hosted.time.map(|_| hosted.__clock_message(id));
+
+        self.send(client, ServerMessage::Joined { game: id, side: Side::from_white(seat == 0) });
+        self.__broadcast(id, started);
+        if let Some(clock) = clock
+        {
+            self.__broadcast(id, clock);
+        }
+        return Ok(());
+    }
+
+    fn __spectate(&mut self, client: ClientId, id: GameId) -> Result<(), String>
+    {
+        let hosted: &mut HostedGame = self.games.get_mut(&id).ok_or(format!("no game {}", id))?;
+        if !hosted.spectators.contains(&client)
+        {
+            hosted.spectators.push(client);
+        }
+
+        let mut board: ChessBoard = hosted.game.get_start().clone();
+        let mut moves: Vec<String> = Vec::new();
+        for mv in hosted.game.get_moves()
+        {
+            moves.push(board.to_uci(*mv));
+            board.make_move(*mv);
+        }
+
+        let message: ServerMessage = ServerMessage::Spectating {
+            game: id,
+            white: hosted.__name(true),
+            black: hosted.__name(false),
+            start_fen: hosted.game.get_start().to_fen(),
+            moves,
+            fen: hosted.game.get_board().to_fen(),
+        };
+        self.send(client, message);
+        return Ok(());
+    }
+
+    fn __move(&mut self, client: ClientId, id: GameId, text: &str, now: Instant) -> Result<(), String>
+    {
+        let hosted: &mut HostedGame = self.games.get_mut(&id).ok_or(format!("no game {}", id))?;
+        let started: Instant = hosted.turn_started.ok_or("the game didn't start yet")?;
+        if !hosted.game.is_on()
+        {
+            return Err("the game is over".to_string());
+        }
+
+        let white: bool = hosted.game.get_board().white_turn();
+        let turn: usize = if white { 0 } else { 1 };
+        if hosted.seats[turn].as_ref().is_none_or(|(c, _)| *c != client)
+        {
+            return Err("it's not your turn".to_string());
+        }
+
+        let mut board: ChessBoard = hosted.game.get_board().clone();
+        let mv: ChessMove = board.from_san(text).or_else(|| board.from_uci(text)).ok_or(format!("illegal move `{}`", text))?;
+
+        if let Some((_, increment)) = hosted.time
+        {
+            let used: Duration = now.saturating_duration_since(started);
+            if used >= hosted.clocks[turn]
+            {
+                // Too late, the clock decides
+                self.tick(now);
+                return Err("out of time".to_string());
+            }
+            hosted.clocks[turn] = hosted.clocks[turn] - used + increment;
+        }
+
+        let san: String = board.to_san(mv);
+        let uci: String = board.to_uci(mv);
+        if !hosted.game.play(mv)
+        {
+            return Err(format!("illegal move `{}`", text));
+        }
+        hosted.turn_started = Some(now);
+
+        let message: ServerMessage = ServerMessage::Move { game: id, uci, san, fen: hosted.game.get_board().to_fen() };
+        let clock: Option<ServerMessage> = hosted.time.map(|_| hosted.__clock_message(id));
+        let over: bool = !hosted.game.is_on();
+
+        self.__broadcast(id, message);
+        if let Some(clock) = clock
+        {
+            self.__broadcast(id, clock);
+        }
+        if over
+        {
+            self.__broadcast_end(id);
+        }
+        return Ok(());
+    }
+
+    fn __resign(&mut self, client: ClientId, id: GameId) -> Result<(), String>
+    {
+        let hosted: &mut HostedGame = self.games.get_mut(&id).ok_or(format!("no game {}", id))?;
+        let seat: usize = hosted.seats.iter().position(|s| s.as_ref().is_some_and(|(c, _)| *c == client)).ok_or("you don't play in this game")?;
+        if !hosted.game.is_on() || hosted.turn_started.is_none()
+        {
+            return Err("the game isn't on".to_string());
+        }
+
+        hosted.game.resign(seat == 0);
+        self.__broadcast_end(id);
+        return Ok(());
+    }
+
+    fn __broadcast_end(&mut self, id: GameId)
+    {
+        let game: &Game = &self.games[&id].game;
+        let message: ServerMessage = ServerMessage::End { game: id, result: game.result().to_string(), reason: end_reason(game) };
+        self.__broadcast(id, message);
+    }
+
+    fn __broadcast(&self, id: GameId, message: ServerMessage)
+    {
+        for client in self.games[&id].__audience()
+        {
+            self.send(client, message.clone());
+        }
+    }
+
+    // Sends the message to the client, if it's still connected
+    pub fn send(&self, client: ClientId, message: ServerMessage)
+    {
+        if let Some(sender) = self.clients.get(&client)
+        {
+            // The client is leaving if its channel is closed
+            let _ = sender.send(message);
+        }
+    }
+}
+
+impl Default for Lobby
+{
+    fn default() -> Self
+    {
+        return Self::new();
+    }
+}
+
+// How a game that's over ended, in a word or two
+pub fn end_reason(game: &Game) -> String
+{
+    let reason: &str = match (game.get_end(), game.get_board().get_state())
+    {
+        (Some(GameEnd::Resigned(_)), _) => "resignation",
+        (Some(GameEnd::Timeout(_)), _) => "time",
+        (Some(GameEnd::Repetition), _) => "repetition",
+        (Some(GameEnd::FiftyMoves), _) => "fifty moves",
+        (Some(GameEnd::Draw), _) => "agreement",
+        (None, ChessState::Checkmate) => "checkmate",
+        (None, ChessState::Stalemate) => "stalemate",
+        (None, ChessState::VariantWin) | (None, ChessState::VariantDraw) => "variant rules",
+        (None, _) => "",
+    };
+    return reason.to_string();
+}
@@ -0,0 +1,191 @@
+// A game server for the LAN, with the `server` feature. Clients create
+// games, join them, play and watch them. Every move is checked by the
+// board and the server keeps the clocks.
+//
+// Clients connect to one port over WebSocket or plain TCP. A connection
+// that starts with an HTTP `GET` is upgraded to WebSocket and sends one
+// message per text frame, any other sends one message per line. The
+// messages are JSON objects with their kind in `type`:
+//
+// From the client:
+//      {"type": "list"}
+//      {"type": "create", "name": "Ann", "side": "white", "variant": "atomic",
+//       "fen": "...", "base_ms": 300000, "increment_ms": 2000}
+//          only the name is needed, the game has no clock without `base_ms`
+//      {"type": "join", "game": 1, "name": "Bob"}
+//      {"type": "spectate", "game": 1}
+//      {"type": "move", "game": 1, "move": "e4"}      in SAN or UCI
+//      {"type": "resign", "game": 1}
+//
+// From the server:
+//      {"type": "games", "games": [{"game": 1, "white": "Ann", "black": null,
+//       "variant": "Standard", "result": "*"}]}
+//      {"type": "created", "game": 1, "side": "white"}
+//      {"type": "joined", "game": 1, "side": "black"}
+//      {"type": "started", "game": 1, "white": "Ann", "black": "Bob", "fen": "..."}
+//      {"type": "spectating", "game": 1, "white": "Ann", "black": "Bob",
+//       "start_fen": "...", "moves": ["e2e4"], "fen": "..."}
+//      {"type": "move", "game": 1, "uci": "e2e4", "san": "e4", "fen": "..."}
+//      {"type": "clock", "game": 1, "white_ms": 299000, "black_ms": 300000}
+//      {"type": "end", "game": 1, "result": "1-0", "reason": "checkmate"}
+//      {"type": "error", "message": "it's not your turn"}
+//
+// The players and the spectators of a game get its `started`, `move`,
+// `clock` and `end` messages. The clock of the color to move starts once
+// both seats are taken, and `clock` gives the time left after every move.
+// A player who disconnects from a game that started resigns it
+use super::*;
+use game::{Game, GameEnd};
+use std::io::{self, BufReader, Write};
+use std::net::{TcpListener, TcpStream, ToSocketAddrs};
+use std::sync::{Mutex, MutexGuard};
+use std::thread;
+use std::time::{Duration, Instant};
+
+mod lobby;
+mod protocol;
+pub mod websocket;
+#[cfg(test)]
+mod tests;
+
+pub use lobby::{end_reason, ClientId, Lobby};
+pub use protocol::{ClientMessage, GameId, GameInfo, ServerMessage, Side};
+
+// How often the clocks are checked for flags
+const TICK: Duration = Duration::from_millis(50);
+
+pub struct Server
+{
+    listener: TcpListener,
+    lobby: Arc<Mutex<Lobby>>,
+}
+
+impl Server
+{
+    pub fn bind(address: impl ToSocketAddrs) -> io::Result<Server>
+    {
+        return Ok(Server { listener: TcpListener::bind(address)?, lobby: Arc::new(Mutex::new(Lobby::new())) });
+    }
+
+    pub fn local_addr(&self) -> io::Result<std::net::SocketAddr>
+    {
+        return self.listener.local_addr();
+    }
+
+    // Serves the clients until the listener fails, every connection on its
+    // own thread
+    pub fn run(self) -> io::Result<()>
+    {
+        let lobby: Arc<Mutex<Lobby>> = self.lobby.clone();
+        thread::spawn(move || loop
+        {
+            thread::sleep(TICK);
+            __lock(&lobby).tick(Instant::now());
+        });
+
+        for stream in self.listener.incoming()
+        {
+            let stream: TcpStream = stream?;
+            let lobby: Arc<Mutex<Lobby>> = self.lobby.clone();
+            thread::spawn(move || __serve_client(stream, lobby));
+        }
+
+        return Ok(());
+    }
+}
+
+// The stream of a client, written by its reading thread for the pongs and
+// by its writing thread for the messages. Every write is whole
+struct SharedWriter(Arc<Mutex<TcpStream>>);
+
+impl Write for SharedWriter
+{
+    fn write(&mut self, buffer: &[u8]) -> io::Result<usize>
+    {
+        self.0.lock().unwrap().write_all(buffer)?;
+        return Ok(buffer.len());
+    }
+
+    fn flush(&mut self) -> io::Result<()>
+    {
+        return self.0.lock().unwrap().flush();
+    }
+}
+
+// A client thread panicking doesn't take the others down
+fn __lock(lobby: &Mutex<Lobby>) -> MutexGuard<'_, Lobby>
+{
+    return lobby.lock().unwrap_or_else(|poisoned| poisoned.into_inner());
+}
+
+fn __serve_client(stream: TcpStream, lobby: Arc<Mutex<Lobby>>) -> io::Result<()>
+{
+    let mut reader: BufReader<TcpStream> = BufReader::new(stream.try_clone()?);
+    let writer: Arc<Mutex<TcpStream>> = Arc::new(Mutex::new(stream));
+
+    let mut first: String = String::new();
+    if websocket::read_line(&mut reader, &mut first)? == 0
+    {
+        return Ok(());
+    }
+
+    let websocket: bool = first.starts_with("GET ");
+    if websocket
+    {
+        websocket::accept(&mut reader, &mut SharedWriter(writer.clone()))?;
+    }
+
+    let (client, messages) = __lock(&lobby).connect();
+    let out: Arc<Mutex<TcpStream>> = writer.clone();
+    thread::spawn(move ||
+    {
+        for message in messages
+        {
+            let text: String = serde_json::to_string(&message).unwrap();
+            let mut stream: SharedWriter = SharedWriter(out.clone());
+            let sent: io::Result<()> = match websocket
+            {
+                true => websocket::write_text(&mut stream, &text),
+                false => writeln!(stream, "{}", text).and_then(|_| stream.flush()),
+            };
+            if sent.is_err() { break; }
+        }
+    });
+
+    let mut pending: Option<String> = (!websocket).then_some(first);
+    loop
+    {
+        let text: String = match pending.take()
+        {
+            Some(text) => text,
+            None if websocket => match websocket::read_message(&mut reader, &mut SharedWriter(writer.clone()))
+            {
+                Ok(Some(message)) => message,
+                _ => break,
+            },
+            None => {
+                // A line that's too long drops the connection as well
+                let mut line: String = String::new();
+                if websocket::read_line(&mut reader, &mut line).unwrap_or(0) == 0
+                {
+                    break;
+                }
+                line
+            },
+        };
+
+        if text.trim().is_empty()
+        {
+            continue;
+        }
+
+        match serde_json::from_str::<ClientMessage>(&text)
+        {
+            Ok(message) => __lock(&lobby).handle(client, message, Instant::now()),
+            Err(error) => __lock(&lobby).send(client, ServerMessage::Error { message: format!("invalid message: {}", error) }),
+        };
+    }
+
+    __lock(&lobby).disconnect(client);
+    return Ok(());
+}
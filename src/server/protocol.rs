@@ -0,0 +1,171 @@
+// The messages of the protocol, see the top of `server` for the details
+use serde::{Deserialize, Serialize};
+
+pub type GameId = u64;
+
+#[derive(Debug, PartialEq, Eq, Clone, Copy, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum Side
+{
+    White,
+    Black,
+}
+
+impl Side
+{
+    pub fn is_white(self) -> bool
+    {
+        return self == Side::White;
+    }
+
+    pub fn from_white(white: bool) -> Side
+    {
+        return if white { Side::White } else { Side::Black };
+    }
+}
+
+// What a client sends
+#[derive(Debug, PartialEq, Eq, Clone, Serialize, Deserialize)]
+#[serde(tag = "type", rename_all = "snake_case")]
+pub enum ClientMessage
+{
+    List,
+    Create
+    {
+        name: String,
+        // White if not given
+        side: Option<Side>,
+        variant: Option<String>,
+        fen: Option<String>,
+        // Without it the game has no clock
+        base_ms: Option<u64>,
+        increment_ms: Option<u64>,
+    },
+    Join
+    {
+        game: GameId,
+        name: String,
+    },
+    Spectate
+    {
+        game: GameId,
+    },
+    Move
+    {
+        game: GameId,
+        // In SAN or UCI
+        #[serde(rename = "move")]
+        mv: String,
+    },
+    Resign
+    {
+        game: GameId,
+    },
+}
+
+#[derive(Debug, PartialEq, Eq, Clone, Serialize, Deserialize)]
+pub struct GameInfo
+{
+    game: GameId,
+    white: Option<String>,
+    black: Option<String>,
+    variant: String,
+    result: String,
+}
+
+impl GameInfo
+{
+    pub fn new(game: GameId, white: Option<String>, black: Option<String>, variant: String, result: String) -> GameInfo
+    {
+        return GameInfo { game, white, black, variant, result };
+    }
+
+    pub fn get_game(&self) -> GameId
+    {
+        return self.game;
+    }
+
+    // `None` while the seat is free
+    pub fn get_white(&self) -> Option<&str>
+    {
+        return self.white.as_deref();
+    }
+
+    pub fn get_black(&self) -> Option<&str>
+    {
+        return self.black.as_deref();
+    }
+
+    pub fn get_variant(&self) -> &str
+    {
+        return &self.variant;
+    }
+
+    // `*` while the game is on
+    pub fn get_result(&self) -> &str
+    {
+        return &self.result;
+    }
+}
+
+// What the server sends
+#[derive(Debug, PartialEq, Eq, Clone, Serialize, Deserialize)]
+#[serde(tag = "type", rename_all = "snake_case")]
+pub enum ServerMessage
+{
+    Games
+    {
+        games: Vec<GameInfo>,
+    },
+    Created
+    {
+        game: GameId,
+        side: Side,
+    },
+    Joined
+    {
+        game: GameId,
+        side: Side,
+    },
+    // To the players and the spectators once both seats are taken
+    Started
+    {
+        game: GameId,
+        white: String,
+        black: String,
+        fen: String,
+    },
+    // The game so far, to a new spectator
+    Spectating
+    {
+        game: GameId,
+        white: Option<String>,
+        black: Option<String>,
+        start_fen: String,
+        moves: Vec<String>,
+        fen: String,
+    },
+    Move
+    {
+        game: GameId,
+        uci: String,
+        san: String,
+        fen: String,
+    },
+    Clock
+    {
+        game: GameId,
+        white_ms: u64,
+        black_ms: u64,
+    },
+    End
+    {
+        game: GameId,
+        result: String,
+        reason: String,
+    },
+    Error
+    {
+        message: String,
+    },
+}
@@ -0,0 +1,228 @@
+use super::*;
+use std::io::{BufRead, Read};
+use std::sync::mpsc::Receiver;
+
+// Everything the client got so far
+fn drain(messages: &Receiver<ServerMessage>) -> Vec<ServerMessage>
+{
+    return messages.try_iter().collect();
+}
+
+fn create(name: &str) -> ClientMessage
+{
+    return ClientMessage::Create { name: name.to_string(), side: None, variant: None, fen: None, base_ms: Some(60_000), increment_ms: Some(1_000) };
+}
+
+fn play(game: GameId, mv: &str) -> ClientMessage
+{
+    return ClientMessage::Move { game, mv: mv.to_string() };
+}
+
+#[test]
+fn test_server_lobby()
+{
+    let mut lobby: Lobby = Lobby::new();
+    let (ann, ann_messages) = lobby.connect();
+    let (bob, bob_messages) = lobby.connect();
+    let (eve, eve_messages) = lobby.connect();
+    let start: Instant = Instant::now();
+
+    lobby.handle(ann, create("Ann"), start);
+    assert!(drain(&ann_messages) == [ServerMessage::Created { game: 1, side: Side::White }]);
+    lobby.handle(ann, play(1, "e4"), start);
+    assert!(matches!(drain(&ann_messages)[..], [ServerMessage::Error { .. }]));
+
+    lobby.handle(eve, ClientMessage::Spectate { game: 1 }, start);
+    lobby.handle(bob, ClientMessage::Join { game: 1, name: "Bob".to_string() }, start);
+    let bob_got: Vec<ServerMessage> = drain(&bob_messages);
+    assert!(bob_got[0] == ServerMessage::Joined { game: 1, side: Side::Black });
+    assert!(matches!(&bob_got[1], ServerMessage::Started { white, black, .. } if white == "Ann" && black == "Bob"));
+    assert!(bob_got[2] == ServerMessage::Clock { game: 1, white_ms: 60_000, black_ms: 60_000 });
+    assert!(drain(&ann_messages).len() == 2 && drain(&eve_messages).len() == 3);
+
+    // Only the player to move, only legal moves
+    lobby.handle(bob, play(1, "e5"), start);
+    lobby.handle(ann, play(1, "e5"), start);
+    assert!(matches!(drain(&bob_messages)[..], [ServerMessage::Error { .. }]));
+    assert!(matches!(drain(&ann_messages)[..], [ServerMessage::Error { .. }]));
+
+    lobby.handle(ann, play(1, "e2e4"), start + Duration::from_secs(5));
+    let eve_got: Vec<ServerMessage> = drain(&eve_messages);
+    assert!(matches!(&eve_got[0], ServerMessage::Move { san, uci, .. } if san == "e4" && uci == "e2e4"));
+    assert!(eve_got[1] == ServerMessage::Clock { game: 1, white_ms: 56_000, black_ms: 60_000 });
+
+    // A late spectator gets the game so far
+    let (tom, tom_messages) = lobby.connect();
+    lobby.handle(tom, ClientMessage::Spectate { game: 1 }, start);
+    assert!(matches!(&drain(&tom_messages)[0], ServerMessage::Spectating { moves, .. } if moves == &["e2e4"]));
+
+    // Black runs out of time
+    assert!(drain(&bob_messages).len() == 2);
+    lobby.tick(start + Duration::from_secs(30));
+    assert!(drain(&bob_messages).is_empty());
+    lobby.tick(start + Duration::from_secs(66));
+    let bob_got: Vec<ServerMessage> = drain(&bob_messages);
+    assert!(bob_got[0] == ServerMessage::Clock { game: 1, white_ms: 56_000, black_ms: 0 });
+    assert!(bob_got[1] == ServerMessage::End { game: 1, result: "1-0".to_string(), reason: "time".to_string() });
+
+    assert!(drain(&tom_messages) == bob_got);
+
+    lobby.handle(tom, ClientMessage::List, start);
+    let games: Vec<ServerMessage> = drain(&tom_messages);
+    assert!(matches!(&games[..], [ServerMessage::Games { games }] if games[0].get_result() == "1-0" && games[0].get_black() == Some("Bob")));
+}
+
+#[test]
+fn test_server_lobby_endings()
+{
+    let mut lobby: Lobby = Lobby::new();
+    let (ann, ann_messages) = lobby.connect();
+    let (bob, bob_messages) = lobby.connect();
+    let now: Instant = Instant::now();
+
+    // Fool's mate without a clock
+    let untimed: ClientMessage = ClientMessage::Create { name: "Ann".to_string(), side: Some(Side::Black), variant: None, fen: None, base_ms: None, increment_ms: None };
+    lobby.handle(ann, untimed, now);
+    lobby.handle(bob, ClientMessage::Join { game: 1, name: "Bob".to_string() }, now);
+    for (player, mv) in [(bob, "f3"), (ann, "e5"), (bob, "g4"), (ann, "Qh4#")]
+    {
+        lobby.handle(player, play(1, mv), now);
+    }
+    let ann_got: Vec<ServerMessage> = drain(&ann_messages);
+    assert!(ann_got.last() == Some(&ServerMessage::End { game: 1, result: "0-1".to_string(), reason: "checkmate".to_string() }));
+    assert!(!ann_got.iter().any(|m| matches!(m, ServerMessage::Clock { .. } | ServerMessage::Error { .. })));
+
+    // A game that didn't start goes away with its player, one that started
+    // is resigned
+    lobby.handle(ann, create("Ann"), now);
+    lobby.handle(bob, create("Bob"), now);
+    lobby.handle(ann, ClientMessage::Join { game: 3, name: "Ann".to_string() }, now);
+    drain(&bob_messages);
+    lobby.disconnect(ann);
+    assert!(lobby.list().iter().map(|g| g.get_game()).collect::<Vec<GameId>>() == [1, 3]);
+    assert!(drain(&bob_messages) == [ServerMessage::End { game: 3, result: "1-0".to_string(), reason: "resignation".to_string() }]);
+
+    lobby.handle(bob, ClientMessage::Create { name: "Bob".to_string(), side: None, variant: Some("nope".to_string()), fen: None, base_ms: None, increment_ms: None }, now);
+    assert!(matches!(&drain(&bob_messages)[..], [ServerMessage::Error { message }] if message.contains("nope")));
+}
+
+#[test]
+fn test_server_websocket_frames()
+{
+    // The example of RFC 6455
+    assert!(websocket::accept_key("dGhlIHNhbXBsZSBub25jZQ==") == "s3pPLMBiTxaQ9kYGzzhZRbK+xOo=");
+
+    // A masked "Hello" from the RFC, after a ping
+    let input: Vec<u8> = [
+        &[0x89, 0x00][..],
+        &[0x81, 0x85, 0x37, 0xfa, 0x21, 0x3d, 0x7f, 0x9f, 0x4d, 0x51, 0x58][..],
+    ].concat();
+    let mut written: Vec<u8> = Vec::new();
+    let message: Option<String> = websocket::read_message(&mut &input[..], &mut written).unwrap();
+    assert!(message.as_deref() == Some("Hello"));
+    assert!(written == [0x8A, 0x00]);
+
+    let mut frame: Vec<u8> = Vec::new();
+    websocket::write_text(&mut frame, &"x".repeat(300)).unwrap();
+    assert!(frame[..4] == [0x81, 126, 0x01, 0x2C]);
+    assert!(websocket::read_frame(&mut &frame[..]).unwrap() == (true, 0x1, vec![b'x'; 300]));
+
+    let mut closed: Vec<u8> = Vec::new();
+    assert!(websocket::read_message(&mut &[0x88, 0x00][..], &mut closed).unwrap().is_none());
+}
+
+// A client stand-in for WebSocket: sends masked frames, reads the frames
+// of the server
+fn websocket_client(address: std::net::SocketAddr) -> (TcpStream, BufReader<TcpStream>)
+{
+    let mut stream: TcpStream = TcpStream::connect(address).unwrap();
+    write!(stream, "GET / HTTP/1.1\r\nHost: localhost\r\nUpgrade: websocket\r\nConnection: Upgrade\r\n\
+        Sec-WebSocket-Key: dGhlIHNhbXBsZSBub25jZQ==\r\nSec-WebSocket-Version: 13\r\n\r\n").unwrap();
+
+    let mut reader: BufReader<TcpStream> = BufReader::new(stream.try_clone().unwrap());
+    let mut response: String = String::new();
+    while !response.ends_with("\r\n\r\n")
+    {
+        let mut line: String = String::new();
+        reader.read_line(&mut line).unwrap();
+        response.push_str(&line);
+    }
+    assert!(response.starts_with("HTTP/1.1 101") && response.contains("s3pPLMBiTxaQ9kYGzzhZRbK+xOo="));
+
+    return (stream, reader);
+}
+
+fn websocket_send(stream: &mut TcpStream, message: &ClientMessage)
+{
+    let text: String = serde_json::to_string(message).unwrap();
+    let mask: [u8; 4] = [1, 2, 3, 4];
+    let mut frame: Vec<u8> = vec![0x81, 0x80 | text.len() as u8];
+    frame.extend(mask);
+    frame.extend(text.bytes().enumerate().map(|(i, b)| b ^ mask[i % 4]));
+    stream.write_all(&frame).unwrap();
+}
+
+fn websocket_receive(reader: &mut BufReader<TcpStream>) -> ServerMessage
+{
+    let (_, _, payload) = websocket::read_frame(reader).unwrap();
+    return serde_json::from_slice(&payload).unwrap();
+}
+
+fn tcp_receive(reader: &mut BufReader<TcpStream>) -> ServerMessage
+{
+    let mut line: String = String::new();
+    reader.read_line(&mut line).unwrap();
+    return serde_json::from_str(&line).unwrap();
+}
+
+#[test]
+fn test_server_sockets()
+{
+    let server: Server = Server::bind("127.0.0.1:0").unwrap();
+    let address: std::net::SocketAddr = server.local_addr().unwrap();
+    thread::spawn(move || server.run());
+
+    // Ann over WebSocket, Bob over plain TCP
+    let (mut ann, mut ann_reader) = websocket_client(address);
+    let mut bob: TcpStream = TcpStream::connect(address).unwrap();
+    let mut bob_reader: BufReader<TcpStream> = BufReader::new(bob.try_clone().unwrap());
+    for reader in [ann_reader.get_ref(), bob_reader.get_ref()]
+    {
+        reader.set_read_timeout(Some(Duration::from_secs(5))).unwrap();
+    }
+
+    websocket_send(&mut ann, &ClientMessage::Create { name: "Ann".to_string(), side: None, variant: None, fen: None, base_ms: None, increment_ms: None });
+    assert!(websocket_receive(&mut ann_reader) == ServerMessage::Created { game: 1, side: Side::White });
+
+    writeln!(bob, "not json").unwrap();
+    assert!(matches!(tcp_receive(&mut bob_reader), ServerMessage::Error { .. }));
+    writeln!(bob, r#"{{"type": "join", "game": 1, "name": "Bob"}}"#).unwrap();
+    assert!(tcp_receive(&mut bob_reader) == ServerMessage::Joined { game: 1, side: Side::Black });
+    assert!(matches!(tcp_receive(&mut bob_reader), ServerMessage::Started { .. }));
+    assert!(matches!(websocket_receive(&mut ann_reader), ServerMessage::Started { .. }));
+
+    websocket_send(&mut ann, &play(1, "Nf3"));
+    assert!(matches!(tcp_receive(&mut bob_reader), ServerMessage::Move { uci, .. } if uci == "g1f3"));
+    assert!(matches!(websocket_receive(&mut ann_reader), ServerMessage::Move { .. }));
+
+    // Ann leaves, so she resigns
+    drop(ann);
+    drop(ann_reader);
+    assert!(tcp_receive(&mut bob_reader) == ServerMessage::End { game: 1, result: "0-1".to_string(), reason: "resignation".to_string() });
+
+    let mut rest: Vec<u8> = Vec::new();
+    bob.shutdown(std::net::Shutdown::Write).unwrap();
+    let _ = bob_reader.read_to_end(&mut rest);
+
+    // A line longer than a message can be drops the connection, without
+    // waiting for its end
+    let mut carl: TcpStream = TcpStream::connect(address).unwrap();
+    let mut carl_reader: BufReader<TcpStream> = BufReader::new(carl.try_clone().unwrap());
+    carl_reader.get_ref().set_read_timeout(Some(Duration::from_secs(5))).unwrap();
+    writeln!(carl, r#"{{"type": "list"}}"#).unwrap();
+    assert!(matches!(tcp_receive(&mut carl_reader), ServerMessage::Games { .. }));
+    carl.write_all(&vec![b' '; (1 << 20) + 1]).unwrap();
+    let mut rest: Vec<u8> = Vec::new();
+    let closed: io::Result<usize> = carl_reader.read_to_end(&mut rest);
+    assert!(matches!(closed, Ok(0)) || closed.is_err_and(|e| e.kind() == io::ErrorKind::ConnectionReset));
+}
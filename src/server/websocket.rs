@@ -0,0 +1,266 @@
+// Just enough of RFC 6455 for the server: the opening handshake and text
+// frames, with the pings answered and fragmented messages put together.
+// SHA-1 and base64 are only needed for `Sec-WebSocket-Accept`
+use std::io::{self, BufRead, Read, Write};
+
+const GUID: &str = "258EAFA5-E914-47DA-95CA-C5AB0DC85B11";
+// Longer messages close the connection
+const MAX_MESSAGE: usize = 1 << 20;
+
+const OP_CONTINUATION: u8 = 0x0;
+const OP_TEXT: u8 = 0x1;
+const OP_BINARY: u8 = 0x2;
+const OP_CLOSE: u8 = 0x8;
+const OP_PING: u8 = 0x9;
+const OP_PONG: u8 = 0xA;
+
+// Same as `BufRead::read_line`, but fails once the line gets longer than
+// `MAX_MESSAGE`. Used for the upgrade request and for the messages of
+// plain TCP clients as well
+pub fn read_line(reader: &mut impl BufRead, line: &mut String) -> io::Result<usize>
+{
+    let read: usize = reader.by_ref().take(MAX_MESSAGE as u64 + 1).read_line(line)?;
+    if read > MAX_MESSAGE && !line.ends_with('\n')
+    {
+        return Err(io::Error::new(io::ErrorKind::InvalidData, "the line is too long"));
+    }
+
+    return Ok(read);
+}
+
+// Reads the rest of the upgrade request after its first line and answers
+// it. Fails if it isn't a WebSocket upgrade
+pub fn accept(reader: &mut impl BufRead, writer: &mut impl Write) -> io::Result<()>
+{
+    let mut key: Option<String> = None;
+    loop
+    {
+        let mut line: String = String::new();
+        if read_line(reader, &mut line)? == 0
+        {
+            return Err(io::Error::new(io::ErrorKind::UnexpectedEof, "the request ended early"));
+        }
+
+        let line: &str = line.trim();
+        if line.is_empty()
+        {
+            break;
+        }
+        if let Some((name, value)) = line.split_once(':')
+        {
+            if name.trim().eq_ignore_ascii_case("sec-websocket-key")
+            {
+                key = Some(value.trim().to_string());
+            }
+        }
+    }
+
+    let key: String = match key
+    {
+        Some(key) => key,
+        None => {
+            writer.write_all(b"HTTP/1.1 400 Bad Request\r\nContent-Length: 0\r\n\r\n")?;
+            return Err(io::Error::new(io::ErrorKind::InvalidData, "not a WebSocket upgrade"));
+        },
+    };
+
+    write!(
+        writer,
+        "HTTP/1.1 101 Switching Protocols\r\nUpgrade: websocket\r\nConnection: Upgrade\r\nSec-WebSocket-Accept: {}\r\n\r\n",
+        accept_key(&key),
+    )?;
+    return writer.flush();
+}
+
+// The `Sec-WebSocket-Accept` answer to the key of the client
+pub fn accept_key(key: &str) -> String
+{
+    return base64(&sha1(format!("{}{}", key, GUID).as_bytes()));
+}
+
+// Reads the next text message, answering pings on the way. `None` once
+// the client closes the connection
+pub fn read_message(reader: &mut impl Read, writer: &mut impl Write) -> io::Result<Option<String>>
+{
+    let mut message: Vec<u8> = Vec::new();
+    loop
+    {
+        let (fin, opcode, payload) = read_frame(reader)?;
+        match opcode
+        {
+            OP_CLOSE => {
+                let _ = write_frame(writer, OP_CLOSE, &payload);
+                return Ok(None);
+            },
+            OP_PING => write_frame(writer, OP_PONG, &payload)?,
+            OP_PONG => {},
+            OP_TEXT | OP_BINARY | OP_CONTINUATION => {
+                message.extend(payload);
+                if message.len() > MAX_MESSAGE
+                {
+                    return Err(io::Error::new(io::ErrorKind::InvalidData, "the message is too long"));
+                }
+                if fin
+                {
+                    return String::from_utf8(message).map(Some).map_err(|_| io::Error::new(io::ErrorKind::InvalidData, "the message isn't UTF-8"));
+                }
+            },
+            _ => return Err(io::Error::new(io::ErrorKind::InvalidData, "unknown opcode")),
+        };
+    }
+}
+
+// One frame: if it's the last of its message, the opcode and the payload,
+// unmasked
+pub fn read_frame(reader: &mut impl Read) -> io::Result<(bool, u8, Vec<u8>)>
+{
+    let mut head: [u8; 2] = [0; 2];
+    reader.read_exact(&mut head)?;
+
+    let fin: bool = head[0] & 0x80 != 0;
+    let opcode: u8 = head[0] & 0x0F;
+    let masked: bool = head[1] & 0x80 != 0;
+    let length: u64 = match head[1] & 0x7F
+    {
+        126 => {
+            let mut bytes: [u8; 2] = [0; 2];
+            reader.read_exact(&mut bytes)?;
+            u16::from_be_bytes(bytes) as u64
+        },
+        127 => {
+            let mut bytes: [u8; 8] = [0; 8];
+            reader.read_exact(&mut bytes)?;
+            u64::from_be_bytes(bytes)
+        },
+        length => length as u64,
+    };
+    if length > MAX_MESSAGE as u64
+    {
+        return Err(io::Error::new(io::ErrorKind::InvalidData, "the frame is too long"));
+    }
+
+    let mut mask: [u8; 4] = [0; 4];
+    if masked
+    {
+        reader.read_exact(&mut mask)?;
+    }
+
+    let mut payload: Vec<u8> = vec![0; length as usize];
+    reader.read_exact(&mut payload)?;
+    for (i, byte) in payload.iter_mut().enumerate()
+    {
+        *byte ^= mask[i % 4];
+    }
+
+    return Ok((fin, opcode, payload));
+}
+
+// Writes a whole message as one frame. Frames of the server aren't masked
+pub fn write_frame(writer: &mut impl Write, opcode: u8, payload: &[u8]) -> io::Result<()>
+{
+    let mut frame: Vec<u8> = vec![0x80 | opcode];
+    match payload.len()
+    {
+        length if length < 126 => frame.push(length as u8),
+        length if length <= u16::MAX as usize => {
+            frame.push(126);
+            frame.extend((length as u16).to_be_bytes());
+        },
+        length => {
+            frame.push(127);
+            frame.extend((length as u64).to_be_bytes());
+        },
+    };
+
+    frame.extend(payload);
+    writer.write_all(&frame)?;
+    return writer.flush();
+}
+
+pub fn write_text(writer: &mut impl Write, text: &str) -> io::Result<()>
+{
+    return write_frame(writer, OP_TEXT, text.as_bytes());
+}
+
+fn sha1(data: &[u8]) -> [u8; 20]
+{
+    let mut h: [u32; 5] = [0x67452301, 0xEFCDAB89, 0x98BADCFE, 0x10325476, 0xC3D2E1F0];
+
+    let mut message: Vec<u8> = data.to_vec();
+    message.push(0x80);
+    while message.len() % 64 != 56
+    {
+        message.push(0);
+    }
+    message.extend((data.len() as u64 * 8).to_be_bytes());
+
+    for chunk in message.chunks(64)
+    {
+        let mut w: [u32; 80] = [0; 80];
+        for i in 0..16
+        {
+            w[i] = u32::from_be_bytes([chunk[4*i], chunk[4*i + 1], chunk[4*i + 2], chunk[4*i + 3]]);
+        }
+        for i in 16..80
+        {
+            w[i] = (w[i-3] ^ w[i-8] ^ w[i-14] ^ w[i-16]).rotate_left(1);
+        }
+
+        let [mut a, mut b, mut c, mut d, mut e] = h;
+        for (i, word) in w.iter().enumerate()
+        {
+            let (f, k) = match i
+            {
+                0..=19 => ((b & c) | (!b & d), 0x5A827999),
+                20..=39 => (b ^ c ^ d, 0x6ED9EBA1),
+                40..=59 => ((b & c) | (b & d) | (c & d), 0x8F1BBCDC),
+                _ => (b ^ c ^ d, 0xCA62C1D6),
+            };
+
+            let temp: u32 = a.rotate_left(5).wrapping_add(f).wrapping_add(e).wrapping_add(k).wrapping_add(*word);
+            e = d;
+            d = c;
+            c = b.rotate_left(30);
+            b = a;
+            a = temp;
+        }
+
+        for (value, add) in h.iter_mut().zip([a, b, c, d, e])
+        {
+            *value = value.wrapping_add(add);
+        }
+    }
+
+    let mut digest: [u8; 20] = [0; 20];
+    for (i, value) in h.iter().enumerate()
+    {
+        digest[4*i..4*i + 4].copy_from_slice(&value.to_be_bytes());
+    }
+    return digest;
+}
+
+fn base64(data: &[u8]) -> String
+{
+    const ALPHABET: &[u8; 64] = b"ABCDEFGHIJKLMNOPQRSTUVWXYZabcdefghijklmnopqrstuvwxyz0123456789+/";
+
+    let mut text: String = String::new();
+    for chunk in data.chunks(3)
+    {
+        let bytes: [u8; 3] = [chunk[0], *chunk.get(1).unwrap_or(&0), *chunk.get(2).unwrap_or(&0)];
+        let n: u32 = (bytes[0] as u32) << 16 | (bytes[1] as u32) << 8 | bytes[2] as u32;
+
+        for i in 0..4
+        {
+            if i <= chunk.len()
+            {
+                text.push(ALPHABET[(n >> (18 - 6*i) & 0x3F) as usize] as char);
+            }
+            else
+            {
+                text.push('=');
+            }
+        }
+    }
+
+    return text;
+}
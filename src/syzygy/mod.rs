@@ -0,0 +1,169 @@
+// Syzygy endgame tablebases, probed with `ChessBoard::probe_wdl` and
+// `ChessBoard::probe_dtz`.
+//
+// Only the interface is here so far: `Syzygy::open` finds the tables of a
+// directory by their file names (`KQvK.rtbw` for the WDL table of king and
+// queen against king, `KQvK.rtbz` for its DTZ table), but the decoder of the
+// files isn't written, so no table is loaded and every probe returns `None`,
+// the same as for a position without a table. Callers fall back to their
+// own search either way
+use super::*;
+use match_runner::{Tablebase, Wdl};
+use std::io;
+use std::path::Path;
+
+#[cfg(test)]
+mod tests;
+
+pub struct Syzygy
+{
+    // The materials with a WDL table in the directory, sorted
+    wdl: Vec<String>,
+    // The materials with a DTZ table in the directory, sorted
+    dtz: Vec<String>,
+}
+
+impl Syzygy
+{
+    // No tables at all
+    pub fn new() -> Syzygy
+    {
+        return Syzygy { wdl: Vec::new(), dtz: Vec::new() };
+    }
+
+    // The tables in a directory, an error if it can't be read. Files that
+    // aren't Syzygy tables are skipped
+    pub fn open(path: &Path) -> io::Result<Syzygy>
+    {
+        let mut syzygy: Syzygy = Syzygy::new();
+        for entry in std::fs::read_dir(path)?
+        {
+            let name: String = entry?.file_name().to_string_lossy().into_owned();
+            let (material, extension) = match name.rsplit_once('.')
+            {
+                Some(split) => split,
+                None => continue,
+            };
+
+            if !Self::__is_material(material)
+            {
+                continue;
+            }
+
+            match extension
+            {
+                "rtbw" => syzygy.wdl.push(material.to_string()),
+                "rtbz" => syzygy.dtz.push(material.to_string()),
+                _ => {},
+            };
+        }
+
+        syzygy.wdl.sort();
+        syzygy.dtz.sort();
+        return Ok(syzygy);
+    }
+
+    // Like `KQvK` or `KRPvKR`: the pieces of both sides, each starting with
+    // its king
+    fn __is_material(material: &str) -> bool
+    {
+        return match material.split_once('v')
+        {
+            Some((white, black)) => [white, black].iter().all(|side| {
+                side.starts_with('K') && side[1..].chars().all(|c| "QRBNP".contains(c))
+            }),
+            None => false,
+        };
+    }
+
+    pub fn get_wdl_tables(&self) -> &[String]
+    {
+        return &self.wdl;
+    }
+
+    pub fn get_dtz_tables(&self) -> &[String]
+    {
+        return &self.dtz;
+    }
+
+    // The number of pieces, kings included, of the largest WDL table
+    pub fn get_max_pieces(&self) -> usize
+    {
+        return self.wdl.iter().map(|material| material.len()-1).max().unwrap_or(0);
+    }
+
+    // Whether the tables could have the position at all: standard chess on
+    // an 8x8 board, no castling rights and not more pieces than the largest
+    // table
+    fn __covers(&self, board: &ChessBoard) -> bool
+    {
+        if board.get_variant().name() != "Standard" || board.get_state() != ChessState::On
+        {
+            return false;
+        }
+
+        if board.width() != 8 || board.height() != 8
+        {
+            return false;
+        }
+
+        if board.castling_rights().iter().any(|rook| rook.is_some())
+        {
+            return false;
+        }
+
+        let pieces: usize = board.get_squares().iter().filter(|piece| !piece.is_empty()).count();
+        return pieces <= self.get_max_pieces();
+    }
+}
+
+impl Default for Syzygy
+{
+    fn default() -> Self
+    {
+        return Syzygy::new();
+    }
+}
+
+impl Tablebase for Syzygy
+{
+    fn probe(&self, board: &ChessBoard) -> Option<Wdl>
+    {
+        return board.probe_wdl(self);
+    }
+}
+
+impl ChessBoard
+{
+    // Win, draw or loss for the color to move with perfect play, `None` if
+    // the tables don't have the position. Cursed wins and blessed losses,
+    // which the 50 move rule turns into draws, count as draws.
+    // No table is loaded yet, see the `syzygy` module, so this is always
+    // `None`
+    pub fn probe_wdl(&self, tables: &Syzygy) -> Option<Wdl>
+    {
+        if !tables.__covers(self)
+        {
+            return None;
+        }
+
+        // The decoder of the table files goes here
+        return None;
+    }
+
+    // The distance in plies to the next capture or pawn move with perfect
+    // play, positive when the color to move wins and negative when it loses,
+    // `None` if the tables don't have the position.
+    // No table is loaded yet, see the `syzygy` module, so this is always
+    // `None`
+    pub fn probe_dtz(&self, tables: &Syzygy) -> Option<i32>
+    {
+        if !tables.__covers(self) || tables.dtz.is_empty()
+        {
+            return None;
+        }
+
+        // The decoder of the table files goes here
+        return None;
+    }
+}
@@ -0,0 +1,33 @@
+use super::*;
+
+#[test]
+fn test_syzygy()
+{
+    let dir = std::env::temp_dir().join(format!("dynchess-syzygy-{}", std::process::id()));
+    std::fs::create_dir_all(&dir).unwrap();
+    for name in ["KQvK.rtbw", "KQvK.rtbz", "KRPvKR.rtbw", "KvK.txt", "README.rtbw"]
+    {
+        std::fs::write(dir.join(name), b"").unwrap();
+    }
+
+    let tables: Syzygy = Syzygy::open(&dir).unwrap();
+    std::fs::remove_dir_all(&dir).unwrap();
+
+    assert!(tables.get_wdl_tables() == ["KQvK", "KRPvKR"]);
+    assert!(tables.get_dtz_tables() == ["KQvK"]);
+    assert!(tables.get_max_pieces() == 5);
+    assert!(Syzygy::open(&dir).is_err());
+    assert!(Syzygy::new().get_max_pieces() == 0);
+
+    // Covered positions and not, no table is loaded either way
+    let board: ChessBoard = ChessBoard::from_fen("4k3/8/8/8/8/8/8/4K2Q w - - 0 1").unwrap();
+    assert!(tables.__covers(&board));
+    assert!(board.probe_wdl(&tables).is_none());
+    assert!(board.probe_dtz(&tables).is_none());
+    assert!(tables.probe(&board).is_none());
+    assert!(!Syzygy::new().__covers(&board));
+
+    let castling: ChessBoard = ChessBoard::from_fen("4k3/8/8/8/8/8/8/4K2R w K - 0 1").unwrap();
+    assert!(!tables.__covers(&castling));
+    assert!(!tables.__covers(&ChessBoard::init_position()));
+}
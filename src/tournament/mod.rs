@@ -0,0 +1,387 @@
+// Runs a tournament: pairs the rounds, keeps the results and ranks the
+// players. Round robins follow the Berger tables, Swiss rounds pair the
+// players with the same score against each other, without rematches
+// while they can be avoided, and knockouts follow a seeded bracket.
+//
+// Players are seeded by rating, the first one added wins the ties. Points
+// are counted as 1 for a win and 0.5 for a draw, a bye is worth a win
+use super::*;
+use game::Game;
+use Format::{Knockout, RoundRobin, Swiss};
+
+mod pairing;
+#[cfg(test)]
+mod tests;
+
+pub type PlayerId = usize;
+
+#[derive(Debug, PartialEq, Eq, Clone, Copy)]
+pub enum Format
+{
+    // Every player meets every other once
+    RoundRobin,
+    // The given number of rounds
+    Swiss(usize),
+    // The loser of every game is out. A drawn game goes to black, like in
+    // an Armageddon game
+    Knockout,
+}
+
+#[derive(Debug, PartialEq, Eq, Clone, Copy)]
+pub enum GameResult
+{
+    WhiteWins,
+    Draw,
+    BlackWins,
+}
+
+impl GameResult
+{
+    // From the color that won, `None` for a draw
+    pub fn from_winner(winner: Option<bool>) -> GameResult
+    {
+        return match winner
+        {
+            Some(true) => GameResult::WhiteWins,
+            Some(false) => GameResult::BlackWins,
+            None => GameResult::Draw,
+        };
+    }
+
+    // The points of the color, true for white
+    pub fn points(self, white: bool) -> f64
+    {
+        return match (self, white)
+        {
+            (GameResult::Draw, _) => 0.5,
+            (GameResult::WhiteWins, true) | (GameResult::BlackWins, false) => 1.0,
+            _ => 0.0,
+        };
+    }
+}
+
+#[derive(Debug, PartialEq, Eq, Clone, Copy)]
+pub struct Pairing
+{
+    white: PlayerId,
+    // `None` for a bye
+    black: Option<PlayerId>,
+    result: Option<GameResult>,
+}
+
+impl Pairing
+{
+    pub fn get_white(&self) -> PlayerId
+    {
+        return self.white;
+    }
+
+    pub fn get_black(&self) -> Option<PlayerId>
+    {
+        return self.black;
+    }
+
+    pub fn get_result(&self) -> Option<GameResult>
+    {
+        return self.result;
+    }
+
+    pub fn is_bye(&self) -> bool
+    {
+        return self.black.is_none();
+    }
+
+    // The opponent of the player, `None` for a bye or if the player isn't
+    // in the game
+    pub fn opponent(&self, player: PlayerId) -> Option<PlayerId>
+    {
+        if self.white == player
+        {
+            return self.black;
+        }
+        return self.black.filter(|black| *black == player).map(|_| self.white);
+    }
+
+    // The points the player got, `None` if the game isn't over
+    pub fn points(&self, player: PlayerId) -> Option<f64>
+    {
+        return self.result.map(|result| result.points(self.white == player));
+    }
+}
+
+#[derive(Debug, Clone)]
+struct Entrant
+{
+    name: String,
+    rating: u32,
+}
+
+#[derive(Debug, Clone)]
+pub struct Tournament
+{
+    format: Format,
+    players: Vec<Entrant>,
+    rounds: Vec<Vec<Pairing>>,
+}
+
+impl Tournament
+{
+    pub fn new(format: Format) -> Tournament
+    {
+        return Tournament { format, players: Vec::new(), rounds: Vec::new() };
+    }
+
+    pub fn get_format(&self) -> Format
+    {
+        return self.format;
+    }
+
+    // Adds a player, `None` once the first round is paired
+    pub fn add_player(&mut self, name: &str, rating: u32) -> Option<PlayerId>
+    {
+        if !self.rounds.is_empty()
+        {
+            return None;
+        }
+
+        self.players.push(Entrant { name: name.to_string(), rating });
+        return Some(self.players.len() - 1);
+    }
+
+    pub fn player_count(&self) -> usize
+    {
+        return self.players.len();
+    }
+
+    pub fn get_name(&self, player: PlayerId) -> &str
+    {
+        return &self.players[player].name;
+    }
+
+    pub fn get_rating(&self, player: PlayerId) -> u32
+    {
+        return self.players[player].rating;
+    }
+
+    pub fn get_rounds(&self) -> &[Vec<Pairing>]
+    {
+        return &self.rounds;
+    }
+
+    // The number of rounds the tournament has, for a knockout the most it
+    // can have
+    pub fn round_count(&self) -> usize
+    {
+        let n: usize = self.players.len();
+        return match self.format
+        {
+            RoundRobin if n < 2 => 0,
+            RoundRobin => n - 1 + n % 2,
+            Swiss(rounds) => rounds,
+            Knockout => n.next_power_of_two().trailing_zeros() as usize,
+        };
+    }
+
+    // If every game of the rounds paired so far was played
+    pub fn is_round_over(&self) -> bool
+    {
+        return self.rounds.last().is_none_or(|round| round.iter().all(|p| p.result.is_some()));
+    }
+
+    pub fn is_over(&self) -> bool
+    {
+        return self.rounds.len() >= self.round_count() && self.is_round_over();
+    }
+
+    // Pairs the next round and returns it. `None` if a game of the round
+    // before isn't over or after the last round
+    pub fn pair_next_round(&mut self) -> Option<&[Pairing]>
+    {
+        if self.players.len() < 2 || !self.is_round_over() || self.rounds.len() >= self.round_count()
+        {
+            return None;
+        }
+
+        let pairs: Vec<(PlayerId, Option<PlayerId>)> = match self.format
+        {
+            RoundRobin => pairing::berger_round(&self.seeds(), self.rounds.len()),
+            Swiss(_) => pairing::swiss_round(self)?,
+            Knockout => pairing::knockout_round(self),
+        };
+
+        // A bye is won without playing
+        let round: Vec<Pairing> = pairs.into_iter().map(|(white, black)| Pairing {
+            white,
+            black,
+            result: black.is_none().then_some(GameResult::WhiteWins),
+        }).collect();
+
+        self.rounds.push(round);
+        return self.rounds.last().map(|round| round.as_slice());
+    }
+
+    // Sets the result of a game, given by its round and its board, both
+    // counted from 0. Results can be corrected, returns false if there's
+    // no such game or it's a bye
+    pub fn record(&mut self, round: usize, board: usize, result: GameResult) -> bool
+    {
+        return match self.rounds.get_mut(round).and_then(|r| r.get_mut(board))
+        {
+            Some(pairing) if !pairing.is_bye() => { pairing.result = Some(result); true },
+            _ => false,
+        };
+    }
+
+    // Same as `Tournament::record` with the result of a finished board.
+    // Returns false if the game is still on or was aborted
+    pub fn record_board(&mut self, round: usize, board: usize, chess_board: &ChessBoard) -> bool
+    {
+        if matches!(chess_board.get_state(), ChessState::On | ChessState::Aborted)
+        {
+            return false;
+        }
+        return self.record(round, board, GameResult::from_winner(chess_board.get_winner()));
+    }
+
+    // Same as `Tournament::record_board` for a `Game`, which can also end
+    // by resignation, on time or by agreement
+    pub fn record_game(&mut self, round: usize, board: usize, game: &Game) -> bool
+    {
+        if game.is_on() || game.get_board().get_state() == ChessState::Aborted
+        {
+            return false;
+        }
+        return self.record(round, board, GameResult::from_winner(game.get_winner()));
+    }
+
+    // The players from the highest rating down
+    pub fn seeds(&self) -> Vec<PlayerId>
+    {
+        let mut seeds: Vec<PlayerId> = (0..self.players.len()).collect();
+        seeds.sort_by_key(|p| core::cmp::Reverse(self.players[*p].rating));
+        return seeds;
+    }
+
+    // The games of the player that are over, with the opponent (`None` for
+    // a bye), the color (true for white) and the points
+    fn __games(&self, player: PlayerId) -> impl Iterator<Item = (Option<PlayerId>, bool, f64)> + '_
+    {
+        return self.rounds.iter().flatten()
+            .filter(move |p| p.white == player || p.black == Some(player))
+            .filter_map(move |p| Some((p.opponent(player), p.white == player, p.points(player)?)));
+    }
+
+    pub fn score(&self, player: PlayerId) -> f64
+    {
+        return self.__games(player).map(|(_, _, points)| points).sum();
+    }
+
+    // The sum of the scores of the opponents
+    pub fn buchholz(&self, player: PlayerId) -> f64
+    {
+        return self.__games(player).filter_map(|(opponent, _, _)| opponent).map(|o| self.score(o)).sum();
+    }
+
+    // The scores of the beaten opponents and half the scores of the drawn
+    // ones
+    pub fn sonneborn_berger(&self, player: PlayerId) -> f64
+    {
+        return self.__games(player).filter_map(|(opponent, _, points)| Some(points * self.score(opponent?))).sum();
+    }
+
+    // The points the player got against the others of the group
+    pub fn direct_encounter(&self, player: PlayerId, group: &[PlayerId]) -> f64
+    {
+        return self.__games(player).filter(|(opponent, _, _)| opponent.is_some_and(|o| group.contains(&o))).map(|(_, _, points)| points).sum();
+    }
+
+    // The players from the first place down, by score, then Buchholz, then
+    // Sonneborn-Berger, then the direct encounters of the players that are
+    // still tied, then the seed. A knockout is ranked by how far the
+    // players got instead of the score
+    pub fn standings(&self) -> Vec<PlayerId>
+    {
+        let seeds: Vec<PlayerId> = self.seeds();
+        let key = |p: PlayerId| -> [f64; 3]
+        {
+            let main: f64 = match self.format
+            {
+                Knockout => pairing::knockout_progress(self, p) as f64,
+                _ => self.score(p),
+            };
+            return [main, self.buchholz(p), self.sonneborn_berger(p)];
+        };
+
+        let mut standings: Vec<PlayerId> = seeds.clone();
+        standings.sort_by(|a, b| key(*b).partial_cmp(&key(*a)).unwrap());
+
+        // Within the groups still tied, the direct encounters decide
+        let mut start: usize = 0;
+        while start < standings.len()
+        {
+            let mut end: usize = start + 1;
+            while end < standings.len() && key(standings[end]) == key(standings[start])
+            {
+                end += 1;
+            }
+
+            let group: Vec<PlayerId> = standings[start..end].to_vec();
+            standings[start..end].sort_by(|a, b|
+            {
+                let de = |p: PlayerId| self.direct_encounter(p, &group);
+                return de(*b).partial_cmp(&de(*a)).unwrap()
+                    .then_with(|| seeds.iter().position(|s| s == a).cmp(&seeds.iter().position(|s| s == b)));
+            });
+            start = end;
+        }
+
+        return standings;
+    }
+
+    // The standings as a table. Every round shows the rank of the
+    // opponent, the color and the result (`+`, `=` or `-`)
+    pub fn crosstable(&self) -> String
+    {
+        let standings: Vec<PlayerId> = self.standings();
+        let rank = |p: PlayerId| standings.iter().position(|s| *s == p).unwrap() + 1;
+        let width: usize = self.players.iter().map(|p| p.name.chars().count()).max().unwrap_or(0).max(4);
+
+        let mut table: String = format!("{:>3}  {:<width$}  {:>6}", "#", "Name", "Rating", width = width);
+        for round in 1..=self.rounds.len()
+        {
+            table.push_str(&format!("  {:>5}", format!("R{}", round)));
+        }
+        table.push_str(&format!("  {:>5}  {:>6}  {:>6}\n", "Pts", "BH", "SB"));
+
+        for (i, player) in standings.iter().enumerate()
+        {
+            table.push_str(&format!("{:>3}  {:<width$}  {:>6}", i + 1, self.get_name(*player), self.get_rating(*player), width = width));
+            for round in self.rounds.iter()
+            {
+                let cell: String = match round.iter().find(|p| p.white == *player || p.black == Some(*player))
+                {
+                    None => "-".to_string(),
+                    Some(p) if p.is_bye() => "bye".to_string(),
+                    Some(p) => {
+                        let color: char = if p.white == *player { 'w' } else { 'b' };
+                        let result: &str = match p.points(*player)
+                        {
+                            None => "",
+                            Some(points) if points > 0.5 => "+",
+                            Some(points) if points < 0.5 => "-",
+                            Some(_) => "=",
+                        };
+                        format!("{}{}{}", rank(p.opponent(*player).unwrap()), color, result)
+                    },
+                };
+                table.push_str(&format!("  {:>5}", cell));
+            }
+            table.push_str(&format!(
+                "  {:>5.1}  {:>6.2}  {:>6.2}\n",
+                self.score(*player), self.buchholz(*player), self.sonneborn_berger(*player),
+            ));
+        }
+
+        return table;
+    }
+}
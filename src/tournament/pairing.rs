@@ -0,0 +1,266 @@
+// The pairings of a round, as (white, black) with `None` for a bye
+use super::*;
+
+type Pairs = Vec<(PlayerId, Option<PlayerId>)>;
+
+// The round of the Berger tables, from 0. The seeds are numbered from 1
+// and a missing last number, for an odd count, gives a bye. The first
+// board goes to the highest number every round, with the colors swapped
+// every round, and the others turn around it
+pub fn berger_round(seeds: &[PlayerId], round: usize) -> Pairs
+{
+    let n: usize = seeds.len() + seeds.len() % 2;
+    let player = |number: usize| seeds.get(number - 1).copied();
+    let wrap = |x: isize| (x - 1).rem_euclid(n as isize - 1) as usize + 1;
+
+    let p: usize = (round * (n/2)) % (n - 1) + 1;
+    let mut numbers: Vec<(usize, usize)> = vec![if round.is_multiple_of(2) { (p, n) } else { (n, p) }];
+    for j in 1..n/2
+    {
+        numbers.push((wrap((p + j) as isize), wrap(p as isize - j as isize)));
+    }
+
+    let mut pairs: Pairs = Vec::new();
+    let mut bye: Option<PlayerId> = None;
+    for (white, black) in numbers
+    {
+        match (player(white), player(black))
+        {
+            (Some(white), Some(black)) => pairs.push((white, Some(black))),
+            (Some(alone), None) | (None, Some(alone)) => bye = Some(alone),
+            (None, None) => {},
+        };
+    }
+
+    pairs.extend(bye.map(|player| (player, None)));
+    return pairs;
+}
+
+// The order of the seeds in a bracket of the size, so the best seeds meet
+// as late as possible: 1, 8, 4, 5, 2, 7, 3, 6 for 8
+fn __bracket(size: usize) -> Vec<usize>
+{
+    let mut order: Vec<usize> = vec![1];
+    while order.len() < size
+    {
+        let next: usize = 2*order.len() + 1;
+        order = order.iter().flat_map(|seed| [*seed, next - seed]).collect();
+    }
+
+    return order;
+}
+
+// The winner of a game in a knockout: a draw goes to black and a bye to
+// the only player
+fn __winner(pairing: &Pairing) -> Option<PlayerId>
+{
+    return match pairing.result?
+    {
+        GameResult::WhiteWins => Some(pairing.white),
+        _ => pairing.black,
+    };
+}
+
+// The first round puts the seeds in the bracket, the best ones get the
+// byes when the players don't fill it. In the rounds after the winners of
+// two games next to each other meet, the one who had black gets white
+pub fn knockout_round(tournament: &Tournament) -> Pairs
+{
+    let last: &Vec<Pairing> = match tournament.rounds.last()
+    {
+        Some(last) => last,
+        None => {
+            let seeds: Vec<PlayerId> = tournament.seeds();
+            let slots: Vec<usize> = __bracket(seeds.len().next_power_of_two());
+            return slots.chunks(2).map(|pair| (seeds[pair[0] - 1], seeds.get(pair[1] - 1).copied())).collect();
+        },
+    };
+
+    let winners: Vec<(PlayerId, bool)> = last.iter().filter_map(|p| __winner(p).map(|w| (w, w == p.white))).collect();
+    return winners.chunks(2).map(|pair| match pair
+    {
+        [(a, true), (b, false)] => (*b, Some(*a)),
+        [(a, _), (b, _)] => (*a, Some(*b)),
+        _ => (pair[0].0, None),
+    }).collect();
+}
+
+// How many games the player won in a knockout, byes included
+pub fn knockout_progress(tournament: &Tournament, player: PlayerId) -> usize
+{
+    return tournament.rounds.iter().flatten().filter(|p| __winner(p) == Some(player)).count();
+}
+
+// The colors a player had, in order, without the byes
+fn __colors(tournament: &Tournament, player: PlayerId) -> Vec<bool>
+{
+    return tournament.rounds.iter().flatten()
+        .filter(|p| !p.is_bye() && (p.white == player || p.black == Some(player)))
+        .map(|p| p.white == player)
+        .collect();
+}
+
+// How much the player wants white, from -2 (must have black) to 2 (must
+// have white). A player must not get the same color three times in a row
+// or two more games with one color than with the other
+fn __color_preference(colors: &[bool]) -> i8
+{
+    let difference: isize = colors.iter().map(|white| if *white { 1 } else { -1 }).sum();
+    let last_two: Option<bool> = match colors
+    {
+        [.., a, b] if a == b => Some(*b),
+        _ => None,
+    };
+
+    if difference <= -1 && last_two == Some(false) || difference <= -2
+    {
+        return 2;
+    }
+    if difference >= 1 && last_two == Some(true) || difference >= 2
+    {
+        return -2;
+    }
+    return match colors.last()
+    {
+        Some(true) => -1,
+        Some(false) => 1,
+        None => 0,
+    };
+}
+
+// Gives the colors of a game to two players, the first one ranked higher.
+// The stronger wish wins, and the higher ranked player's on an equal one
+fn __allocate(a: (PlayerId, i8), b: (PlayerId, i8), board: usize) -> (PlayerId, PlayerId)
+{
+    let a_white: bool = match (a.1, b.1)
+    {
+        (0, 0) => board.is_multiple_of(2),
+        (x, y) if x.signum() != y.signum() => x > y,
+        (x, y) if x.abs() != y.abs() => if x.abs() > y.abs() { x > 0 } else { y < 0 },
+        (x, _) => x > 0,
+    };
+
+    return if a_white { (a.0, b.0) } else { (b.0, a.0) };
+}
+
+// The Dutch system: the players are ranked by score, then by seed, and
+// every score group is split in halves, with the first of the top half
+// meeting the first of the bottom half and so on. Players left over float
+// down to the next group. Players never meet twice and colors are never
+// given against an absolute wish when that can be avoided. Once every
+// pairing would need a rematch, rematches are allowed rather than ending
+// the tournament early. The lowest ranked player who didn't have a bye yet
+// gets it
+pub fn swiss_round(tournament: &Tournament) -> Option<Pairs>
+{
+    let seeds: Vec<PlayerId> = tournament.seeds();
+    let mut ranking: Vec<PlayerId> = seeds.clone();
+    ranking.sort_by(|a, b| tournament.score(*b).partial_cmp(&tournament.score(*a)).unwrap());
+
+    let mut met: Vec<(PlayerId, PlayerId)> = Vec::new();
+    let mut had_bye: Vec<PlayerId> = Vec::new();
+    for pairing in tournament.rounds.iter().flatten()
+    {
+        match pairing.black
+        {
+            Some(black) => met.push((pairing.white, black)),
+            None => had_bye.push(pairing.white),
+        };
+    }
+
+    let preferences: Vec<i8> = (0..tournament.players.len()).map(|p| __color_preference(&__colors(tournament, p))).collect();
+    let scores: Vec<f64> = (0..tournament.players.len()).map(|p| tournament.score(p)).collect();
+    let context: SwissContext = SwissContext { met, preferences, scores };
+
+    // The bye candidates from the bottom up, anyone if everyone had one
+    let mut byes: Vec<Option<PlayerId>> = vec![None];
+    if ranking.len() % 2 == 1
+    {
+        byes = ranking.iter().rev().filter(|p| !had_bye.contains(p)).map(|p| Some(*p)).collect();
+        if byes.is_empty()
+        {
+            byes = ranking.iter().rev().map(|p| Some(*p)).collect();
+        }
+    }
+
+    for (strict_colors, rematches) in [(true, false), (false, false), (false, true)]
+    {
+        for bye in byes.iter()
+        {
+            let players: Vec<PlayerId> = ranking.iter().copied().filter(|p| Some(*p) != *bye).collect();
+            if let Some(games) = context.__pair(&players, strict_colors, rematches)
+            {
+                let mut pairs: Pairs = games.iter().enumerate().map(|(board, (a, b))|
+                {
+                    let (white, black) = __allocate((*a, context.preferences[*a]), (*b, context.preferences[*b]), board);
+                    (white, Some(black))
+                }).collect();
+                pairs.extend(bye.map(|player| (player, None)));
+                return Some(pairs);
+            }
+        }
+    }
+
+    return None;
+}
+
+struct SwissContext
+{
+    met: Vec<(PlayerId, PlayerId)>,
+    preferences: Vec<i8>,
+    scores: Vec<f64>,
+}
+
+impl SwissContext
+{
+    fn __met(&self, a: PlayerId, b: PlayerId) -> bool
+    {
+        return self.met.contains(&(a, b)) || self.met.contains(&(b, a));
+    }
+
+    fn __can_meet(&self, a: PlayerId, b: PlayerId, strict_colors: bool, rematches: bool) -> bool
+    {
+        if !rematches && self.__met(a, b)
+        {
+            return false;
+        }
+        // Both must have the same color
+        return !strict_colors || self.preferences[a] * self.preferences[b] != 4;
+    }
+
+    // Pairs the players, given in ranking order, trying the opponents of
+    // the first one in the order of the Dutch system: the top of the bottom
+    // half of its score group, then the rest of the bottom half, then the
+    // top half from its end, then the players of the groups below. With
+    // `rematches` the players it didn't meet yet still come first
+    fn __pair(&self, players: &[PlayerId], strict_colors: bool, rematches: bool) -> Option<Vec<(PlayerId, PlayerId)>>
+    {
+        let first: PlayerId = match players.first()
+        {
+            Some(first) => *first,
+            None => return Some(Vec::new()),
+        };
+
+        let group: usize = players.iter().take_while(|p| self.scores[**p] == self.scores[first]).count();
+        let half: usize = (group / 2).max(1);
+        let mut order: Vec<usize> = (half..group).chain((1..half).rev()).chain(group..players.len()).collect();
+        order.sort_by_key(|i| self.__met(first, players[*i]));
+
+        for i in order
+        {
+            if !self.__can_meet(first, players[i], strict_colors, rematches)
+            {
+                continue;
+            }
+
+            let rest: Vec<PlayerId> = players[1..].iter().copied().filter(|p| *p != players[i]).collect();
+            if let Some(mut games) = self.__pair(&rest, strict_colors, rematches)
+            {
+                games.insert(0, (first, players[i]));
+                return Some(games);
+            }
+        }
+
+        return None;
+    }
+}
@@ -0,0 +1,188 @@
+use super::*;
+
+fn new_tournament(format: Format, count: usize) -> Tournament
+{
+    let mut tournament: Tournament = Tournament::new(format);
+    for i in 0..count
+    {
+        tournament.add_player(&format!("Player {}", i + 1), 2000 - 10*i as u32);
+    }
+    return tournament;
+}
+
+// The pairings of the last round by the names' numbers, 0 for a bye
+fn numbers(tournament: &Tournament) -> Vec<(usize, usize)>
+{
+    return tournament.get_rounds().last().unwrap().iter().map(|p| (p.get_white() + 1, p.get_black().map_or(0, |b| b + 1))).collect();
+}
+
+#[test]
+fn test_tournament_berger()
+{
+    // The FIDE tables for six players
+    let expected: [[(usize, usize); 3]; 5] = [
+        [(1, 6), (2, 5), (3, 4)],
+        [(6, 4), (5, 3), (1, 2)],
+        [(2, 6), (3, 1), (4, 5)],
+        [(6, 5), (1, 4), (2, 3)],
+        [(3, 6), (4, 2), (5, 1)],
+    ];
+
+    let mut tournament: Tournament = new_tournament(Format::RoundRobin, 6);
+    for round in expected.iter()
+    {
+        tournament.pair_next_round().unwrap();
+        assert!(numbers(&tournament) == round);
+        // The round must be over before the next one
+        assert!(tournament.pair_next_round().is_none());
+        for board in 0..3
+        {
+            assert!(tournament.record(tournament.get_rounds().len() - 1, board, GameResult::WhiteWins));
+        }
+    }
+    assert!(tournament.is_over() && tournament.pair_next_round().is_none());
+    assert!(tournament.add_player("Late", 1500).is_none());
+
+    // Five players, the sixth number is a bye
+    let mut tournament: Tournament = new_tournament(Format::RoundRobin, 5);
+    assert!(tournament.round_count() == 5);
+    tournament.pair_next_round().unwrap();
+    assert!(numbers(&tournament) == [(2, 5), (3, 4), (1, 0)]);
+    assert!(!tournament.record(0, 2, GameResult::Draw));
+    assert!(tournament.score(0) == 1.0);
+}
+
+#[test]
+fn test_tournament_swiss()
+{
+    let mut tournament: Tournament = new_tournament(Format::Swiss(4), 7);
+    let mut met: Vec<(usize, usize)> = Vec::new();
+
+    tournament.pair_next_round().unwrap();
+    // Top half against the bottom half, the lowest seed gets the bye
+    assert!(numbers(&tournament) == [(1, 4), (5, 2), (3, 6), (7, 0)]);
+
+    for round in 0..4
+    {
+        if round > 0
+        {
+            tournament.pair_next_round().unwrap();
+        }
+
+        for (board, pairing) in tournament.get_rounds()[round].clone().iter().enumerate()
+        {
+            if let Some(black) = pairing.get_black()
+            {
+                let pair: (usize, usize) = (pairing.get_white().min(black), pairing.get_white().max(black));
+                assert!(!met.contains(&pair));
+                met.push(pair);
+                // The higher seed wins
+                let result: GameResult = if pairing.get_white() < black { GameResult::WhiteWins } else { GameResult::BlackWins };
+                assert!(tournament.record(round, board, result));
+            }
+        }
+    }
+
+    // No one had two byes or more than two games with one color
+    for player in 0..7
+    {
+        let byes: usize = tournament.get_rounds().iter().flatten().filter(|p| p.is_bye() && p.get_white() == player).count();
+        assert!(byes <= 1);
+        let whites: usize = tournament.get_rounds().iter().flatten().filter(|p| !p.is_bye() && p.get_white() == player).count();
+        let blacks: usize = tournament.get_rounds().iter().flatten().filter(|p| p.get_black() == Some(player)).count();
+        assert!(whites.abs_diff(blacks) <= 2);
+    }
+
+    assert!(tournament.is_over());
+    assert!(tournament.standings()[0] == 0 && tournament.score(0) == 4.0);
+
+    // As many rounds as a round robin, where the last rounds can only be
+    // paired with a rematch
+    let mut tournament: Tournament = new_tournament(Format::Swiss(9), 10);
+    for round in 0..9
+    {
+        assert!(tournament.pair_next_round().is_some());
+        for board in 0..5
+        {
+            assert!(tournament.record(round, board, GameResult::WhiteWins));
+        }
+    }
+    assert!(tournament.is_over() && tournament.pair_next_round().is_none());
+}
+
+#[test]
+fn test_tournament_knockout()
+{
+    let mut tournament: Tournament = new_tournament(Format::Knockout, 6);
+    assert!(tournament.round_count() == 3);
+
+    // The two best seeds get the byes
+    tournament.pair_next_round().unwrap();
+    assert!(numbers(&tournament) == [(1, 0), (4, 5), (2, 0), (3, 6)]);
+    assert!(tournament.record(0, 1, GameResult::Draw));
+    assert!(tournament.record(0, 3, GameResult::WhiteWins));
+
+    // The draw went to black
+    tournament.pair_next_round().unwrap();
+    assert!(numbers(&tournament) == [(5, 1), (2, 3)]);
+    assert!(tournament.record(1, 0, GameResult::BlackWins));
+    assert!(tournament.record(1, 1, GameResult::BlackWins));
+
+    tournament.pair_next_round().unwrap();
+    assert!(numbers(&tournament) == [(1, 3)]);
+    let mut board: ChessBoard = ChessBoard::init_position();
+    assert!(!tournament.record_board(2, 0, &board));
+    for (from, to) in [(0o15, 0o25), (0o64, 0o44), (0o16, 0o36), (0o73, 0o37)]
+    {
+        board.drag(from, to);
+    }
+    assert!(tournament.record_board(2, 0, &board));
+
+    assert!(tournament.is_over());
+    assert!(tournament.standings()[..2] == [2, 0]);
+}
+
+#[test]
+fn test_tournament_tiebreaks()
+{
+    let mut tournament: Tournament = new_tournament(Format::RoundRobin, 4);
+    let results: [[GameResult; 2]; 3] = [
+        // 1-4, 2-3
+        [GameResult::Draw, GameResult::WhiteWins],
+        // 4-3, 1-2
+        [GameResult::BlackWins, GameResult::WhiteWins],
+        // 2-4, 3-1
+        [GameResult::WhiteWins, GameResult::WhiteWins],
+    ];
+
+    for (round, result) in results.iter().enumerate()
+    {
+        tournament.pair_next_round().unwrap();
+        for (board, r) in result.iter().enumerate()
+        {
+            let mut game: Game = Game::new();
+            assert!(!tournament.record_game(round, board, &game));
+            match r
+            {
+                GameResult::WhiteWins => game.resign(false),
+                GameResult::BlackWins => game.resign(true),
+                GameResult::Draw => game.draw(),
+            };
+            assert!(tournament.record_game(round, board, &game));
+        }
+    }
+
+    // Players 2 and 3 are tied on points and Buchholz, player 2 beat
+    // stronger players
+    assert!(tournament.score(0) == 1.5 && tournament.score(1) == 2.0 && tournament.score(2) == 2.0 && tournament.score(3) == 0.5);
+    assert!(tournament.buchholz(1) == 4.0 && tournament.buchholz(2) == 4.0);
+    assert!(tournament.sonneborn_berger(1) == 2.5 && tournament.sonneborn_berger(2) == 2.0);
+    assert!(tournament.direct_encounter(1, &[1, 2]) == 1.0);
+    assert!(tournament.standings() == [1, 2, 0, 3]);
+
+    let table: String = tournament.crosstable();
+    let lines: Vec<&str> = table.lines().collect();
+    assert!(lines.len() == 5);
+    assert!(lines[0].contains("R3") && lines[0].contains("SB"));
+    assert!(lines[1].contains("Player 2") && lines[1].contains("2w+") && lines[1].contains("3b-") && lines[1].contains("2.0"));
+}
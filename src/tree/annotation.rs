@@ -0,0 +1,343 @@
+use super::*;
+use core::time::Duration;
+
+// Evaluation of a position from white's side
+#[derive(Debug, PartialEq, Eq, Clone, Copy)]
+pub enum Eval
+{
+    Centipawns(i32),
+    // Mate in that many moves, negative when black mates
+    Mate(i32),
+}
+
+// Colors of the arrows and highlights, as lichess draws them
+#[derive(Debug, PartialEq, Eq, Clone, Copy)]
+pub enum MarkColor
+{
+    Green,
+    Red,
+    Yellow,
+    Blue,
+}
+
+impl MarkColor
+{
+    fn __letter(self) -> char
+    {
+        return match self
+        {
+            MarkColor::Green => 'G',
+            MarkColor::Red => 'R',
+            MarkColor::Yellow => 'Y',
+            MarkColor::Blue => 'B',
+        };
+    }
+
+    fn __from_letter(c: char) -> Option<MarkColor>
+    {
+        return match c
+        {
+            'G' => Some(MarkColor::Green),
+            'R' => Some(MarkColor::Red),
+            'Y' => Some(MarkColor::Yellow),
+            'B' => Some(MarkColor::Blue),
+            _ => None,
+        };
+    }
+}
+
+// What is said about a move: the comment, the NAGs, the clock and the eval
+// after it, and the arrows and highlights drawn on the board. In PGN all
+// but the NAGs are part of the comment, like
+// `{ Good move [%clk 0:01:05] [%eval 0.35] [%csl Ge4] [%cal Gd1h5] }`
+#[derive(Debug, PartialEq, Eq, Clone, Default)]
+pub struct Annotation
+{
+    comment: String,
+    // Numeric Annotation Glyphs, `$1` is `!`
+    nags: Vec<u8>,
+    clock: Option<Duration>,
+    eval: Option<Eval>,
+    arrows: Vec<(MarkColor, u8, u8)>,
+    highlights: Vec<(MarkColor, u8)>,
+}
+
+impl Annotation
+{
+    // Reads a PGN comment, without the braces. The blocks of the arrows and
+    // highlights are read on the given board, commands that can't be read
+    // stay in the comment
+    pub fn from_comment(text: &str, board: &ChessBoard) -> Annotation
+    {
+        let mut annotation: Annotation = Annotation::default();
+        let mut comment: String = String::new();
+        let mut rest: &str = text;
+
+        while let Some(start) = rest.find("[%")
+        {
+            let end: usize = match rest[start..].find(']')
+            {
+                Some(end) => start + end,
+                None => break,
+            };
+
+            comment.push_str(&rest[..start]);
+            let command: &str = &rest[start+2..end];
+            if !annotation.__read_command(command, board)
+            {
+                comment.push_str(&rest[start..=end]);
+            }
+            rest = &rest[end+1..];
+        }
+        comment.push_str(rest);
+
+        annotation.comment = comment.split_whitespace().collect::<Vec<&str>>().join(" ");
+        return annotation;
+    }
+
+    // Writes the annotation as a PGN comment, without the braces and the
+    // NAGs
+    pub fn to_comment(&self, board: &ChessBoard) -> String
+    {
+        let mut parts: Vec<String> = Vec::new();
+        if !self.comment.is_empty()
+        {
+            parts.push(self.comment.clone());
+        }
+
+        if let Some(clock) = self.clock
+        {
+            let seconds: u64 = clock.as_secs();
+            parts.push(format!("[%clk {}:{:02}:{:02}]", seconds / 3600, seconds / 60 % 60, seconds % 60));
+        }
+
+        match self.eval
+        {
+            Some(Eval::Centipawns(cp)) => parts.push(format!("[%eval {}{}.{:02}]",
+                if cp < 0 { "-" } else { "" }, cp.abs() / 100, cp.abs() % 100)),
+            Some(Eval::Mate(moves)) => parts.push(format!("[%eval #{}]", moves)),
+            None => {},
+        };
+
+        if !self.highlights.is_empty()
+        {
+            let marks: Vec<String> = self.highlights.iter()
+                .map(|(color, coords)| format!("{}{}", color.__letter(), board.write_coords(*coords))).collect();
+            parts.push(format!("[%csl {}]", marks.join(",")));
+        }
+
+        if !self.arrows.is_empty()
+        {
+            let marks: Vec<String> = self.arrows.iter()
+                .map(|(color, from, to)| format!("{}{}{}", color.__letter(), board.write_coords(*from), board.write_coords(*to)))
+                .collect();
+            parts.push(format!("[%cal {}]", marks.join(",")));
+        }
+
+        return parts.join(" ");
+    }
+
+    // If there's nothing to write in a comment
+    pub fn is_empty(&self) -> bool
+    {
+        return self.comment.is_empty() && self.clock.is_none() && self.eval.is_none()
+            && self.arrows.is_empty() && self.highlights.is_empty();
+    }
+
+    pub fn get_comment(&self) -> &str
+    {
+        return &self.comment;
+    }
+
+    // A `}` would end the comment in PGN, it's written as `)`
+    pub fn set_comment(&mut self, comment: &str)
+    {
+        self.comment = comment.replace('}', ")");
+    }
+
+    pub fn get_nags(&self) -> &[u8]
+    {
+        return &self.nags;
+    }
+
+    // Adds the NAG if it isn't there yet
+    pub fn add_nag(&mut self, nag: u8)
+    {
+        if !self.nags.contains(&nag)
+        {
+            self.nags.push(nag);
+        }
+    }
+
+    pub fn remove_nag(&mut self, nag: u8)
+    {
+        self.nags.retain(|n| *n != nag);
+    }
+
+    // Time left on the clock of the color that moved
+    pub fn get_clock(&self) -> Option<Duration>
+    {
+        return self.clock;
+    }
+
+    pub fn set_clock(&mut self, clock: Option<Duration>)
+    {
+        self.clock = clock;
+    }
+
+    pub fn get_eval(&self) -> Option<Eval>
+    {
+        return self.eval;
+    }
+
+    pub fn set_eval(&mut self, eval: Option<Eval>)
+    {
+        self.eval = eval;
+    }
+
+    // Arrows as color, from and to
+    pub fn get_arrows(&self) -> &[(MarkColor, u8, u8)]
+    {
+        return &self.arrows;
+    }
+
+    // Adds the arrow, or removes it if it's already there, the way drawing
+    // it twice on lichess does
+    pub fn toggle_arrow(&mut self, color: MarkColor, from: u8, to: u8)
+    {
+        match self.arrows.iter().position(|a| *a == (color, from, to))
+        {
+            Some(i) => { self.arrows.remove(i); },
+            None => self.arrows.push((color, from, to)),
+        };
+    }
+
+    pub fn get_highlights(&self) -> &[(MarkColor, u8)]
+    {
+        return &self.highlights;
+    }
+
+    // Same as `Annotation::toggle_arrow` for a highlighted block
+    pub fn toggle_highlight(&mut self, color: MarkColor, coords: u8)
+    {
+        match self.highlights.iter().position(|h| *h == (color, coords))
+        {
+            Some(i) => { self.highlights.remove(i); },
+            None => self.highlights.push((color, coords)),
+        };
+    }
+
+    // Reads a command like `clk 0:01:05`, returns false if it can't
+    fn __read_command(&mut self, command: &str, board: &ChessBoard) -> bool
+    {
+        let (name, value) = match command.split_once(' ')
+        {
+            Some((name, value)) => (name, value.trim()),
+            None => return false,
+        };
+
+        match name
+        {
+            "clk" => {
+                let fields: Vec<f64> = match value.split(':').map(|f| f.parse::<f64>()).collect::<Result<Vec<f64>, _>>()
+                {
+                    Ok(fields) if !fields.is_empty() && fields.len() <= 3 && fields.iter().all(|f| *f >= 0.0) => fields,
+                    _ => return false,
+                };
+                let seconds: f64 = fields.iter().fold(0.0, |total, f| total * 60.0 + f);
+                // Infinite or too many seconds for a `Duration`
+                match Duration::try_from_secs_f64(seconds)
+                {
+                    Ok(clock) => self.clock = Some(clock),
+                    Err(_) => return false,
+                };
+            },
+            "eval" => {
+                self.eval = Some(match value.strip_prefix('#')
+                {
+                    Some(moves) => match moves.parse::<i32>()
+                    {
+                        Ok(moves) => Eval::Mate(moves),
+                        Err(_) => return false,
+                    },
+                    None => match value.parse::<f64>()
+                    {
+                        // Rounded to the closest centipawn
+                        Ok(pawns) => Eval::Centipawns((pawns * 100.0 + 0.5 * pawns.signum()) as i32),
+                        Err(_) => return false,
+                    },
+                });
+            },
+            "csl" => {
+                let mut marks: Vec<(MarkColor, u8)> = Vec::new();
+                for mark in value.split(',')
+                {
+                    let mut chars = mark.trim().chars();
+                    let color: Option<MarkColor> = chars.next().and_then(MarkColor::__from_letter);
+                    match (color, board.read_coords(chars.as_str()))
+                    {
+                        (Some(color), Some(coords)) => marks.push((color, coords)),
+                        _ => return false,
+                    };
+                }
+                self.highlights.extend(marks);
+            },
+            "cal" => {
+                let mut marks: Vec<(MarkColor, u8, u8)> = Vec::new();
+                for mark in value.split(',')
+                {
+                    let mut chars = mark.trim().chars();
+                    let color: Option<MarkColor> = chars.next().and_then(MarkColor::__from_letter);
+                    let blocks: &str = chars.as_str();
+
+                    // The second block starts at the second letter
+                    let second: Option<usize> = blocks.char_indices().skip(1).find(|(_, c)| c.is_ascii_lowercase()).map(|(i, _)| i);
+                    let (from, to) = match second
+                    {
+                        Some(i) => (board.read_coords(&blocks[..i]), board.read_coords(&blocks[i..])),
+                        None => return false,
+                    };
+                    match (color, from, to)
+                    {
+                        (Some(color), Some(from), Some(to)) => marks.push((color, from, to)),
+                        _ => return false,
+                    };
+                }
+                self.arrows.extend(marks);
+            },
+            _ => return false,
+        };
+
+        return true;
+    }
+}
+
+// The NAG of an annotation symbol like `!?` or `+-`
+pub fn nag_from_symbol(symbol: &str) -> Option<u8>
+{
+    return NAG_SYMBOLS.iter().find(|(_, s)| *s == symbol).map(|(nag, _)| *nag);
+}
+
+// The symbol of a NAG, for the ones that have a common one
+pub fn nag_symbol(nag: u8) -> Option<&'static str>
+{
+    return NAG_SYMBOLS.iter().find(|(n, _)| *n == nag).map(|(_, s)| *s);
+}
+
+const NAG_SYMBOLS: [(u8, &str); 15] = [
+    (1, "!"),
+    (2, "?"),
+    (3, "!!"),
+    (4, "??"),
+    (5, "!?"),
+    (6, "?!"),
+    (7, "□"),
+    (10, "="),
+    (13, "∞"),
+    (14, "+="),
+    (15, "=+"),
+    (16, "+/-"),
+    (17, "-/+"),
+    (18, "+-"),
+    (19, "-+"),
+];
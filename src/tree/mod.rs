@@ -0,0 +1,381 @@
+// A game tree for analysis: the mainline and nested variations from a start
+// position, with an `Annotation` on every move.
+//
+// The nodes are kept in a list and named by their index, a `NodeId`. The
+// root is the start position, every other node is the position after a
+// move. The first child of a node continues the line, the others are the
+// variations. Every node keeps its board, so going to any node is free
+use super::*;
+
+mod annotation;
+#[cfg(test)]
+mod tests;
+
+pub use annotation::{nag_from_symbol, nag_symbol, Annotation, Eval, MarkColor};
+
+pub type NodeId = usize;
+
+#[derive(Clone)]
+struct Node
+{
+    parent: Option<NodeId>,
+    // The move that led to the node, `None` for the root
+    mv: Option<ChessMove>,
+    san: String,
+    board: ChessBoard,
+    children: Vec<NodeId>,
+    annotation: Annotation,
+}
+
+#[derive(Clone)]
+pub struct GameTree
+{
+    // Deleted nodes leave a `None` behind, so the ids of the others stay
+    nodes: Vec<Option<Node>>,
+    current: NodeId,
+}
+
+impl GameTree
+{
+    // A tree with only the given start position
+    pub fn new(board: ChessBoard) -> GameTree
+    {
+        let root: Node = Node { parent: None, mv: None, san: String::new(), board, children: Vec::new(), annotation: Annotation::default() };
+        return GameTree { nodes: vec![Some(root)], current: 0 };
+    }
+
+    pub fn root(&self) -> NodeId
+    {
+        return 0;
+    }
+
+    // The node the tree is at, where `GameTree::play` adds moves
+    pub fn current(&self) -> NodeId
+    {
+        return self.current;
+    }
+
+    // If the node exists and wasn't deleted
+    pub fn contains(&self, node: NodeId) -> bool
+    {
+        return self.nodes.get(node).is_some_and(|n| n.is_some());
+    }
+
+    // The position at the node. Panics if there is no such node, like the
+    // other functions taking a `NodeId`
+    pub fn get_board(&self, node: NodeId) -> &ChessBoard
+    {
+        return &self.__node(node).board;
+    }
+
+    pub fn legal_moves(&self, node: NodeId) -> Vec<ChessMove>
+    {
+        return self.__node(node).board.clone().legal_moves();
+    }
+
+    // The move that led to the node, `None` for the root
+    pub fn get_move(&self, node: NodeId) -> Option<ChessMove>
+    {
+        return self.__node(node).mv;
+    }
+
+    // Same as `GameTree::get_move` in SAN
+    pub fn get_san(&self, node: NodeId) -> Option<&str>
+    {
+        return self.__node(node).mv.map(|_| self.__node(node).san.as_str());
+    }
+
+    pub fn get_parent(&self, node: NodeId) -> Option<NodeId>
+    {
+        return self.__node(node).parent;
+    }
+
+    // The moves from the node, the first one continues the line
+    pub fn get_children(&self, node: NodeId) -> &[NodeId]
+    {
+        return &self.__node(node).children;
+    }
+
+    pub fn get_annotation(&self, node: NodeId) -> &Annotation
+    {
+        return &self.__node(node).annotation;
+    }
+
+    pub fn get_annotation_mut(&mut self, node: NodeId) -> &mut Annotation
+    {
+        return &mut self.__node_mut(node).annotation;
+    }
+
+    // Adds the move after the node and returns the new node, or the node
+    // of the move if it was already there. `None` if the move isn't legal
+    pub fn add_move(&mut self, node: NodeId, mv: ChessMove) -> Option<NodeId>
+    {
+        if let Some(child) = self.__node(node).children.iter().find(|c| self.__node(**c).mv == Some(mv))
+        {
+            return Some(*child);
+        }
+
+        let mut board: ChessBoard = self.__node(node).board.clone();
+        if board.get_state() != ChessState::On || !board.legal_moves().contains(&mv)
+        {
+            return None;
+        }
+
+        let san: String = board.to_san(mv);
+        board.make_move(mv);
+
+        let child: NodeId = self.nodes.len();
+        self.nodes.push(Some(Node { parent: Some(node), mv: Some(mv), san, board, children: Vec::new(), annotation: Annotation::default() }));
+        self.__node_mut(node).children.push(child);
+
+        return Some(child);
+    }
+
+    // Plays the move from the current node and goes to it. A move that
+    // isn't the one of the line starts a variation
+    pub fn play(&mut self, mv: ChessMove) -> Option<NodeId>
+    {
+        let child: NodeId = self.add_move(self.current, mv)?;
+        self.current = child;
+        return Some(child);
+    }
+
+    // Same as `GameTree::play` for a move in SAN
+    pub fn play_san(&mut self, san: &str) -> Option<NodeId>
+    {
+        let mv: ChessMove = self.__node(self.current).board.clone().from_san(san)?;
+        return self.play(mv);
+    }
+
+    // Goes to the next move of the line, returns false at its end
+    pub fn forward(&mut self) -> bool
+    {
+        return match self.__node(self.current).children.first()
+        {
+            Some(child) => { self.current = *child; true },
+            None => false,
+        };
+    }
+
+    // Goes to the move before, returns false at the root
+    pub fn back(&mut self) -> bool
+    {
+        return match self.__node(self.current).parent
+        {
+            Some(parent) => { self.current = parent; true },
+            None => false,
+        };
+    }
+
+    // Returns false if there's no such node
+    pub fn go_to(&mut self, node: NodeId) -> bool
+    {
+        if !self.contains(node)
+        {
+            return false;
+        }
+
+        self.current = node;
+        return true;
+    }
+
+    pub fn go_to_start(&mut self)
+    {
+        self.current = self.root();
+    }
+
+    // Goes to the end of the line the current node is in
+    pub fn go_to_end(&mut self)
+    {
+        while self.forward() {}
+    }
+
+    // The nodes from the root to the node, both included
+    pub fn path(&self, node: NodeId) -> Vec<NodeId>
+    {
+        let mut path: Vec<NodeId> = vec![node];
+        while let Some(parent) = self.__node(*path.last().unwrap()).parent
+        {
+            path.push(parent);
+        }
+
+        path.reverse();
+        return path;
+    }
+
+    // The nodes of the mainline after the root
+    pub fn mainline(&self) -> Vec<NodeId>
+    {
+        let mut line: Vec<NodeId> = Vec::new();
+        let mut node: NodeId = self.root();
+        while let Some(child) = self.__node(node).children.first()
+        {
+            line.push(*child);
+            node = *child;
+        }
+
+        return line;
+    }
+
+    pub fn is_mainline(&self, node: NodeId) -> bool
+    {
+        return self.path(node).windows(2).all(|w| self.__node(w[0]).children[0] == w[1]);
+    }
+
+    // Moves the variation of the node one place up among the moves of its
+    // parent, so the first variation becomes the line. Returns false if
+    // it's already the line
+    pub fn promote(&mut self, node: NodeId) -> bool
+    {
+        let parent: NodeId = match self.__node(node).parent
+        {
+            Some(parent) => parent,
+            None => return false,
+        };
+
+        let children: &mut Vec<NodeId> = &mut self.__node_mut(parent).children;
+        let i: usize = children.iter().position(|c| *c == node).unwrap();
+        if i == 0
+        {
+            return false;
+        }
+
+        children.swap(i-1, i);
+        return true;
+    }
+
+    // Makes every move up to the node the first one of its parent, so the
+    // node ends up on the mainline
+    pub fn promote_to_mainline(&mut self, node: NodeId)
+    {
+        for w in self.path(node).windows(2)
+        {
+            let children: &mut Vec<NodeId> = &mut self.__node_mut(w[0]).children;
+            let i: usize = children.iter().position(|c| *c == w[1]).unwrap();
+            children[..=i].rotate_right(1);
+        }
+    }
+
+    // Deletes the node and everything after it. The root can't be deleted.
+    // If the current node was deleted the tree goes to the parent
+    pub fn delete(&mut self, node: NodeId) -> bool
+    {
+        let parent: NodeId = match self.contains(node).then(|| self.__node(node).parent).flatten()
+        {
+            Some(parent) => parent,
+            None => return false,
+        };
+
+        if self.path(self.current).contains(&node)
+        {
+            self.current = parent;
+        }
+
+        self.__node_mut(parent).children.retain(|c| *c != node);
+
+        let mut stack: Vec<NodeId> = vec![node];
+        while let Some(n) = stack.pop()
+        {
+            if let Some(removed) = self.nodes[n].take()
+            {
+                stack.extend(removed.children);
+            }
+        }
+
+        return true;
+    }
+
+    // Writes the moves in PGN, with the variations in parentheses, the NAGs
+    // and the comments. The comment of the root comes first
+    pub fn to_movetext(&self) -> String
+    {
+        let mut text: String = String::new();
+
+        let root: &Node = self.__node(self.root());
+        if !root.annotation.is_empty()
+        {
+            text.push_str(&format!("{{ {} }}", root.annotation.to_comment(&root.board)));
+        }
+
+        self.__write_line(self.root(), &mut text, true);
+        return text;
+    }
+
+    // Writes the moves after the node, with the variations of every move
+    // right after it
+    fn __write_line(&self, mut node: NodeId, text: &mut String, mut number: bool)
+    {
+        while let Some(first) = self.__node(node).children.first()
+        {
+            let children: &[NodeId] = &self.__node(node).children;
+            self.__write_move(*first, text, number);
+
+            for variation in children[1..].iter()
+            {
+                Self::__push_token(text, "(");
+                self.__write_move(*variation, text, true);
+                self.__write_line(*variation, text, false);
+                text.push(')');
+            }
+
+            // The move number is repeated after anything that interrupts
+            // the line
+            number = children.len() > 1 || !self.__node(*first).annotation.is_empty();
+            node = *first;
+        }
+    }
+
+    fn __write_move(&self, node: NodeId, text: &mut String, number: bool)
+    {
+        let before: &ChessBoard = &self.__node(self.__node(node).parent.unwrap()).board;
+        if before.white_turn()
+        {
+            Self::__push_token(text, &format!("{}.", before.fullmove()));
+        }
+        else if number
+        {
+            Self::__push_token(text, &format!("{}...", before.fullmove()));
+        }
+
+        let _node: &Node = self.__node(node);
+        Self::__push_token(text, &_node.san);
+        for nag in _node.annotation.get_nags()
+        {
+            Self::__push_token(text, &format!("${}", nag));
+        }
+
+        if !_node.annotation.is_empty()
+        {
+            Self::__push_token(text, &format!("{{ {} }}", _node.annotation.to_comment(&_node.board)));
+        }
+    }
+
+    // Adds a space before the token, unless it starts the text or a
+    // variation
+    fn __push_token(text: &mut String, token: &str)
+    {
+        if !text.is_empty() && !text.ends_with('(')
+        {
+            text.push(' ');
+        }
+        text.push_str(token);
+    }
+
+    fn __node(&self, node: NodeId) -> &Node
+    {
+        return self.nodes[node].as_ref().expect("The node was deleted");
+    }
+
+    fn __node_mut(&mut self, node: NodeId) -> &mut Node
+    {
+        return self.nodes[node].as_mut().expect("The node was deleted");
+    }
+}
+
+impl Default for GameTree
+{
+    fn default() -> Self
+    {
+        return Self::new(ChessBoard::init_position());
+    }
+}
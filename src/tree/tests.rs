@@ -0,0 +1,112 @@
+use super::*;
+use core::time::Duration;
+
+#[test]
+fn test_tree_variations()
+{
+    let mut tree: GameTree = GameTree::default();
+    let e4: NodeId = tree.play_san("e4").unwrap();
+    let e5: NodeId = tree.play_san("e5").unwrap();
+    tree.play_san("Nf3").unwrap();
+
+    // 1... c5 as a variation, and 1... e5 again doesn't add a node
+    tree.go_to(e4);
+    let c5: NodeId = tree.play_san("c5").unwrap();
+    let nf3: NodeId = tree.play_san("Nf3").unwrap();
+    assert_eq!(tree.add_move(e4, ChessMove::drag(0o64, 0o44)), Some(e5));
+    assert_eq!(tree.get_children(e4), &[e5, c5]);
+    assert!(tree.play_san("Ke3").is_none());
+
+    assert_eq!(tree.get_board(nf3).to_fen(), "rnbqkbnr/pp1ppppp/8/2p5/4P3/5N2/PPPP1PPP/RNBQKB1R b KQkq - 1 2");
+    assert_eq!(tree.legal_moves(c5).len(), 30);
+    assert!(!tree.is_mainline(nf3));
+    assert_eq!(tree.path(nf3), vec![tree.root(), e4, c5, nf3]);
+
+    // Navigating
+    assert!(tree.back());
+    assert_eq!(tree.current(), c5);
+    tree.go_to_start();
+    assert!(!tree.back());
+    tree.go_to_end();
+    assert_eq!(tree.get_san(tree.current()), Some("Nf3"));
+    assert!(tree.is_mainline(tree.current()));
+
+    // Promoting the variation
+    tree.promote_to_mainline(nf3);
+    assert!(tree.is_mainline(nf3));
+    assert_eq!(tree.mainline(), vec![e4, c5, nf3]);
+    assert!(!tree.promote(c5));
+    assert!(tree.promote(e5));
+    assert_eq!(tree.get_children(e4), &[e5, c5]);
+
+    // Deleting the current line
+    tree.go_to(nf3);
+    assert!(tree.delete(c5));
+    assert!(!tree.contains(nf3));
+    assert_eq!(tree.current(), e4);
+    assert_eq!(tree.get_children(e4), &[e5]);
+    assert!(!tree.delete(tree.root()));
+}
+
+#[test]
+fn test_tree_annotations()
+{
+    let board: ChessBoard = ChessBoard::init_position();
+    let text: &str = "Best by test [%clk 0:01:05.5] [%eval -0.35] [%csl Ge4,Rd5] [%cal Gg1f3] [%unknown x]";
+    let annotation: Annotation = Annotation::from_comment(text, &board);
+
+    assert_eq!(annotation.get_comment(), "Best by test [%unknown x]");
+    assert_eq!(annotation.get_clock(), Some(Duration::from_millis(65_500)));
+    assert_eq!(annotation.get_eval(), Some(Eval::Centipawns(-35)));
+    assert_eq!(annotation.get_highlights(), &[(MarkColor::Green, 0o34), (MarkColor::Red, 0o43)]);
+    assert_eq!(annotation.get_arrows(), &[(MarkColor::Green, 0o06, 0o25)]);
+    assert_eq!(annotation.to_comment(&board),
+        "Best by test [%unknown x] [%clk 0:01:05] [%eval -0.35] [%csl Ge4,Rd5] [%cal Gg1f3]");
+
+    let annotation: Annotation = Annotation::from_comment("[%eval #-3]", &board);
+    assert_eq!(annotation.get_eval(), Some(Eval::Mate(-3)));
+    assert_eq!(annotation.get_comment(), "");
+
+    // Clocks that don't fit a `Duration` aren't read
+    for text in ["[%clk 1e400]", "[%clk inf]", "[%clk 1e300:00:00]"]
+    {
+        assert_eq!(Annotation::from_comment(text, &board).get_clock(), None);
+    }
+
+    // A `}` can't end the comment early
+    let mut annotation: Annotation = Annotation::default();
+    annotation.set_comment("a} {b");
+    assert_eq!(annotation.to_comment(&board), "a) {b");
+
+    assert_eq!(nag_from_symbol("?!"), Some(6));
+    assert_eq!(nag_from_symbol("+-"), Some(18));
+    assert_eq!(nag_symbol(3), Some("!!"));
+    assert_eq!(nag_symbol(200), None);
+
+    let mut annotation: Annotation = Annotation::default();
+    annotation.toggle_arrow(MarkColor::Blue, 0o14, 0o34);
+    annotation.toggle_arrow(MarkColor::Blue, 0o14, 0o34);
+    assert!(annotation.is_empty());
+}
+
+#[test]
+fn test_tree_movetext()
+{
+    let mut tree: GameTree = GameTree::default();
+    tree.get_annotation_mut(tree.root()).set_comment("Start");
+    let e4: NodeId = tree.play_san("e4").unwrap();
+    tree.play_san("e5").unwrap();
+    tree.play_san("Nf3").unwrap();
+    tree.play_san("Nc6").unwrap();
+
+    tree.go_to(e4);
+    let c5: NodeId = tree.play_san("c5").unwrap();
+    tree.play_san("Nf3").unwrap();
+    let annotation: &mut Annotation = tree.get_annotation_mut(c5);
+    annotation.add_nag(nag_from_symbol("!?").unwrap());
+    annotation.set_comment("Sicilian");
+    annotation.toggle_highlight(MarkColor::Yellow, 0o42);
+
+    assert_eq!(tree.to_movetext(),
+        "{ Start } 1. e4 e5 (1... c5 $5 { Sicilian [%csl Yc5] } 2. Nf3) 2. Nf3 Nc6");
+}
@@ -0,0 +1,88 @@
+use super::*;
+
+// Losing all pieces wins the game. Capturing is forced, the king is a
+// normal piece that can be captured and pawns can be promoted to a king.
+// A color without legal moves wins as well
+pub struct Antichess;
+
+impl Antichess
+{
+    // Checks if the color that is about to move can capture anything. The
+    // answer is kept until the position changes
+    fn __can_capture(board: &mut ChessBoard) -> bool
+    {
+        let hash: u64 = board.hash();
+        if let Some((_hash, can_capture)) = board._can_capture
+        {
+            if _hash == hash { return can_capture; }
+        }
+
+        let can_capture: bool = Self::__find_capture(board);
+        board._can_capture = Some((hash, can_capture));
+
+        return can_capture;
+    }
+
+    fn __find_capture(board: &mut ChessBoard) -> bool
+    {
+        for from in 0..board.__size()
+        {
+            let _el: ChessPiece = board.get_piece(from);
+            if _el.is_empty() || _el.is_white() != board.white_turn()
+            {
+                continue;
+            }
+
+            for to in 0..board.__size()
+            {
+                if board.is_capture(from, to) && board.__verify_move(from, to)
+                {
+                    return true;
+                }
+            }
+        }
+
+        return false;
+    }
+}
+
+impl Variant for Antichess
+{
+    fn name(&self) -> &str
+    {
+        return "Antichess";
+    }
+
+    // No castling either
+    fn start_fen(&self) -> &str
+    {
+        return "rnbqkbnr/pppppppp/8/8/8/8/PPPPPPPP/RNBQKBNR w - - 0 1";
+    }
+
+    fn has_king(&self, _white: bool) -> bool
+    {
+        return false;
+    }
+
+    fn is_legal(&self, board: &mut ChessBoard, from: u8, to: u8) -> bool
+    {
+        if !board.__verify_move(from, to)
+        {
+            return false;
+        }
+
+        // Other moves are only allowed if there's nothing to capture
+        return board.is_capture(from, to) || !Self::__can_capture(board);
+    }
+
+    // Having no pieces left is one way of having no legal moves
+    fn no_moves_state(&self, board: &mut ChessBoard) -> (ChessState, Option<bool>)
+    {
+        return (ChessState::VariantWin, Some(board.white_turn()));
+    }
+
+    fn can_promote_to(&self, _type: ChessPieceType) -> bool
+    {
+        return _type != ChessPieceType::Pawn;
+    }
+}
@@ -0,0 +1,165 @@
+use super::*;
+
+// Every capture is an explosion that takes away the capturing piece, the
+// captured one and all pieces apart from pawns around them. Exploding the
+// enemy king wins the game, so kings can't capture, and while the kings
+// stand next to each other neither of them can be in check
+pub struct Atomic;
+
+impl Atomic
+{
+    // The blocks an explosion on `to` reaches: `to` and the blocks around
+    // it, at most 9
+    fn __blast(board: &ChessBoard, to: u8) -> impl Iterator<Item = u8>
+    {
+        let pos: ChessPos = board.__pos(to);
+        let (width, height) = (board.width() as i8, board.height() as i8);
+
+        return (-1i8..=1).flat_map(move |dy| (-1i8..=1).map(move |dx| (pos.get_x() as i8 + dx, pos.get_y() as i8 + dy)))
+            .filter(move |(x, y)| *x >= 0 && *y >= 0 && *x < width && *y < height)
+            .map(move |(x, y)| y as u8 * width as u8 + x as u8);
+    }
+
+    // Takes away the piece on `to` and all pieces apart from pawns around it
+    fn __explode(board: &mut ChessBoard, to: u8)
+    {
+        board.__remove_piece(to);
+
+        for coords in Self::__blast(board, to)
+        {
+            let _el: ChessPiece = board.get_piece(coords);
+            if _el != ChessPiece::WPawn && _el != ChessPiece::BPawn
+            {
+                board.__remove_piece(coords);
+            }
+        }
+
+        // Rooks that exploded can't castle anymore
+        for side in 0..4
+        {
+            if board._castling[side].is_some_and(|rook| board.get_piece(rook).is_empty())
+            {
+                board._castling[side] = None;
+            }
+        }
+    }
+
+    fn __has_king(board: &ChessBoard, white: bool) -> bool
+    {
+        let k_coords: u8 = if white { board._w_king } else { board._b_king };
+        let _king: ChessPiece = if white { ChessPiece::WKing } else { ChessPiece::BKing };
+
+        return board.get_piece(k_coords) == _king;
+    }
+
+    fn __kings_touch(board: &ChessBoard) -> bool
+    {
+        let w: ChessPos = board.__pos(board._w_king);
+        let b: ChessPos = board.__pos(board._b_king);
+
+        return w.get_x().abs_diff(b.get_x()) < 2 && w.get_y().abs_diff(b.get_y()) < 2;
+    }
+}
+
+impl Variant for Atomic
+{
+    fn name(&self) -> &str
+    {
+        return "Atomic";
+    }
+
+    fn is_legal(&self, board: &mut ChessBoard, from: u8, to: u8) -> bool
+    {
+        if !board.__verify_move(from, to)
+        {
+            return false;
+        }
+
+        let from_el: ChessPiece = board.get_piece(from);
+        let to_el: ChessPiece = board.get_piece(to);
+        let white: bool = from_el.is_white();
+        let _is_king: bool = from_el == ChessPiece::WKing || from_el == ChessPiece::BKing;
+
+        // The king would blow itself up
+        if _is_king && !to_el.is_empty()
+        {
+            return false;
+        }
+
+        // Play the move, look at what's left and put everything back. The
+        // blocks the move can change are saved first: `from`, the pawn taken
+        // en passant and the blast around `to`
+        let capture: bool = board.is_capture(from, to);
+        let victim: Option<u8> = board.__en_passant_victim(from, to);
+
+        let mut saved: [(u8, ChessPiece, bool); 11] = [(0, ChessPiece::Empty, false); 11];
+        let mut count: usize = 0;
+        for coords in [Some(from), victim].into_iter().flatten().chain(Self::__blast(board, to))
+        {
+            saved[count] = (coords, board.board[coords as usize], board._promoted[coords as usize]);
+            count += 1;
+        }
+        let (castling, kings, piece_count) = (board._castling, (board._w_king, board._b_king), board._piece_count);
+
+        if let Some(victim) = victim
+        {
+            board.__remove_piece(victim);
+        }
+        board.board[from as usize] = ChessPiece::Empty;
+        board.board[to as usize] = from_el;
+        board.__locate_kings();
+        if capture
+        {
+            Self::__explode(board, to);
+        }
+
+        let legal: bool = Self::__has_king(board, white) && (!Self::__has_king(board, !white)
+            || Self::__kings_touch(board) || !board.is_check(white));
+
+        for (coords, _el, promoted) in saved[..count].iter()
+        {
+            board.board[*coords as usize] = *_el;
+            board._promoted[*coords as usize] = *promoted;
+        }
+        board._castling = castling;
+        (board._w_king, board._b_king) = kings;
+        board._piece_count = piece_count;
+
+        return legal;
+    }
+
+    fn after_move(&self, board: &mut ChessBoard, _from: u8, to: u8, captured: ChessPiece)
+    {
+        if !captured.is_empty()
+        {
+            Self::__explode(board, to);
+        }
+    }
+
+    fn check_state(&self, board: &mut ChessBoard) -> Option<(ChessState, Option<bool>)>
+    {
+        if !Self::__has_king(board, true)
+        {
+            return Some((ChessState::VariantWin, Some(false)));
+        }
+
+        if !Self::__has_king(board, false)
+        {
+            return Some((ChessState::VariantWin, Some(true)));
+        }
+
+        return None;
+    }
+
+    // A king next to the enemy king isn't in check
+    fn no_moves_state(&self, board: &mut ChessBoard) -> (ChessState, Option<bool>)
+    {
+        let white: bool = board.white_turn();
+        if board.is_check(white) && !Self::__kings_touch(board)
+        {
+            return (ChessState::Checkmate, Some(!white));
+        }
+
+        return (ChessState::Stalemate, None);
+    }
+}
@@ -0,0 +1,113 @@
+use super::*;
+
+// Captured pieces go into the pocket of the capturing color and can be
+// dropped back on the board instead of making a move, see
+// `ChessBoard::drop_piece`
+pub struct Crazyhouse;
+
+impl Variant for Crazyhouse
+{
+    fn name(&self) -> &str
+    {
+        return "Crazyhouse";
+    }
+
+    fn has_pockets(&self) -> bool
+    {
+        return true;
+    }
+}
+
+// The rules of a single Bughouse board, the same as Crazyhouse. The
+// captured pieces are passed to the partner by `BughouseGame`
+pub struct Bughouse;
+
+impl Variant for Bughouse
+{
+    fn name(&self) -> &str
+    {
+        return "Bughouse";
+    }
+
+    fn has_pockets(&self) -> bool
+    {
+        return true;
+    }
+}
+
+// Two Bughouse boards played at once by two teams. The player with white
+// on one board is the partner of the player with black on the other one,
+// and every piece captured goes into the pocket of the partner. The game
+// is over as soon as it's over on one of the boards
+pub struct BughouseGame
+{
+    boards: [ChessBoard; 2],
+}
+
+impl BughouseGame
+{
+    pub fn new() -> BughouseGame
+    {
+        let board: ChessBoard = ChessBoard::init_variant(Arc::new(Bughouse));
+        return BughouseGame { boards: [board.clone(), board] };
+    }
+
+    // Get one of the boards, 0 or 1
+    pub fn get_board(&self, board: usize) -> &ChessBoard
+    {
+        return &self.boards[board];
+    }
+
+    // `ChessBoard::drag` on one of the boards
+    pub fn drag(&mut self, board: usize, from: u8, to: u8)
+    {
+        if self.get_state() != ChessState::On
+        {
+            return;
+        }
+
+        let white: bool = self.boards[board].white_turn();
+        let pocket: Vec<ChessPieceType> = self.boards[board].get_pocket(white).to_vec();
+
+        self.boards[board].drag(from, to);
+
+        // Whatever went into the own pocket was captured, hand it over
+        let mut captured: Vec<ChessPieceType> = self.boards[board].get_pocket(white).to_vec();
+        for _type in pocket
+        {
+            let i: usize = captured.iter().position(|t| *t == _type).unwrap();
+            captured.remove(i);
+        }
+
+        for _type in captured
+        {
+            self.boards[board].take_from_pocket(white, _type);
+            self.boards[1-board].add_to_pocket(!white, _type);
+        }
+    }
+
+    // `ChessBoard::drop_piece` on one of the boards
+    pub fn drop_piece(&mut self, board: usize, _type: ChessPieceType, to: u8)
+    {
+        if self.get_state() != ChessState::On
+        {
+            return;
+        }
+
+        self.boards[board].drop_piece(_type, to);
+    }
+
+    // The state of the board where the game ended, or `ChessState::On`
+    pub fn get_state(&self) -> ChessState
+    {
+        return self.boards.iter().map(|b| b.get_state()).find(|s| *s != ChessState::On).unwrap_or(ChessState::On);
+    }
+}
+
+impl Default for BughouseGame
+{
+    fn default() -> Self
+    {
+        return Self::new();
+    }
+}
@@ -0,0 +1,48 @@
+use super::*;
+
+// White plays with a horde of pawns and no king against the default black
+// army. Black wins by capturing every white piece, white by checkmating.
+// White pawns on the first rank can take two steps as well
+pub struct Horde;
+
+impl Variant for Horde
+{
+    fn name(&self) -> &str
+    {
+        return "Horde";
+    }
+
+    fn start_fen(&self) -> &str
+    {
+        return "rnbqkbnr/pppppppp/8/1PP2PP1/PPPPPPPP/PPPPPPPP/PPPPPPPP/PPPPPPPP w kq - 0 1";
+    }
+
+    fn has_king(&self, white: bool) -> bool
+    {
+        return !white;
+    }
+
+    fn is_legal(&self, board: &mut ChessBoard, from: u8, to: u8) -> bool
+    {
+        // Two steps from the first rank, the blocks passed have to be empty
+        let _first_rank: bool = from < board.width() && board.get_piece(from) == ChessPiece::WPawn;
+        if _first_rank && board.white_turn() && to == from + 2*board.width()
+        {
+            let _empty: bool = board.get_piece(from + board.width()).is_empty() && board.get_piece(to).is_empty();
+            return _empty && board.__is_safe_move(from, to);
+        }
+
+        return board.__verify_move(from, to) && board.__is_safe_move(from, to);
+    }
+
+    fn check_state(&self, board: &mut ChessBoard) -> Option<(ChessState, Option<bool>)>
+    {
+        let _white_left: bool = board.get_squares().iter().any(|p| p.is_white());
+        if !_white_left
+        {
+            return Some((ChessState::VariantWin, Some(false)));
+        }
+
+        return None;
+    }
+}
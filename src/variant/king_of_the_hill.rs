@@ -0,0 +1,42 @@
+use super::*;
+
+// Bringing the king to one of the 4 center blocks wins the game
+pub struct KingOfTheHill;
+
+impl KingOfTheHill
+{
+    fn __on_hill(board: &ChessBoard, coords: u8) -> bool
+    {
+        let pos: ChessPos = board.__pos(coords);
+        let x: u8 = pos.get_x();
+        let y: u8 = pos.get_y();
+
+        // d4, e4, d5 and e5 on the default board
+        let _hill_x: bool = x == board.width()/2-1 || x == board.width()/2;
+        let _hill_y: bool = y == board.height()/2-1 || y == board.height()/2;
+
+        return _hill_x && _hill_y;
+    }
+}
+
+impl Variant for KingOfTheHill
+{
+    fn name(&self) -> &str
+    {
+        return "King of the Hill";
+    }
+
+    fn check_state(&self, board: &mut ChessBoard) -> Option<(ChessState, Option<bool>)>
+    {
+        // Only the color that just moved can have reached the hill
+        let white: bool = !board.white_turn();
+        let k_coords: u8 = if white { board._w_king } else { board._b_king };
+
+        if Self::__on_hill(board, k_coords)
+        {
+            return Some((ChessState::VariantWin, Some(white)));
+        }
+
+        return None;
+    }
+}
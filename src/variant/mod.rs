@@ -0,0 +1,152 @@
+// Rule sets on top of the default chess rules.
+//
+// A `Variant` is picked when the board is built, with
+// `ChessBoard::init_variant` or `ChessBoard::from_fen_variant`, and can
+// change which moves are legal, what happens after a move and when the game
+// is over. Every method has a default that follows the default chess rules,
+// so a variant only overrides what it changes.
+//
+// The built in variants follow the rules used by lichess:
+// `KingOfTheHill`, `ThreeCheck`, `Antichess`, `Atomic`, `Horde`,
+// `RacingKings` and `Crazyhouse`. `BughouseGame` links two boards that
+// pass the captured pieces to each other
+
+use super::*;
+
+mod king_of_the_hill;
+mod three_check;
+mod antichess;
+mod atomic;
+mod horde;
+mod racing_kings;
+mod crazyhouse;
+
+#[cfg(test)]
+mod tests;
+
+pub use king_of_the_hill::KingOfTheHill;
+pub use three_check::ThreeCheck;
+pub use antichess::Antichess;
+pub use atomic::Atomic;
+pub use horde::Horde;
+pub use racing_kings::RacingKings;
+pub use crazyhouse::{Bughouse, BughouseGame, Crazyhouse};
+
+pub const STANDARD_FEN: &str = "rnbqkbnr/pppppppp/8/8/8/8/PPPPPPPP/RNBQKBNR w KQkq - 0 1";
+
+pub trait Variant: Send + Sync
+{
+    // Name of the variant as written in the `Variant` tag of PGN
+    fn name(&self) -> &str;
+
+    // Start position of the variant
+    fn start_fen(&self) -> &str
+    {
+        return STANDARD_FEN;
+    }
+
+    // If the color has a king that has to be kept out of check. Positions
+    // without a king for such a color are not accepted
+    fn has_king(&self, _white: bool) -> bool
+    {
+        return true;
+    }
+
+    // Checks if the move from `from` to `to` is allowed for the color that
+    // is about to move. Castling is handled by the board itself
+    fn is_legal(&self, board: &mut ChessBoard, from: u8, to: u8) -> bool
+    {
+        return board.__verify_move(from, to) && board.__is_safe_move(from, to);
+    }
+
+    // Called after a move is made, before the turn is handed over.
+    // `captured` is the piece that was on `to` before the move
+    fn after_move(&self, _board: &mut ChessBoard, _from: u8, _to: u8, _captured: ChessPiece)
+    {
+    }
+
+    // Checks if the game is over by a rule of the variant, before the
+    // color that is about to move is checked for legal moves. Returns the
+    // new state and the color that won, true for white
+    fn check_state(&self, _board: &mut ChessBoard) -> Option<(ChessState, Option<bool>)>
+    {
+        return None;
+    }
+
+    // The state of the game when the color that is about to move has no
+    // legal moves left
+    fn no_moves_state(&self, board: &mut ChessBoard) -> (ChessState, Option<bool>)
+    {
+        let white: bool = board.white_turn();
+        if board.is_check(white)
+        {
+            return (ChessState::Checkmate, Some(!white));
+        }
+
+        return (ChessState::Stalemate, None);
+    }
+
+    // Piece types a pawn can be promoted to
+    fn can_promote_to(&self, _type: ChessPieceType) -> bool
+    {
+        return _type != ChessPieceType::King && _type != ChessPieceType::Pawn;
+    }
+
+    // If the checks given are counted and written into FEN as `+W+B`
+    fn counts_checks(&self) -> bool
+    {
+        return false;
+    }
+
+    // If captured pieces go into the pocket of the capturing color, from
+    // where they can be dropped back on the board. The pockets are written
+    // into FEN as `[Qn]` after the pieces
+    fn has_pockets(&self) -> bool
+    {
+        return false;
+    }
+}
+
+// The default chess rules
+pub struct Standard;
+
+impl Variant for Standard
+{
+    fn name(&self) -> &str
+    {
+        return "Standard";
+    }
+}
+
+// Get a built in variant by its name, as written in the `Variant` tag of
+// PGN. Case, spaces and dashes don't matter, so `kingofthehill` works too
+pub fn from_name(name: &str) -> Option<Arc<dyn Variant>>
+{
+    let key: String = name.chars().filter(|c| c.is_alphanumeric()).collect::<String>().to_lowercase();
+
+    let variant: Arc<dyn Variant> = match key.as_str()
+    {
+        "standard" | "chess" => Arc::new(Standard),
+        "kingofthehill" | "koth" => Arc::new(KingOfTheHill),
+        "threecheck" | "3check" => Arc::new(ThreeCheck),
+        "antichess" | "giveaway" => Arc::new(Antichess),
+        "atomic" => Arc::new(Atomic),
+        "horde" => Arc::new(Horde),
+        "racingkings" => Arc::new(RacingKings),
+        "crazyhouse" => Arc::new(Crazyhouse),
+        "bughouse" => Arc::new(Bughouse),
+        _ => return None,
+    };
+
+    return Some(variant);
+}
+
+impl ChessBoard
+{
+    // Initialize a chess board with the start position of the variant
+    pub fn init_variant(variant: Arc<dyn Variant>) -> ChessBoard
+    {
+        let fen: String = variant.start_fen().to_string();
+        return ChessBoard::from_fen_variant(&fen, variant).expect("Invalid start position of the variant");
+    }
+}
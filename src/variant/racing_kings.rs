@@ -0,0 +1,88 @@
+use super::*;
+
+// The first king to reach the last rank wins. Checks are not allowed at
+// all. If white gets there first black has one move to reach it as well,
+// which makes the game a draw
+pub struct RacingKings;
+
+impl RacingKings
+{
+    fn __on_goal(board: &ChessBoard, white: bool) -> bool
+    {
+        let k_coords: u8 = if white { board._w_king } else { board._b_king };
+        return board.__pos(k_coords).get_y() == board.height()-1;
+    }
+}
+
+impl Variant for RacingKings
+{
+    fn name(&self) -> &str
+    {
+        return "Racing Kings";
+    }
+
+    fn start_fen(&self) -> &str
+    {
+        return "8/8/8/8/8/8/krbnNBRK/qrbnNBRQ w - - 0 1";
+    }
+
+    fn is_legal(&self, board: &mut ChessBoard, from: u8, to: u8) -> bool
+    {
+        if !board.__verify_move(from, to) || !board.__is_safe_move(from, to)
+        {
+            return false;
+        }
+
+        // Giving check isn't allowed either: play the move, look for the
+        // check and put the pieces back
+        let from_el: ChessPiece = board.get_piece(from);
+        let to_el: ChessPiece = board.get_piece(to);
+        board.board[from as usize] = ChessPiece::Empty;
+        board.board[to as usize] = from_el;
+
+        let check: bool = board.is_check(!from_el.is_white());
+
+        board.board[from as usize] = from_el;
+        board.board[to as usize] = to_el;
+
+        return !check;
+    }
+
+    fn check_state(&self, board: &mut ChessBoard) -> Option<(ChessState, Option<bool>)>
+    {
+        let white: bool = Self::__on_goal(board, true);
+        let black: bool = Self::__on_goal(board, false);
+
+        if white && black
+        {
+            return Some((ChessState::VariantDraw, None));
+        }
+
+        if black
+        {
+            return Some((ChessState::VariantWin, Some(false)));
+        }
+
+        if !white
+        {
+            return None;
+        }
+
+        // Black still gets to move if its king can reach the last rank
+        if !board.white_turn()
+        {
+            let y: u8 = board.height()-1;
+            let b_king: u8 = board._b_king;
+            for x in 0..board.width()
+            {
+                let to: u8 = board.__conv(x, y);
+                if board.__is_legal(b_king, to)
+                {
+                    return None;
+                }
+            }
+        }
+
+        return Some((ChessState::VariantWin, Some(true)));
+    }
+}
@@ -0,0 +1,231 @@
+use super::*;
+use fen::FenError;
+
+fn board(fen: &str, variant: Arc<dyn Variant>) -> ChessBoard
+{
+    return ChessBoard::from_fen_variant(fen, variant).unwrap();
+}
+
+#[test]
+fn test_variant_king_of_the_hill()
+{
+    let mut chess_board: ChessBoard = board("4k3/8/8/8/8/4K3/8/8 w - - 0 1", Arc::new(KingOfTheHill));
+    assert!(chess_board.get_variant().name() == "King of the Hill");
+    assert!(from_name("King of the Hill").unwrap().name() == "King of the Hill");
+    assert!(from_name("three-check").unwrap().name() == "Three-check");
+    assert!(from_name("chess 2").is_none());
+
+    // e3 -> e4
+    chess_board.drag(0o24, 0o34);
+    assert!(chess_board.get_state() == ChessState::VariantWin);
+    assert!(chess_board.get_winner() == Some(true));
+}
+
+#[test]
+fn test_variant_three_check()
+{
+    let mut chess_board: ChessBoard = ChessBoard::init_variant(Arc::new(ThreeCheck));
+    assert_eq!(chess_board.to_fen(), "rnbqkbnr/pppppppp/8/8/8/8/PPPPPPPP/RNBQKBNR w KQkq - 0 1 +0+0");
+
+    // 1. e4 d5 2. Bb5 (check)
+    chess_board.drag(0o14, 0o34);
+    chess_board.drag(0o63, 0o43);
+    chess_board.drag(0o05, 0o41);
+    assert!(chess_board.get_checks() == [1, 0]);
+    assert!(chess_board.to_fen().ends_with(" +1+0"));
+
+    // Two more checks to go
+    let mut chess_board: ChessBoard = board("4k3/8/8/8/8/8/8/R3K3 w - - 0 1 +2+0", Arc::new(ThreeCheck));
+    chess_board.drag(0o00, 0o70);
+    assert!(chess_board.get_state() == ChessState::VariantWin);
+    assert!(chess_board.get_winner() == Some(true));
+
+    assert!(ChessBoard::from_fen_variant("4k3/8/8/8/8/8/8/R3K3 w - - 0 1 +2", Arc::new(ThreeCheck)).is_err());
+}
+
+#[test]
+fn test_variant_antichess()
+{
+    let mut chess_board: ChessBoard = ChessBoard::init_variant(Arc::new(Antichess));
+    assert!(chess_board.castling_rights() == [None; 4]);
+
+    // 1. e4 b5, white has to take
+    chess_board.drag(0o14, 0o34);
+    chess_board.drag(0o61, 0o41);
+    chess_board.drag(0o13, 0o33);
+    assert!(chess_board.white_turn());
+    chess_board.drag(0o05, 0o41);
+    assert!(!chess_board.white_turn());
+
+    // The king can be left in check and be taken
+    let mut chess_board: ChessBoard = board("8/8/8/8/8/8/1k6/K7 w - - 0 1", Arc::new(Antichess));
+    chess_board.drag(0o00, 0o11);
+    assert!(chess_board.get_state() == ChessState::VariantWin);
+    assert!(chess_board.get_winner() == Some(false));
+
+    // Taking en passant is a capture as well, so it's the only move
+    let mut chess_board: ChessBoard = board("8/8/8/8/5p2/8/4P3/7k w - - 0 1", Arc::new(Antichess));
+    chess_board.drag(0o14, 0o34);
+    assert!(chess_board.legal_moves() == [ChessMove::drag(0o35, 0o24)]);
+
+    // Promotion to a king is allowed
+    let mut chess_board: ChessBoard = board("8/P7/8/8/8/8/8/7k w - - 0 1", Arc::new(Antichess));
+    chess_board.set_promotion(ChessPieceType::King);
+    chess_board.drag(0o60, 0o70);
+    assert!(chess_board.get_piece(0o70) == ChessPiece::WKing);
+
+    // The camel can't take the pawn until it moves like a knight
+    let mut chess_board: ChessBoard = board("8/8/8/8/8/8/2p5/L6k w - - 0 1", Arc::new(Antichess));
+    assert!(!chess_board.legal_moves().contains(&ChessMove::drag(0o00, 0o12)));
+    chess_board.register_piece(ChessPieceType::Camel, "N").unwrap();
+    assert!(chess_board.legal_moves() == [ChessMove::drag(0o00, 0o12)]);
+}
+
+#[test]
+fn test_variant_atomic()
+{
+    // Taking the knight on f6 blows up the king on e7
+    let mut chess_board: ChessBoard = board("8/4k3/5n2/8/7Q/8/8/4K3 w - - 0 1", Arc::new(Atomic));
+    chess_board.drag(0o37, 0o55);
+    assert!(chess_board.get_piece(0o55).is_empty());
+    assert!(chess_board.get_piece(0o64).is_empty());
+    assert!(chess_board.get_state() == ChessState::VariantWin);
+    assert!(chess_board.get_winner() == Some(true));
+
+    // Pawns survive explosions
+    let mut chess_board: ChessBoard = board("4k3/8/8/3p4/4p3/3PR3/8/4K3 w - - 0 1", Arc::new(Atomic));
+    chess_board.drag(0o24, 0o34);
+    assert!(chess_board.get_piece(0o34).is_empty());
+    assert!(chess_board.get_piece(0o43) == ChessPiece::BPawn);
+    assert!(chess_board.get_piece(0o23) == ChessPiece::WPawn);
+
+    // Kings can't capture and can stand next to each other
+    let mut chess_board: ChessBoard = board("8/8/8/8/4k3/3Q4/8/3K4 w - - 0 1", Arc::new(Atomic));
+    chess_board.drag(0o03, 0o13);
+    chess_board.drag(0o34, 0o23);
+    assert!(chess_board.get_piece(0o34) == ChessPiece::BKing);
+    chess_board.drag(0o34, 0o24);
+    assert!(chess_board.get_piece(0o24) == ChessPiece::BKing);
+
+    // Trying the captures leaves the board as it was
+    let mut chess_board: ChessBoard = board("r3k2r/1pp2ppp/8/3pP3/8/2n5/PPP2PPP/R3K2R w KQkq d6 0 1", Arc::new(Atomic));
+    let before: ChessBoard = chess_board.clone();
+    assert!(chess_board.legal_moves().contains(&ChessMove::drag(0o44, 0o53)));
+    assert!(chess_board == before);
+    assert!(chess_board.castling_rights() == before.castling_rights());
+}
+
+#[test]
+fn test_variant_horde()
+{
+    let mut chess_board: ChessBoard = ChessBoard::init_variant(Arc::new(Horde));
+    assert!(chess_board.get_state() == ChessState::On);
+
+    // Pawns on the first rank take two steps only if the way is free
+    chess_board.drag(0o00, 0o20);
+    assert!(chess_board.white_turn());
+
+    let mut chess_board: ChessBoard = board("4k3/8/8/8/8/8/8/P7 w - - 0 1", Arc::new(Horde));
+    chess_board.drag(0o00, 0o20);
+    assert!(chess_board.get_piece(0o20) == ChessPiece::WPawn);
+
+    // Capturing the last white piece wins for black
+    let mut chess_board: ChessBoard = board("r3k3/8/8/8/8/8/8/P7 b - - 0 1", Arc::new(Horde));
+    chess_board.drag(0o70, 0o00);
+    assert!(chess_board.get_state() == ChessState::VariantWin);
+    assert!(chess_board.get_winner() == Some(false));
+
+    // White needs no king, black still does
+    assert!(ChessBoard::from_fen_variant("8/8/8/8/8/8/8/P7 w - - 0 1", Arc::new(Horde)).is_err());
+}
+
+#[test]
+fn test_variant_racing_kings()
+{
+    let mut chess_board: ChessBoard = ChessBoard::init_variant(Arc::new(RacingKings));
+    assert!(chess_board.get_state() == ChessState::On);
+
+    // Ne2 -> c3 would give check to the king on a2
+    chess_board.drag(0o14, 0o22);
+    assert!(chess_board.white_turn());
+
+    // White reaches the last rank first but black can follow
+    let mut chess_board: ChessBoard = board("8/1k4K1/8/8/8/8/8/8 w - - 0 1", Arc::new(RacingKings));
+    chess_board.drag(0o66, 0o76);
+    assert!(chess_board.get_state() == ChessState::On);
+    chess_board.drag(0o61, 0o71);
+    assert!(chess_board.get_state() == ChessState::VariantDraw);
+
+    // Black can't follow
+    let mut chess_board: ChessBoard = board("8/6K1/8/1k6/8/8/8/8 w - - 0 1", Arc::new(RacingKings));
+    chess_board.drag(0o66, 0o76);
+    assert!(chess_board.get_state() == ChessState::VariantWin);
+    assert!(chess_board.get_winner() == Some(true));
+}
+
+#[test]
+fn test_variant_crazyhouse()
+{
+    let mut chess_board: ChessBoard = ChessBoard::init_variant(Arc::new(Crazyhouse));
+    assert_eq!(chess_board.to_fen(), "rnbqkbnr/pppppppp/8/8/8/8/PPPPPPPP/RNBQKBNR[] w KQkq - 0 1");
+
+    // 1. e4 d5 2. exd5, the pawn goes into the pocket
+    chess_board.drag(0o14, 0o34);
+    chess_board.drag(0o63, 0o43);
+    chess_board.drag(0o34, 0o43);
+    assert!(chess_board.get_pocket(true) == [ChessPieceType::Pawn]);
+
+    // 2... Qxd5 3. P@e6
+    chess_board.drag(0o73, 0o43);
+    assert!(chess_board.get_pocket(false) == [ChessPieceType::Pawn]);
+    let (_type, to) = chess_board.read_drop("P@e6").unwrap();
+    chess_board.drop_piece(_type, to);
+    assert!(chess_board.get_piece(0o54) == ChessPiece::WPawn);
+    assert!(chess_board.get_pocket(true).is_empty());
+    assert_eq!(chess_board.to_fen(), "rnb1kbnr/ppp1pppp/4P3/3q4/8/8/PPPP1PPP/RNBQKBNR[p] b KQkq - 1 3");
+
+    // No pawns on the first or the last rank, no drops on taken blocks
+    let mut chess_board: ChessBoard = board("4k3/8/8/8/8/8/8/4K3[PN] w - - 0 1", Arc::new(Crazyhouse));
+    chess_board.drop_piece(ChessPieceType::Pawn, 0o70);
+    chess_board.drop_piece(ChessPieceType::Knight, 0o04);
+    chess_board.drop_piece(ChessPieceType::Queen, 0o33);
+    assert!(chess_board.white_turn());
+    chess_board.drop_piece(ChessPieceType::Knight, 0o33);
+    assert!(!chess_board.white_turn());
+    assert_eq!(chess_board.write_drop(ChessPieceType::Knight, 0o33), "N@d4");
+
+    // Promoted pieces are captured as pawns
+    let mut chess_board: ChessBoard = board("3rk3/8/8/8/8/8/8/3QK3[] b - - 0 1", Arc::new(Crazyhouse));
+    let mut chess_board2: ChessBoard = board("3rk3/8/8/8/8/8/8/3Q~K3[] b - - 0 1", Arc::new(Crazyhouse));
+    chess_board.drag(0o73, 0o03);
+    chess_board2.drag(0o73, 0o03);
+    assert!(chess_board.get_pocket(false) == [ChessPieceType::Queen]);
+    assert!(chess_board2.get_pocket(false) == [ChessPieceType::Pawn]);
+
+    // A drop can block a check, so this is no checkmate
+    let chess_board: ChessBoard = board("4k3/8/8/8/8/8/5PPP/r5K1[N] w - - 0 1", Arc::new(Crazyhouse));
+    assert!(chess_board.get_state() == ChessState::On);
+
+    assert!(ChessBoard::from_fen("4k3/8/8/8/8/8/8/4K3[N] w - - 0 1").err() == Some(FenError::InvalidPocket));
+    assert!(ChessBoard::from_fen_variant("4k3/8/8/8/8/8/8/4K3[K] w - - 0 1", Arc::new(Crazyhouse)).is_err());
+}
+
+#[test]
+fn test_variant_bughouse()
+{
+    let mut game: BughouseGame = BughouseGame::new();
+
+    // 1. e4 d5 2. exd5 on the first board
+    game.drag(0, 0o14, 0o34);
+    game.drag(0, 0o63, 0o43);
+    game.drag(0, 0o34, 0o43);
+
+    // The pawn goes to the partner, black on the second board
+    assert!(game.get_board(0).get_pocket(true).is_empty());
+    assert!(game.get_board(1).get_pocket(false) == [ChessPieceType::Pawn]);
+
+    game.drag(1, 0o14, 0o34);
+    game.drop_piece(1, ChessPieceType::Pawn, 0o44);
+    assert!(game.get_board(1).get_piece(0o44) == ChessPiece::BPawn);
+    assert!(game.get_state() == ChessState::On);
+}
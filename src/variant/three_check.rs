@@ -0,0 +1,33 @@
+use super::*;
+
+// Giving check for the third time wins the game
+pub struct ThreeCheck;
+
+impl Variant for ThreeCheck
+{
+    fn name(&self) -> &str
+    {
+        return "Three-check";
+    }
+
+    fn check_state(&self, board: &mut ChessBoard) -> Option<(ChessState, Option<bool>)>
+    {
+        let checks: [u8; 2] = board.get_checks();
+        if checks[0] >= 3
+        {
+            return Some((ChessState::VariantWin, Some(true)));
+        }
+
+        if checks[1] >= 3
+        {
+            return Some((ChessState::VariantWin, Some(false)));
+        }
+
+        return None;
+    }
+
+    fn counts_checks(&self) -> bool
+    {
+        return true;
+    }
+}
@@ -0,0 +1,165 @@
+// JavaScript bindings with the `wasm` feature, built for
+// `wasm32-unknown-unknown` with `wasm-bindgen`. The crate is an `rlib`, the
+// module for the browser comes from
+// `cargo rustc --lib --release --target wasm32-unknown-unknown --features wasm --crate-type cdylib`
+//
+// JavaScript gets a `Game` class on top of `game::Game`. Moves are strings
+// in UCI notation or SAN and blocks are written like `e4`, so the page
+// doesn't need to know how the board is numbered
+use super::*;
+use wasm_bindgen::prelude::*;
+
+#[cfg(test)]
+mod tests;
+
+#[wasm_bindgen(js_name = Game)]
+pub struct WasmGame
+{
+    game: game::Game,
+}
+
+#[wasm_bindgen(js_class = Game)]
+impl WasmGame
+{
+    // A game of default chess from the start position
+    #[wasm_bindgen(constructor)]
+    pub fn new() -> WasmGame
+    {
+        return WasmGame { game: game::Game::new() };
+    }
+
+    // A game from the FEN, of the variant if given, like `"crazyhouse"`.
+    // `undefined` if either of them can't be read
+    #[wasm_bindgen(js_name = fromFen)]
+    pub fn from_fen(fen: &str, variant: Option<String>) -> Option<WasmGame>
+    {
+        let variant: Arc<dyn variant::Variant> = variant::from_name(variant.as_deref().unwrap_or("standard"))?;
+        let board: ChessBoard = ChessBoard::from_fen_variant(fen, variant).ok()?;
+
+        return Some(WasmGame { game: game::Game::from_board(board) });
+    }
+
+    // Plays a move like `e2e4`, false if it isn't legal
+    #[wasm_bindgen(js_name = playUci)]
+    pub fn play_uci(&mut self, uci: &str) -> bool
+    {
+        return self.game.play_uci(uci);
+    }
+
+    // Plays a move like `Nf3`, false if it isn't legal
+    #[wasm_bindgen(js_name = playSan)]
+    pub fn play_san(&mut self, san: &str) -> bool
+    {
+        return self.game.play_san(san);
+    }
+
+    // Takes the last move back, false if there's none
+    pub fn undo(&mut self) -> bool
+    {
+        return self.game.undo();
+    }
+
+    // The legal moves in UCI notation
+    #[wasm_bindgen(js_name = legalMoves)]
+    pub fn legal_moves(&self) -> Vec<String>
+    {
+        let mut board: ChessBoard = self.game.get_board().clone();
+        return board.legal_moves().into_iter().map(|mv| board.to_uci(mv)).collect();
+    }
+
+    // The blocks the piece on the block can move to, empty if it can't or
+    // the block doesn't exist
+    #[wasm_bindgen(js_name = legalDestinations)]
+    pub fn legal_destinations(&self, square: &str) -> Vec<String>
+    {
+        let mut board: ChessBoard = self.game.get_board().clone();
+        let from: u8 = match board.read_coords(square)
+        {
+            Some(from) => from,
+            None => return Vec::new(),
+        };
+
+        // A promotion to every piece is still one destination
+        let mut destinations: Vec<String> = Vec::new();
+        for mv in board.legal_moves()
+        {
+            if let ChessMove::Drag { from: f, to, .. } = mv
+            {
+                let to: String = board.write_coords(to);
+                if f == from && !destinations.contains(&to)
+                {
+                    destinations.push(to);
+                }
+            }
+        }
+
+        return destinations;
+    }
+
+    pub fn fen(&self) -> String
+    {
+        return self.game.get_board().to_fen();
+    }
+
+    pub fn pgn(&self) -> String
+    {
+        return self.game.to_pgn();
+    }
+
+    // `"on"`, `"checkmate"`, `"stalemate"`, `"aborted"`, `"variantWin"`,
+    // `"variantDraw"`, or how the game ended otherwise: `"resigned"`,
+    // `"timeout"`, `"repetition"`, `"fiftyMoves"` and `"draw"`
+    pub fn state(&self) -> String
+    {
+        let state: &str = match (self.game.get_end(), self.game.get_board().get_state())
+        {
+            (Some(game::GameEnd::Resigned(_)), _) => "resigned",
+            (Some(game::GameEnd::Timeout(_)), _) => "timeout",
+            (Some(game::GameEnd::Repetition), _) => "repetition",
+            (Some(game::GameEnd::FiftyMoves), _) => "fiftyMoves",
+            (Some(game::GameEnd::Draw), _) => "draw",
+            (None, ChessState::On) => "on",
+            (None, ChessState::Checkmate) => "checkmate",
+            (None, ChessState::Stalemate) => "stalemate",
+            (None, ChessState::Aborted) => "aborted",
+            (None, ChessState::VariantWin) => "variantWin",
+            (None, ChessState::VariantDraw) => "variantDraw",
+        };
+
+        return state.to_string();
+    }
+
+    // `1-0`, `0-1`, `1/2-1/2`, or `*` while the game is on
+    pub fn result(&self) -> String
+    {
+        return self.game.result().to_string();
+    }
+
+    // If the color to move is in check
+    #[wasm_bindgen(js_name = isCheck)]
+    pub fn is_check(&self) -> bool
+    {
+        let board: &ChessBoard = self.game.get_board();
+        return board.is_check(board.white_turn());
+    }
+
+    #[wasm_bindgen(js_name = whiteTurn)]
+    pub fn white_turn(&self) -> bool
+    {
+        return self.game.get_board().white_turn();
+    }
+
+    // The color resigns, true for white
+    pub fn resign(&mut self, white: bool)
+    {
+        self.game.resign(white);
+    }
+}
+
+impl Default for WasmGame
+{
+    fn default() -> Self
+    {
+        return Self::new();
+    }
+}
@@ -0,0 +1,44 @@
+// The tests run natively with `cargo test --features wasm`, and in a wasm
+// runtime with `cargo test --lib --features wasm --target wasm32-unknown-unknown wasm`
+use super::*;
+#[cfg(target_arch = "wasm32")]
+use wasm_bindgen_test::wasm_bindgen_test;
+
+#[cfg_attr(not(target_arch = "wasm32"), test)]
+#[cfg_attr(target_arch = "wasm32", wasm_bindgen_test)]
+fn test_wasm_game()
+{
+    let mut game: WasmGame = WasmGame::new();
+    assert_eq!(game.legal_moves().len(), 20);
+    assert_eq!(game.legal_destinations("g1"), vec!["f3", "h3"]);
+    assert!(game.legal_destinations("e4").is_empty());
+    assert!(game.legal_destinations("z9").is_empty());
+
+    assert!(game.play_uci("f2f3"));
+    assert!(!game.play_uci("f3f4"));
+    assert!(game.play_san("e5"));
+    assert!(game.play_san("g4"));
+    assert!(game.play_san("Qh4#"));
+    assert_eq!(game.state(), "checkmate");
+    assert!(game.is_check());
+    assert_eq!(game.result(), "0-1");
+
+    assert!(game.undo());
+    assert_eq!(game.state(), "on");
+    assert!(!game.white_turn());
+    assert!(game.pgn().contains("1. f3 e5 2. g4 *"));
+}
+
+#[cfg_attr(not(target_arch = "wasm32"), test)]
+#[cfg_attr(target_arch = "wasm32", wasm_bindgen_test)]
+fn test_wasm_from_fen()
+{
+    let game: WasmGame = WasmGame::from_fen("4k3/1P6/8/8/8/8/8/4K3 w - - 0 1", None).unwrap();
+    assert_eq!(game.legal_destinations("b7"), vec!["b8"]);
+
+    let game: WasmGame = WasmGame::from_fen("4k3/8/8/8/8/8/8/4K3[Q] w - - 0 1", Some(String::from("crazyhouse"))).unwrap();
+    assert!(game.legal_moves().contains(&String::from("Q@d5")));
+
+    assert!(WasmGame::from_fen("4k3/8/8/8/8/8/8/4K3[Q] w - - 0 1", None).is_none());
+    assert!(WasmGame::from_fen("4k3/8/8/8/8/8/8/4K3 w - - 0 1", Some(String::from("shogi"))).is_none());
+}
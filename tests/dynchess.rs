@@ -0,0 +1,51 @@
+// `dynchess` needs `std`
+#![cfg(feature = "std")]
+#![allow(clippy::needless_return)]
+
+use std::io::Write;
+use std::process::{Child, Command, Stdio};
+use std::time::{Duration, Instant};
+
+// Runs `dynchess` with the arguments and the input, and gives back what
+// it printed. `None` if it didn't exit within the time
+fn run_dynchess(args: &[&str], input: &str, timeout: Duration) -> Option<String>
+{
+    let mut child: Child = Command::new(env!("CARGO_BIN_EXE_dynchess"))
+        .args(args)
+        .stdin(Stdio::piped())
+        .stdout(Stdio::piped())
+        .stderr(Stdio::null())
+        .spawn()
+        .unwrap();
+    child.stdin.take().unwrap().write_all(input.as_bytes()).unwrap();
+
+    let deadline: Instant = Instant::now() + timeout;
+    while child.try_wait().unwrap().is_none()
+    {
+        if Instant::now() >= deadline
+        {
+            child.kill().unwrap();
+            child.wait().unwrap();
+            return None;
+        }
+        std::thread::sleep(Duration::from_millis(20));
+    }
+
+    return Some(String::from_utf8(child.wait_with_output().unwrap().stdout).unwrap());
+}
+
+#[test]
+fn test_dynchess_game_over_on_engine_turn()
+{
+    // The mate ends the game on the turn of the engine, which must not
+    // search the finished board and still read the commands
+    let output: Option<String> = run_dynchess(
+        &["--ascii", "--fen", "6k1/5ppp/8/8/8/8/8/R5K1 w - - 0 1"],
+        "Ra8\nfen\nquit\n",
+        Duration::from_secs(10),
+    );
+
+    let output: String = output.expect("dynchess didn't exit");
+    assert!(output.contains("1-0"));
+    assert!(output.contains("R5k1/5ppp/8/8/8/8/8/6K1 b - - 1 1"));
+}